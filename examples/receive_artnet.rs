@@ -2,7 +2,7 @@ use artnet_protocol::*;
 use std::net::UdpSocket;
 
 fn main() {
-    let socket = UdpSocket::bind(("0.0.0.0", 6454)).unwrap();
+    let socket = UdpSocket::bind(("0.0.0.0", ARTNET_PORT)).unwrap();
 
     loop {
         let mut buffer = [0u8; 1024];
@@ -10,15 +10,12 @@ fn main() {
         let command = ArtCommand::from_buffer(&buffer[..length]).unwrap();
 
         println!("Received {:?}", command);
-        match command {
-            ArtCommand::Output(output) => {
-                println!(
-                    "port {:?} data: {:?}",
-                    u16::from(output.port_address),
-                    output.data
-                )
-            }
-            _ => {}
+        if let ArtCommand::Output(output) = command {
+            println!(
+                "port {:?} data: {:?}",
+                u16::from(output.port_address),
+                output.data
+            )
         }
     }
 }
@@ -2,15 +2,15 @@ use artnet_protocol::*;
 use std::net::{ToSocketAddrs, UdpSocket};
 
 fn main() {
-    let socket = UdpSocket::bind(("0.0.0.0", 6454)).unwrap();
-    let broadcast_addr = ("255.255.255.255", 6454)
+    let socket = UdpSocket::bind(("0.0.0.0", ARTNET_PORT)).unwrap();
+    let broadcast_addr = ("255.255.255.255", ARTNET_PORT)
         .to_socket_addrs()
         .unwrap()
         .next()
         .unwrap();
     socket.set_broadcast(true).unwrap();
     let buff = ArtCommand::Poll(Poll::default()).write_to_buffer().unwrap();
-    socket.send_to(&buff, &broadcast_addr).unwrap();
+    socket.send_to(&buff, broadcast_addr).unwrap();
 
     loop {
         let mut buffer = [0u8; 1024];
@@ -19,17 +19,15 @@ fn main() {
 
         println!("Received {:?}", command);
         match command {
-            ArtCommand::Poll(poll) => {
+            ArtCommand::Poll(_poll) => {
                 // This will most likely be our own poll request, as this is broadcast to all devices on the network
             }
-            ArtCommand::PollReply(reply) => {
+            ArtCommand::PollReply(_reply) => {
                 // This is an ArtNet node on the network. We can send commands to it like this:
-                let command = ArtCommand::Output(Output {
-                    data: vec![1, 2, 3, 4, 5].into(), // The data we're sending to the node
-                    ..Output::default()
-                });
+                // Send the data to port address 1 of the node
+                let command = ArtCommand::Output(Output::new(1, vec![1, 2, 3, 4, 5]));
                 let bytes = command.write_to_buffer().unwrap();
-                socket.send_to(&bytes, &addr).unwrap();
+                socket.send_to(&bytes, addr).unwrap();
             }
             _ => {}
         }
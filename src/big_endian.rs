@@ -0,0 +1,90 @@
+use crate::convert::Cursor;
+use crate::{convert::Convertable, Result};
+use alloc::vec::Vec;
+use byteorder::BigEndian;
+
+/// A 16 bit number that is sent big endian, e.g. the port counts of `Input` and `PollReply`.
+///
+/// Most numbers in Art-Net are little endian, this makes sure the byte order of these fields can not be mixed up:
+///
+/// ```
+/// use artnet_protocol::{ArtCommand, BigEndianU16, Input};
+///
+/// let input = Input {
+///     num_ports: BigEndianU16(2),
+///     ..Input::default()
+/// };
+/// let bytes = ArtCommand::Input(input).write_to_buffer().unwrap();
+/// assert_eq!(bytes[14..16], [0, 2]);
+/// ```
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct BigEndianU16(pub u16);
+
+impl From<u16> for BigEndianU16 {
+    fn from(value: u16) -> BigEndianU16 {
+        BigEndianU16(value)
+    }
+}
+
+impl From<BigEndianU16> for u16 {
+    fn from(value: BigEndianU16) -> u16 {
+        value.0
+    }
+}
+
+impl From<[u8; 2]> for BigEndianU16 {
+    fn from(bytes: [u8; 2]) -> BigEndianU16 {
+        BigEndianU16(u16::from_be_bytes(bytes))
+    }
+}
+
+impl From<BigEndianU16> for [u8; 2] {
+    fn from(value: BigEndianU16) -> [u8; 2] {
+        value.0.to_be_bytes()
+    }
+}
+
+impl<T> Convertable<T> for BigEndianU16 {
+    fn from_cursor(cursor: &mut Cursor<&[u8]>) -> Result<Self> {
+        Ok(BigEndianU16(cursor.read_u16::<BigEndian>()?))
+    }
+
+    fn write_to_buffer(&self, buffer: &mut Vec<u8>, _context: &T) -> Result<()> {
+        buffer.extend_from_slice(&self.0.to_be_bytes());
+        Ok(())
+    }
+
+    fn serialized_len(&self) -> usize {
+        2
+    }
+    #[cfg(test)]
+    fn get_test_value() -> Self {
+        BigEndianU16(0x1234)
+    }
+
+    #[cfg(test)]
+    fn is_equal(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_big_endian() {
+        let mut buffer = Vec::new();
+        Convertable::<()>::write_to_buffer(&BigEndianU16(256), &mut buffer, &()).unwrap();
+        assert_eq!(buffer, [1, 0]);
+
+        let parsed: BigEndianU16 =
+            Convertable::<()>::from_cursor(&mut Cursor::new(&[1, 0][..])).unwrap();
+        assert_eq!(parsed, BigEndianU16(256));
+    }
+}
@@ -0,0 +1,126 @@
+use crate::command::ARTNET_PROTOCOL_VERSION;
+use crate::{AddressCommand, Error, FixedName, Result};
+
+data_structure! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[doc = "ArtAddress is sent by a controller to remotely program a node, e.g. its name and Port-Addresses."]
+    #[doc = ""]
+    #[doc = "The `Default` implementation leaves every setting of the node unchanged, so only the fields that should be programmed need to be set."]
+    pub struct Address {
+        #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
+        pub version: [u8; 2],
+        #[doc = "Bits 14-8 of the 15 bit Port-Address are encoded into the bottom 7 bits of this field. The value is ignored unless bit 7 is set. Set to 0x00 to reset to the physical switch setting, or 0x7f for no change."]
+        pub net_switch: u8,
+        #[doc = "The bind index of the node that should be programmed. Set to 0 or 1 for the root device."]
+        pub bind_index: u8,
+        #[doc = "The null terminated short name of the node. A null string (the default) means no change."]
+        pub short_name: FixedName<18>,
+        #[doc = "The null terminated long name of the node. A null string (the default) means no change."]
+        pub long_name: FixedName<64>,
+        #[doc = "Bits 3-0 of the 15 bit Port-Address for each of the 4 possible input ports, encoded into the low nibble. The value is ignored unless bit 7 is set."]
+        pub sw_in: [u8; 4],
+        #[doc = "Bits 3-0 of the 15 bit Port-Address for each of the 4 possible output ports, encoded into the low nibble. The value is ignored unless bit 7 is set."]
+        pub sw_out: [u8; 4],
+        #[doc = "Bits 7-4 of the 15 bit Port-Address are encoded into the bottom 4 bits of this field. The value is ignored unless bit 7 is set."]
+        pub sub_switch: u8,
+        #[doc = "The sACN priority of the node, 0 - 200. A value of 255 means no change."]
+        pub acn_priority: u8,
+        #[doc = "The action that the node should perform"]
+        pub command: AddressCommand,
+    }
+}
+
+impl Default for Address {
+    fn default() -> Address {
+        Address {
+            version: ARTNET_PROTOCOL_VERSION,
+            net_switch: 0x7f,
+            bind_index: 0,
+            short_name: FixedName::default(),
+            long_name: FixedName::default(),
+            sw_in: [0x7f; 4],
+            sw_out: [0x7f; 4],
+            sub_switch: 0x7f,
+            acn_priority: 255,
+            command: AddressCommand::None,
+        }
+    }
+}
+
+impl Address {
+    /// Check that `command` is defined by the specification, and that commands for a single port use a port from 0 to 3.
+    ///
+    /// A port above 3 would be sent as a different port, because only the lowest 2 bits are sent. Serializing does not check this, so unknown commands can still be forwarded.
+    pub fn validate(&self) -> Result<()> {
+        if let AddressCommand::Unknown(command) = AddressCommand::from(u8::from(self.command)) {
+            return Err(Error::UnknownAddressCommand(command));
+        }
+        match self.command.port() {
+            Some(port) if port > 3 => Err(Error::FieldOutOfRange {
+                field: "command",
+                value: port,
+                max: 3,
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArtCommand;
+
+    #[test]
+    fn address_round_trip() {
+        let short_name = FixedName::new("Node").unwrap();
+        let command = ArtCommand::Address(Address {
+            net_switch: 0x81,
+            short_name,
+            command: AddressCommand::MergeLtp(1),
+            ..Address::default()
+        });
+        let bytes = command.write_to_buffer().unwrap();
+        assert_eq!(bytes.len(), 107);
+        assert_eq!(&bytes[8..14], &[0, 0x60, 0, 14, 0x81, 0]);
+        assert_eq!(&bytes[14..18], b"Node");
+        assert_eq!(&bytes[104..], &[0x7f, 255, 0x11]);
+
+        match ArtCommand::from_buffer(&bytes).unwrap() {
+            ArtCommand::Address(address) => {
+                assert_eq!(address.net_switch, 0x81);
+                assert_eq!(address.short_name, short_name);
+                assert_eq!(address.long_name.as_str(), Ok(""));
+                assert_eq!(address.sw_out, [0x7f; 4]);
+                assert_eq!(address.acn_priority, 255);
+                assert_eq!(address.command, AddressCommand::MergeLtp(1));
+            }
+            command => panic!("Expected an Address, got {:?}", command),
+        }
+    }
+
+    #[test]
+    fn validate_command() {
+        let address = |command| Address {
+            command,
+            ..Address::default()
+        };
+        assert!(address(AddressCommand::LedLocate).validate().is_ok());
+        assert!(address(AddressCommand::RdmEnable(3)).validate().is_ok());
+        // An Unknown that holds a defined value is sent as that value
+        assert!(address(AddressCommand::Unknown(0x02)).validate().is_ok());
+
+        assert!(matches!(
+            address(AddressCommand::Unknown(0x0d)).validate(),
+            Err(Error::UnknownAddressCommand(0x0d))
+        ));
+        assert!(matches!(
+            address(AddressCommand::MergeHtp(4)).validate(),
+            Err(Error::FieldOutOfRange {
+                field: "command",
+                value: 4,
+                max: 3
+            })
+        ));
+    }
+}
@@ -0,0 +1,107 @@
+use crate::command::output::{BigEndianLength, HasPayloadLen};
+use crate::convert::null_terminated_str;
+use crate::{command::ARTNET_PROTOCOL_VERSION, Error, Result};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::str::Utf8Error;
+
+data_structure! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[doc = "ArtCommand is used to send text based parameter commands, e.g. `SwoutText=Playback&`."]
+    #[doc = ""]
+    #[doc = "This is called `CommandText` to avoid confusion with the `ArtCommand` enum of this library."]
+    pub struct CommandText {
+        #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
+        pub version: [u8; 2],
+        #[doc = "The ESTA manufacturer code, big endian. Set to 0xffff for commands that all manufacturers should accept."]
+        pub esta_code: [u8; 2],
+        #[doc = "The length of the text, set by the artnet library itself"]
+        #[cfg_attr(feature = "serde", serde(skip))]
+        pub length: BigEndianLength<CommandText>,
+        #[remainder]
+        #[doc = "The null terminated ASCII text of the commands, at most 512 bytes"]
+        pub data: Vec<u8>,
+    }
+}
+
+impl CommandText {
+    /// Create a command packet with the given text. The text is null terminated automatically.
+    ///
+    /// Returns an error if the text and the null byte are longer than 512 bytes.
+    pub fn new(esta_code: [u8; 2], text: &str) -> Result<CommandText> {
+        let mut data = text.as_bytes().to_vec();
+        data.push(0);
+        if data.len() > 512 {
+            return Err(Error::MessageSizeInvalid {
+                message: data,
+                allowed_size: 0..513,
+            });
+        }
+        Ok(CommandText {
+            esta_code,
+            data,
+            ..CommandText::default()
+        })
+    }
+
+    /// The text of the commands, up to the first null byte
+    pub fn text(&self) -> core::result::Result<&str, Utf8Error> {
+        null_terminated_str(&self.data)
+    }
+}
+
+impl Default for CommandText {
+    fn default() -> CommandText {
+        CommandText {
+            version: ARTNET_PROTOCOL_VERSION,
+            esta_code: [0xff, 0xff],
+            length: BigEndianLength::default(),
+            data: vec![0],
+        }
+    }
+}
+
+impl HasPayloadLen for CommandText {
+    fn payload_len(&self) -> Result<usize> {
+        Ok(self.data.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArtCommand;
+
+    #[test]
+    fn parse_command_text() {
+        let packet = [
+            &[
+                65, 114, 116, 45, 78, 101, 116, 0, 0, 0x24, 0, 14, 0xff, 0xff, 0, 19,
+            ][..],
+            b"SwoutText=Playback\0",
+        ]
+        .concat();
+        let command = match ArtCommand::from_buffer(&packet).unwrap() {
+            ArtCommand::CommandText(command) => command,
+            command => panic!("Expected a CommandText, got {:?}", command),
+        };
+        assert_eq!(command.esta_code, [0xff, 0xff]);
+        assert_eq!(*command.length, 19);
+        assert_eq!(command.text(), Ok("SwoutText=Playback"));
+
+        let bytes = ArtCommand::CommandText(command).write_to_buffer().unwrap();
+        assert_eq!(bytes, packet);
+    }
+
+    #[test]
+    fn new_command_text() {
+        let command = CommandText::new([0xff, 0xff], "SwinText=Record").unwrap();
+        assert_eq!(command.text(), Ok("SwinText=Record"));
+        // The text and the null byte can be 512 bytes long
+        assert!(CommandText::new([0xff, 0xff], &"a".repeat(511)).is_ok());
+        assert!(matches!(
+            CommandText::new([0xff, 0xff], &"a".repeat(512)),
+            Err(Error::MessageSizeInvalid { allowed_size, .. }) if allowed_size == (0..513)
+        ));
+    }
+}
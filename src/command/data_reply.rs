@@ -0,0 +1,119 @@
+use crate::command::output::{BigEndianLength, HasPayloadLen};
+use crate::{command::ARTNET_PROTOCOL_VERSION, DataRequestType, Error, Oem, Result};
+use alloc::vec::Vec;
+
+data_structure! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[doc = "ArtDataReply is sent by a node in reply to an ArtDataRequest, and contains the requested product data."]
+    pub struct DataReply {
+        #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
+        pub version: [u8; 2],
+        #[doc = "The ESTA manufacturer code of the node, big endian"]
+        pub esta_code: [u8; 2],
+        #[doc = "The Oem code of the node, big endian"]
+        pub oem: [u8; 2],
+        #[doc = "The type of data that was requested, big endian. See `request_type`."]
+        pub request: [u8; 2],
+        #[doc = "The length of the payload, set by the artnet library itself"]
+        #[cfg_attr(feature = "serde", serde(skip))]
+        pub payload_length: BigEndianLength<DataReply>,
+        #[remainder]
+        #[doc = "The requested data, at most 512 bytes. URLs are null terminated ASCII text."]
+        pub payload: Vec<u8>,
+    }
+}
+
+impl DataReply {
+    /// Create a reply with the given data.
+    ///
+    /// Returns an error if the payload is longer than 512 bytes.
+    pub fn new(
+        esta_code: [u8; 2],
+        oem: impl Into<Oem>,
+        request_type: DataRequestType,
+        payload: &[u8],
+    ) -> Result<DataReply> {
+        if payload.len() > 512 {
+            return Err(Error::MessageSizeInvalid {
+                message: payload.to_vec(),
+                allowed_size: 0..513,
+            });
+        }
+        Ok(DataReply {
+            esta_code,
+            oem: oem.into().into(),
+            request: u16::from(request_type).to_be_bytes(),
+            payload: payload.to_vec(),
+            ..DataReply::default()
+        })
+    }
+
+    /// The type of data that was requested
+    pub fn request_type(&self) -> DataRequestType {
+        u16::from_be_bytes(self.request).into()
+    }
+
+    /// The requested data
+    pub fn payload(&self) -> &[u8] {
+        &self.payload
+    }
+}
+
+impl Default for DataReply {
+    fn default() -> DataReply {
+        DataReply {
+            version: ARTNET_PROTOCOL_VERSION,
+            esta_code: [0, 0],
+            oem: [0, 0],
+            request: [0, 0],
+            payload_length: BigEndianLength::default(),
+            payload: Vec::new(),
+        }
+    }
+}
+
+impl HasPayloadLen for DataReply {
+    fn payload_len(&self) -> Result<usize> {
+        Ok(self.payload.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArtCommand;
+
+    #[test]
+    fn parse_data_reply() {
+        let packet = [
+            &[
+                65, 114, 116, 45, 78, 101, 116, 0, 0, 0x28, 0, 14, 0x12, 0x34, 0x56, 0x78, 0, 1, 0,
+                20,
+            ][..],
+            b"https://example.com\0",
+        ]
+        .concat();
+        let reply = match ArtCommand::from_buffer(&packet).unwrap() {
+            ArtCommand::DataReply(reply) => reply,
+            command => panic!("Expected a DataReply, got {:?}", command),
+        };
+        assert_eq!(reply.request_type(), DataRequestType::UrlProduct);
+        assert_eq!(*reply.payload_length, 20);
+        assert_eq!(reply.payload(), b"https://example.com\0");
+
+        let bytes = ArtCommand::DataReply(reply).write_to_buffer().unwrap();
+        assert_eq!(bytes, packet);
+    }
+
+    #[test]
+    fn new_data_reply() {
+        let reply = DataReply::new([0, 0], [0, 0], DataRequestType::Poll, &[]).unwrap();
+        let bytes = ArtCommand::DataReply(reply).write_to_buffer().unwrap();
+        assert_eq!(bytes.len(), 20);
+        assert!(DataReply::new([0, 0], [0, 0], DataRequestType::Poll, &[0; 512]).is_ok());
+        assert!(matches!(
+            DataReply::new([0, 0], [0, 0], DataRequestType::Poll, &[0; 513]),
+            Err(Error::MessageSizeInvalid { allowed_size, .. }) if allowed_size == (0..513)
+        ));
+    }
+}
@@ -0,0 +1,82 @@
+use crate::{command::ARTNET_PROTOCOL_VERSION, DataRequestType, Oem};
+
+data_structure! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[doc = "ArtDataRequest is used to request product data from a node, e.g. the URL of its product page."]
+    pub struct DataRequest {
+        #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
+        pub version: [u8; 2],
+        #[doc = "The ESTA manufacturer code of the product, big endian"]
+        pub esta_code: [u8; 2],
+        #[doc = "The Oem code of the product, big endian"]
+        pub oem: [u8; 2],
+        #[doc = "The type of data that is requested, big endian. See `request_type`."]
+        pub request: [u8; 2],
+        #[doc(hidden)]
+        pub spare: [u8; 22],
+    }
+}
+
+impl DataRequest {
+    /// Create a request for the given type of data
+    pub fn new(
+        esta_code: [u8; 2],
+        oem: impl Into<Oem>,
+        request_type: DataRequestType,
+    ) -> DataRequest {
+        DataRequest {
+            esta_code,
+            oem: oem.into().into(),
+            request: u16::from(request_type).to_be_bytes(),
+            ..DataRequest::default()
+        }
+    }
+
+    /// The type of data that is requested
+    pub fn request_type(&self) -> DataRequestType {
+        u16::from_be_bytes(self.request).into()
+    }
+}
+
+impl Default for DataRequest {
+    fn default() -> DataRequest {
+        DataRequest {
+            version: ARTNET_PROTOCOL_VERSION,
+            esta_code: [0, 0],
+            oem: [0, 0],
+            request: [0, 0],
+            spare: [0; 22],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArtCommand;
+
+    #[test]
+    fn data_request_round_trip() {
+        let request = DataRequest::new([0x12, 0x34], [0x56, 0x78], DataRequestType::UrlSupport);
+        let bytes = ArtCommand::DataRequest(request).write_to_buffer().unwrap();
+        assert_eq!(bytes.len(), 40);
+        assert_eq!(
+            &bytes[..20],
+            &[
+                65, 114, 116, 45, 78, 101, 116, 0, 0, 0x27, 0, 14, 0x12, 0x34, 0x56, 0x78, 0, 3, 0,
+                0
+            ]
+        );
+
+        let request = match ArtCommand::from_buffer(&bytes).unwrap() {
+            ArtCommand::DataRequest(request) => request,
+            command => panic!("Expected a DataRequest, got {:?}", command),
+        };
+        assert_eq!(request.request_type(), DataRequestType::UrlSupport);
+        assert_eq!(request.esta_code, [0x12, 0x34]);
+        assert_eq!(
+            ArtCommand::DataRequest(request).write_to_buffer().unwrap(),
+            bytes
+        );
+    }
+}
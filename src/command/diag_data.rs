@@ -0,0 +1,102 @@
+use crate::command::output::{BigEndianLength, HasPayloadLen};
+use crate::convert::null_terminated_str;
+use crate::{command::ARTNET_PROTOCOL_VERSION, Result};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::str::Utf8Error;
+
+data_structure! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[doc = "ArtDiagData is a general purpose packet that allows a node or controller to send diagnostics data for display."]
+    pub struct DiagData {
+        #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
+        pub version: [u8; 2],
+        #[doc = "Ignore by receiver, set to zero by sender"]
+        pub filler1: u8,
+        #[doc = "The priority of this diagnostic data: 0x10 (low), 0x40 (medium), 0x80 (high), 0xe0 (critical) or 0xf0 (volatile)"]
+        pub priority: u8,
+        #[doc = "The logical DMX port of the product to which the message relates. Set to zero for general messages."]
+        pub logical_port: u8,
+        #[doc = "Ignore by receiver, set to zero by sender"]
+        pub filler3: u8,
+        #[doc = "The length of the text, set by the artnet library itself"]
+        #[cfg_attr(feature = "serde", serde(skip))]
+        pub length: BigEndianLength<DiagData>,
+        #[remainder]
+        #[doc = "The null terminated ASCII text of the diagnostic message"]
+        pub data: Vec<u8>,
+    }
+}
+
+impl DiagData {
+    /// Create a diagnostic message with the given priority. The text is null terminated automatically.
+    pub fn new(priority: u8, message: &str) -> DiagData {
+        let mut data = message.as_bytes().to_vec();
+        data.push(0);
+        DiagData {
+            priority,
+            data,
+            ..DiagData::default()
+        }
+    }
+
+    /// The text of the message, up to the first null byte
+    pub fn message(&self) -> core::result::Result<&str, Utf8Error> {
+        null_terminated_str(&self.data)
+    }
+}
+
+impl Default for DiagData {
+    fn default() -> DiagData {
+        DiagData {
+            version: ARTNET_PROTOCOL_VERSION,
+            filler1: 0,
+            priority: 0x10,
+            logical_port: 0,
+            filler3: 0,
+            length: BigEndianLength::default(),
+            data: vec![0],
+        }
+    }
+}
+
+impl HasPayloadLen for DiagData {
+    fn payload_len(&self) -> Result<usize> {
+        Ok(self.data.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArtCommand;
+
+    #[test]
+    fn parse_diag_data() {
+        let packet = [
+            &[
+                65, 114, 116, 45, 78, 101, 116, 0, 0, 0x23, 0, 14, 0, 0x80, 1, 0, 0, 13,
+            ][..],
+            b"Input failed\0",
+        ]
+        .concat();
+        let diag = match ArtCommand::from_buffer(&packet).unwrap() {
+            ArtCommand::DiagData(diag) => diag,
+            command => panic!("Expected a DiagData, got {:?}", command),
+        };
+        assert_eq!(diag.priority, 0x80);
+        assert_eq!(diag.logical_port, 1);
+        assert_eq!(*diag.length, 13);
+        assert_eq!(diag.message(), Ok("Input failed"));
+
+        let bytes = ArtCommand::DiagData(diag).write_to_buffer().unwrap();
+        assert_eq!(bytes, packet);
+    }
+
+    #[test]
+    fn new_terminates_message() {
+        let diag = DiagData::new(0x40, "Hello");
+        assert_eq!(diag.data, b"Hello\0");
+        assert_eq!(diag.message(), Ok("Hello"));
+    }
+}
@@ -0,0 +1,58 @@
+use crate::command::ARTNET_PROTOCOL_VERSION;
+
+data_structure! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[doc = "ArtDirectory is used to request the list of files that a node stores, e.g. show files."]
+    pub struct Directory {
+        #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
+        pub version: [u8; 2],
+        #[doc = "Pad length to match ArtPoll"]
+        pub filler1: u8,
+        #[doc = "Pad length to match ArtPoll"]
+        pub filler2: u8,
+        #[doc = "The directory command, which selects the file that the node replies with"]
+        pub command: u8,
+        #[doc = "The number of the file that is requested, big endian"]
+        pub file: [u8; 2],
+    }
+}
+
+impl Directory {
+    /// Create a request for the given file
+    pub fn new(command: u8, file: u16) -> Directory {
+        Directory {
+            command,
+            file: file.to_be_bytes(),
+            ..Directory::default()
+        }
+    }
+}
+
+impl Default for Directory {
+    fn default() -> Directory {
+        Directory {
+            version: ARTNET_PROTOCOL_VERSION,
+            filler1: 0,
+            filler2: 0,
+            command: 0,
+            file: [0, 0],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArtCommand;
+
+    #[test]
+    fn directory_round_trip() {
+        let command = ArtCommand::OpDirectory(Directory::new(1, 0x0102));
+        let bytes = command.write_to_buffer().unwrap();
+        assert_eq!(
+            bytes,
+            vec![65, 114, 116, 45, 78, 101, 116, 0, 0, 0x9a, 0, 14, 0, 0, 1, 1, 2]
+        );
+        assert_eq!(ArtCommand::from_buffer(&bytes).unwrap(), command);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::command::ARTNET_PROTOCOL_VERSION;
+use crate::convert::null_terminated_str;
+use core::str::Utf8Error;
+
+data_structure! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[doc = "ArtDirectoryReply is sent by a node as a reply to an ArtDirectory, and describes one of the files that it stores."]
+    pub struct DirectoryReply {
+        #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
+        pub version: [u8; 2],
+        #[doc = "Pad length to match ArtPoll"]
+        pub filler1: u8,
+        #[doc = "Pad length to match ArtPoll"]
+        pub filler2: u8,
+        #[doc = "Flags that describe the file"]
+        pub flags: u8,
+        #[doc = "The number of the file, big endian"]
+        pub file: [u8; 2],
+        #[doc = "The null terminated 8.3 file name"]
+        pub name: [u8; 16],
+        #[doc = "The null terminated description of the file"]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_array"))]
+        pub desc: [u8; 64],
+        #[doc = "The length of the file in bytes, big endian"]
+        pub length: [u8; 8],
+        #[doc = "Application specific data"]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_array"))]
+        pub data: [u8; 64],
+    }
+}
+
+impl DirectoryReply {
+    /// The file name, up to the first null byte and without trailing whitespace
+    pub fn name(&self) -> core::result::Result<&str, Utf8Error> {
+        null_terminated_str(&self.name).map(str::trim_end)
+    }
+
+    /// The description of the file, up to the first null byte and without trailing whitespace
+    pub fn desc(&self) -> core::result::Result<&str, Utf8Error> {
+        null_terminated_str(&self.desc).map(str::trim_end)
+    }
+}
+
+impl Default for DirectoryReply {
+    fn default() -> DirectoryReply {
+        DirectoryReply {
+            version: ARTNET_PROTOCOL_VERSION,
+            filler1: 0,
+            filler2: 0,
+            flags: 0,
+            file: [0, 0],
+            name: [0; 16],
+            desc: [0; 64],
+            length: [0; 8],
+            data: [0; 64],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ArtCommand;
+
+    fn padded<const N: usize>(value: &[u8]) -> [u8; N] {
+        let mut field = [0; N];
+        field[..value.len()].copy_from_slice(value);
+        field
+    }
+
+    #[test]
+    fn parse_directory_reply() {
+        let packet = [
+            &[
+                65, 114, 116, 45, 78, 101, 116, 0, 0, 0x9b, 0, 14, 0, 0, 0x01, 0, 3,
+            ][..],
+            &padded::<16>(b"SHOW01.DAT  "),
+            &padded::<64>(b"Opening scene"),
+            &[0, 0, 0, 0, 0, 1, 0, 0],
+            &[0xaa; 64],
+        ]
+        .concat();
+        assert_eq!(packet.len(), 10 + 159);
+
+        let reply = match ArtCommand::from_buffer(&packet).unwrap() {
+            ArtCommand::OpDirectoryReply(reply) => reply,
+            command => panic!("Expected a DirectoryReply, got {:?}", command),
+        };
+        assert_eq!(reply.flags, 0x01);
+        assert_eq!(u16::from_be_bytes(reply.file), 3);
+        assert_eq!(reply.name(), Ok("SHOW01.DAT"));
+        assert_eq!(reply.desc(), Ok("Opening scene"));
+        assert_eq!(u64::from_be_bytes(reply.length), 65_536);
+        assert_eq!(reply.data, [0xaa; 64]);
+
+        let bytes = ArtCommand::OpDirectoryReply(reply)
+            .write_to_buffer()
+            .unwrap();
+        assert_eq!(bytes, packet);
+    }
+}
@@ -0,0 +1,57 @@
+use crate::command::ARTNET_PROTOCOL_VERSION;
+
+data_structure! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[doc = "ArtFileFnReply is sent by a controller to acknowledge the blocks of an ArtFileFnMaster download."]
+    pub struct FileFnReply {
+        #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
+        pub version: [u8; 2],
+        #[doc = "Pad length to match ArtPoll"]
+        pub filler1: u8,
+        #[doc = "Pad length to match ArtPoll"]
+        pub filler2: u8,
+        #[doc = "Whether the last block was received (0x00), the whole file was received (0x01), or the download failed (0xff)"]
+        pub block_type: u8,
+        #[doc(hidden)]
+        pub spare: [u8; 21],
+    }
+}
+
+impl FileFnReply {
+    /// The `block_type` that acknowledges a block
+    pub const BLOCK_GOOD: u8 = 0x00;
+    /// The `block_type` that acknowledges the whole file
+    pub const ALL_GOOD: u8 = 0x01;
+    /// The `block_type` that aborts the download
+    pub const FAIL: u8 = 0xff;
+}
+
+impl Default for FileFnReply {
+    fn default() -> FileFnReply {
+        FileFnReply {
+            version: ARTNET_PROTOCOL_VERSION,
+            filler1: 0,
+            filler2: 0,
+            block_type: FileFnReply::BLOCK_GOOD,
+            spare: [0; 21],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArtCommand;
+
+    #[test]
+    fn file_fn_reply_round_trip() {
+        let command = ArtCommand::FileFnReply(FileFnReply {
+            block_type: FileFnReply::ALL_GOOD,
+            ..FileFnReply::default()
+        });
+        let bytes = command.write_to_buffer().unwrap();
+        assert_eq!(bytes.len(), 36);
+        assert_eq!(&bytes[8..15], &[0, 0xf6, 0, 14, 0, 0, 0x01]);
+        assert_eq!(ArtCommand::from_buffer(&bytes).unwrap(), command);
+    }
+}
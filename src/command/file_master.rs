@@ -0,0 +1,198 @@
+use crate::command::ARTNET_PROTOCOL_VERSION;
+use crate::{Error, Result};
+use alloc::vec::Vec;
+
+/// Define a file transfer packet. ArtFileTnMaster and ArtFileFnMaster have the same layout, and only differ in the direction of the transfer.
+///
+/// Every packet is defined in its own module, see `data_structure!`.
+macro_rules! file_master {
+    ($module:ident, $name:ident, $(#[doc = $doc:literal])*) => {
+        data_structure! {
+            mod $module;
+            #[derive(Debug, Clone, PartialEq, Eq)]
+            $(#[doc = $doc])*
+            pub struct $name {
+                #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
+                pub version: [u8; 2],
+                #[doc = "Pad length to match ArtPoll"]
+                pub filler1: u8,
+                #[doc = "Pad length to match ArtPoll"]
+                pub filler2: u8,
+                #[doc = "Whether this is the first block (0x00), a following block (0x01) or the last block (0x02) of the file"]
+                pub block_type: u8,
+                #[doc = "The number of this block, starting at 0"]
+                pub block_id: u8,
+                #[doc = "The length of the whole file in bytes, big endian"]
+                pub file_size: [u8; 4],
+                #[doc = "The 16 bit additive checksum of the whole file, big endian"]
+                pub file_sum: [u8; 2],
+                #[doc(hidden)]
+                pub spare: [u8; 18],
+                #[doc = "The data of this block. The last block is padded with zeros."]
+                #[cfg_attr(feature = "serde", serde(with = "crate::serde_array"))]
+                pub data: [u8; 512],
+            }
+        }
+
+        impl $name {
+            /// The `block_type` of the first block
+            pub const FIRST: u8 = 0x00;
+            /// The `block_type` of every block between the first and the last block
+            pub const CONTINUE: u8 = 0x01;
+            /// The `block_type` of the last block. A file with one block only has a last block.
+            pub const LAST: u8 = 0x02;
+            /// The number of bytes of the file in every block
+            pub const BLOCK_LEN: usize = 512;
+
+            /// Split a file into numbered blocks, with the file size and checksum set in every block.
+            ///
+            /// Returns an error if the file does not fit in 256 blocks of 512 bytes.
+            pub fn blocks(file: &[u8]) -> Result<Vec<$name>> {
+                const MAX_LEN: usize = 256 * $name::BLOCK_LEN;
+                if file.len() > MAX_LEN {
+                    return Err(Error::MessageSizeInvalid {
+                        message: file.to_vec(),
+                        allowed_size: 0..MAX_LEN + 1,
+                    });
+                }
+                let file_size = (file.len() as u32).to_be_bytes();
+                let file_sum = file
+                    .iter()
+                    .fold(0u16, |sum, &byte| sum.wrapping_add(u16::from(byte)))
+                    .to_be_bytes();
+                let count = file.len().div_ceil($name::BLOCK_LEN).max(1);
+                Ok((0..count)
+                    .map(|index| {
+                        let start = (index * $name::BLOCK_LEN).min(file.len());
+                        let end = (start + $name::BLOCK_LEN).min(file.len());
+                        let mut data = [0; 512];
+                        data[..end - start].copy_from_slice(&file[start..end]);
+                        $name {
+                            block_type: match index {
+                                index if index + 1 == count => $name::LAST,
+                                0 => $name::FIRST,
+                                _ => $name::CONTINUE,
+                            },
+                            block_id: index as u8,
+                            file_size,
+                            file_sum,
+                            data,
+                            ..$name::default()
+                        }
+                    })
+                    .collect())
+            }
+
+            /// The length of the whole file in bytes
+            pub fn file_len(&self) -> u32 {
+                u32::from_be_bytes(self.file_size)
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> $name {
+                $name {
+                    version: ARTNET_PROTOCOL_VERSION,
+                    filler1: 0,
+                    filler2: 0,
+                    block_type: 0,
+                    block_id: 0,
+                    file_size: [0; 4],
+                    file_sum: [0; 2],
+                    spare: [0; 18],
+                    data: [0; 512],
+                }
+            }
+        }
+    };
+}
+
+file_master!(
+    tn_master,
+    FileTnMaster,
+    #[doc = "ArtFileTnMaster is sent by a controller to upload a user file to a node, in blocks of 512 bytes."]
+    #[doc = ""]
+    #[doc = "The node acknowledges every block with an ArtFirmwareReply, like a firmware upload, which is not implemented yet, see `ArtCommand::FirmwareReply`."]
+    #[doc = "[FileFnReply](struct.FileFnReply.html) is only used for downloads with [FileFnMaster](struct.FileFnMaster.html)."]
+    #[doc = ""]
+    #[doc = "Use [FileTnMaster::blocks](#method.blocks) to split a file into packets."]
+);
+
+file_master!(
+    fn_master,
+    FileFnMaster,
+    #[doc = "ArtFileFnMaster is sent by a node to download a user file to a controller, in blocks of 512 bytes. The controller acknowledges every block with an [ArtFileFnReply](struct.FileFnReply.html)."]
+    #[doc = ""]
+    #[doc = "The layout is the same as [FileTnMaster](struct.FileTnMaster.html). Use [FileFnMaster::blocks](#method.blocks) to split a file into packets."]
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArtCommand;
+
+    #[test]
+    fn two_block_transfer() {
+        let file: Vec<u8> = (0..700).map(|i| i as u8).collect();
+        let blocks = FileTnMaster::blocks(&file).unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].block_type, FileTnMaster::FIRST);
+        assert_eq!(blocks[1].block_type, FileTnMaster::LAST);
+        assert_eq!(blocks[1].file_len(), 700);
+
+        let mut received = Vec::new();
+        for (index, block) in blocks.into_iter().enumerate() {
+            let bytes = ArtCommand::FileTnMaster(Box::new(block.clone()))
+                .write_to_buffer()
+                .unwrap();
+            assert_eq!(bytes.len(), 552);
+            let parsed = match ArtCommand::from_buffer(&bytes).unwrap() {
+                ArtCommand::FileTnMaster(parsed) => *parsed,
+                command => panic!("Expected a FileTnMaster, got {:?}", command),
+            };
+            assert_eq!(parsed, block);
+            assert_eq!(usize::from(parsed.block_id), index);
+            received.extend_from_slice(&parsed.data);
+        }
+        received.truncate(700);
+        assert_eq!(received, file);
+
+        assert_eq!(
+            FileTnMaster::blocks(&[]).unwrap()[0].block_type,
+            FileTnMaster::LAST
+        );
+        assert!(FileTnMaster::blocks(&[0; 256 * 512 + 1]).is_err());
+    }
+
+    #[test]
+    fn file_fn_master_round_trip() {
+        let blocks = FileFnMaster::blocks(&[1, 2, 3]).unwrap();
+        assert_eq!(blocks.len(), 1);
+        let command = ArtCommand::FileFnMaster(Box::new(FileFnMaster {
+            block_id: 4,
+            ..blocks[0].clone()
+        }));
+        let bytes = command.write_to_buffer().unwrap();
+        assert_eq!(
+            &bytes[8..22],
+            &[
+                0,
+                0xf5,
+                0,
+                14,
+                0,
+                0,
+                FileFnMaster::LAST,
+                4,
+                0,
+                0,
+                0,
+                3,
+                0,
+                6
+            ]
+        );
+        assert_eq!(&bytes[40..43], &[1, 2, 3]);
+        assert_eq!(ArtCommand::from_buffer(&bytes).unwrap(), command);
+    }
+}
@@ -0,0 +1,56 @@
+use crate::command::ARTNET_PROTOCOL_VERSION;
+use crate::BigEndianU16;
+
+data_structure! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[doc = "ArtInput is sent by a controller to enable or disable the DMX inputs of a node."]
+    pub struct Input {
+        #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
+        pub version: [u8; 2],
+        #[doc = "Pad length to match ArtPoll"]
+        pub filler1: u8,
+        #[doc = "The bind index of the node that should be programmed. Set to 0 or 1 for the root device."]
+        pub bind_index: u8,
+        #[doc = "The number of input ports. The maximum value is 4."]
+        pub num_ports: BigEndianU16,
+        #[doc = "One byte per input port. Setting bit 0 disables the port, clearing it enables the port."]
+        pub input: [u8; 4],
+    }
+}
+
+impl Default for Input {
+    fn default() -> Input {
+        Input {
+            version: ARTNET_PROTOCOL_VERSION,
+            filler1: 0,
+            bind_index: 0,
+            num_ports: BigEndianU16(4),
+            input: [0; 4],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArtCommand;
+
+    #[test]
+    fn disable_second_port() {
+        let mut input = Input::default();
+        input.input[1] = 0x01;
+        let bytes = ArtCommand::Input(input).write_to_buffer().unwrap();
+        assert_eq!(
+            bytes,
+            vec![65, 114, 116, 45, 78, 101, 116, 0, 0, 0x70, 0, 14, 0, 0, 0, 4, 0, 1, 0, 0]
+        );
+
+        match ArtCommand::from_buffer(&bytes).unwrap() {
+            ArtCommand::Input(input) => {
+                assert_eq!(input.num_ports, BigEndianU16(4));
+                assert_eq!(input.input, [0, 1, 0, 0]);
+            }
+            command => panic!("Expected an Input, got {:?}", command),
+        }
+    }
+}
@@ -0,0 +1,88 @@
+use crate::command::ARTNET_PROTOCOL_VERSION;
+use crate::IpProgCommand;
+use core::net::Ipv4Addr;
+
+data_structure! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[doc = "ArtIpProg is sent by a controller to reprogram the IP address, subnet mask and default gateway of a node."]
+    #[doc = ""]
+    #[doc = "The node replies with an ArtIpProgReply."]
+    pub struct IpProg {
+        #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
+        pub version: [u8; 2],
+        #[doc = "Pad length to match ArtPoll"]
+        pub filler1: u8,
+        #[doc = "Pad length to match ArtPoll"]
+        pub filler2: u8,
+        #[doc = "Determines what should be programmed"]
+        pub command: IpProgCommand,
+        #[doc = "Set to zero"]
+        pub filler4: u8,
+        #[doc = "The IP address to be programmed into the node"]
+        pub prog_ip: Ipv4Addr,
+        #[doc = "The subnet mask to be programmed into the node"]
+        pub prog_sm: Ipv4Addr,
+        #[doc = "The port to be programmed into the node, big endian. This is deprecated"]
+        pub prog_port: [u8; 2],
+        #[doc = "The default gateway to be programmed into the node"]
+        pub prog_dg: Ipv4Addr,
+        #[doc(hidden)]
+        pub spare: [u8; 4],
+    }
+}
+
+impl Default for IpProg {
+    fn default() -> IpProg {
+        IpProg {
+            version: ARTNET_PROTOCOL_VERSION,
+            filler1: 0,
+            filler2: 0,
+            command: IpProgCommand::NONE,
+            filler4: 0,
+            prog_ip: Ipv4Addr::UNSPECIFIED,
+            prog_sm: Ipv4Addr::UNSPECIFIED,
+            prog_port: [0; 2],
+            prog_dg: Ipv4Addr::UNSPECIFIED,
+            spare: [0; 4],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArtCommand;
+
+    #[test]
+    fn ip_prog_round_trip() {
+        let command = ArtCommand::OpIpProg(IpProg {
+            command: IpProgCommand::ENABLE_PROGRAMMING
+                | IpProgCommand::PROGRAM_IP
+                | IpProgCommand::PROGRAM_SUBNET_MASK,
+            prog_ip: Ipv4Addr::new(10, 0, 0, 5),
+            prog_sm: Ipv4Addr::new(255, 0, 0, 0),
+            ..IpProg::default()
+        });
+        let bytes = command.write_to_buffer().unwrap();
+        assert_eq!(bytes.len(), 34);
+        assert_eq!(
+            &bytes[8..26],
+            &[0, 0xf8, 0, 14, 0, 0, 0x86, 0, 10, 0, 0, 5, 255, 0, 0, 0, 0, 0]
+        );
+
+        match ArtCommand::from_buffer(&bytes).unwrap() {
+            ArtCommand::OpIpProg(prog) => {
+                assert_eq!(
+                    prog.command,
+                    IpProgCommand::ENABLE_PROGRAMMING
+                        | IpProgCommand::PROGRAM_IP
+                        | IpProgCommand::PROGRAM_SUBNET_MASK
+                );
+                assert_eq!(prog.prog_ip, Ipv4Addr::new(10, 0, 0, 5));
+                assert_eq!(prog.prog_sm, Ipv4Addr::new(255, 0, 0, 0));
+                assert_eq!(prog.prog_dg, Ipv4Addr::UNSPECIFIED);
+            }
+            command => panic!("Expected an IpProg, got {:?}", command),
+        }
+    }
+}
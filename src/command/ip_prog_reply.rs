@@ -0,0 +1,86 @@
+use crate::command::ARTNET_PROTOCOL_VERSION;
+use core::net::Ipv4Addr;
+
+data_structure! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[doc = "ArtIpProgReply is sent by a node in response to an ArtIpProg, and contains the current network configuration of the node."]
+    pub struct IpProgReply {
+        #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
+        pub version: [u8; 2],
+        #[doc = "Pad length to match ArtPoll"]
+        pub filler: [u8; 4],
+        #[doc = "The IP address of the node"]
+        pub prog_ip: Ipv4Addr,
+        #[doc = "The subnet mask of the node"]
+        pub prog_sm: Ipv4Addr,
+        #[doc = "The port of the node, big endian. This is deprecated"]
+        pub prog_port: [u8; 2],
+        #[doc = "Bit 6 is set if DHCP is enabled on the node"]
+        pub status: u8,
+        #[doc = "Transmit as zero"]
+        pub spare2: u8,
+        #[doc = "The default gateway of the node"]
+        pub prog_dg: Ipv4Addr,
+        #[doc(hidden)]
+        pub spare: [u8; 2],
+    }
+}
+
+impl IpProgReply {
+    /// Returns `true` if the node has DHCP enabled
+    pub fn dhcp_enabled(&self) -> bool {
+        self.status & 0b0100_0000 != 0
+    }
+}
+
+impl Default for IpProgReply {
+    fn default() -> IpProgReply {
+        IpProgReply {
+            version: ARTNET_PROTOCOL_VERSION,
+            filler: [0; 4],
+            prog_ip: Ipv4Addr::UNSPECIFIED,
+            prog_sm: Ipv4Addr::UNSPECIFIED,
+            prog_port: [0x19, 0x36],
+            status: 0,
+            spare2: 0,
+            prog_dg: Ipv4Addr::UNSPECIFIED,
+            spare: [0; 2],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArtCommand;
+
+    #[test]
+    fn ip_prog_reply_round_trip() {
+        let command = ArtCommand::OpIpProgReply(IpProgReply {
+            prog_ip: Ipv4Addr::new(2, 0, 0, 10),
+            prog_sm: Ipv4Addr::new(255, 0, 0, 0),
+            status: 0b0100_0000,
+            prog_dg: Ipv4Addr::new(2, 0, 0, 1),
+            ..IpProgReply::default()
+        });
+        let bytes = command.write_to_buffer().unwrap();
+        assert_eq!(bytes.len(), 34);
+        assert_eq!(
+            &bytes[8..],
+            &[
+                0, 0xf9, 0, 14, 0, 0, 0, 0, 2, 0, 0, 10, 255, 0, 0, 0, 0x19, 0x36, 0x40, 0, 2, 0,
+                0, 1, 0, 0
+            ]
+        );
+
+        match ArtCommand::from_buffer(&bytes).unwrap() {
+            ArtCommand::OpIpProgReply(reply) => {
+                assert_eq!(reply.prog_ip, Ipv4Addr::new(2, 0, 0, 10));
+                assert_eq!(reply.prog_sm, Ipv4Addr::new(255, 0, 0, 0));
+                assert_eq!(reply.prog_dg, Ipv4Addr::new(2, 0, 0, 1));
+                assert!(reply.dhcp_enabled());
+            }
+            command => panic!("Expected an IpProgReply, got {:?}", command),
+        }
+    }
+}
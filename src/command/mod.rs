@@ -1,21 +1,78 @@
+mod address;
+mod command_text;
+mod data_reply;
+mod data_request;
+mod diag_data;
+mod directory;
+mod directory_reply;
+mod file_fn_reply;
+mod file_master;
+mod input;
+mod ip_prog;
+mod ip_prog_reply;
+mod nzs;
+mod opaque;
+mod opcode;
 mod output;
 mod poll;
 mod poll_reply;
+mod sync;
+#[cfg(test)]
+mod tests;
+mod time_sync;
 mod timecode;
+mod tod_control;
+mod tod_data;
+mod tod_request;
+mod trigger;
+mod video_data;
+mod video_palette;
+mod video_setup;
 
-use crate::{Error, Result};
-use byteorder::{ByteOrder, LittleEndian, WriteBytesExt};
+use crate::{Error, ProtocolVersion, Result};
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+use core::convert::TryFrom;
+use core::fmt::Write;
 
-pub use self::output::{Output, PaddedData};
+pub use self::address::Address;
+pub use self::command_text::CommandText;
+pub use self::data_reply::DataReply;
+pub use self::data_request::DataRequest;
+pub use self::diag_data::DiagData;
+pub use self::directory::Directory;
+pub use self::directory_reply::DirectoryReply;
+pub use self::file_fn_reply::FileFnReply;
+pub use self::file_master::{FileFnMaster, FileTnMaster};
+pub use self::input::Input;
+pub use self::ip_prog::IpProg;
+pub use self::ip_prog_reply::IpProgReply;
+pub use self::nzs::Nzs;
+pub use self::opaque::{MacMaster, MacSlave, Media, MediaControl, MediaControlReply, MediaPatch};
+pub use self::opcode::OpCode;
+pub use self::output::{Channels, Output, OutputBuilder, OutputRef, PaddedData, Vlc};
 pub use self::poll::Poll;
 pub use self::poll_reply::PollReply;
+pub use self::sync::Sync;
+pub use self::time_sync::TimeSync;
 pub use self::timecode::Timecode;
+pub use self::tod_control::TodControl;
+pub use self::tod_data::TodData;
+pub use self::tod_request::TodRequest;
+pub use self::trigger::Trigger;
+pub use self::video_data::VideoData;
+pub use self::video_palette::VideoPalette;
+pub use self::video_setup::VideoSetup;
 
 /// The ArtCommand, to be used for ArtNet.
 ///
 /// This struct implements an `write_to_buffer` and `from_buffer` function, to be used with UDP connections.
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ArtCommand {
     /// A poll command, used to discover devices on the network
     Poll(Poll),
@@ -23,35 +80,41 @@ pub enum ArtCommand {
     /// A reply to the poll command, it contains device status information
     PollReply(Box<PollReply>),
 
-    /// [Not implemented] Diagnostics and data logging packet
-    DiagData,
+    /// Diagnostics and data logging packet
+    DiagData(DiagData),
 
-    /// [Not implemented] Used to send text based parameter commands
-    Command,
+    /// Used to send text based parameter commands
+    CommandText(CommandText),
+
+    /// Used to request product data from a node, like the URL of its product page
+    DataRequest(DataRequest),
+
+    /// The reply to a DataRequest, containing the requested product data
+    DataReply(DataReply),
 
     /// An ArtDmx data packet. Used to send actual data to a node in the network
     Output(Output),
 
-    /// [Not implemented] This is an ArtNzs data packet. It contains non-zero start code (except RDM) DMX512 information for a single Universe
-    Nzs,
+    /// This is an ArtNzs data packet. It contains non-zero start code (except RDM) DMX512 information for a single Universe
+    Nzs(Nzs),
 
-    /// [Not implemented] This is an ArtSync data packet. It is used to force synchronous transfer of ArtDmx packets to a node's output
-    Sync,
+    /// This is an ArtSync data packet. It is used to force synchronous transfer of ArtDmx packets to a node's output
+    Sync(Sync),
 
-    /// [Not implemented] This is an ArtAddress packet. It contains remote programming information for a Node.
-    Address,
+    /// This is an ArtAddress packet. It contains remote programming information for a Node.
+    Address(Address),
 
-    /// [Not implemented] This is an ArtInput packet. It contains enable – disable data for DMX inputs
-    Input,
+    /// This is an ArtInput packet. It contains enable – disable data for DMX inputs
+    Input(Input),
 
-    /// [Not implemented] This is an ArtTodRequest packet. It is used to request a Table of Devices (ToD) for RDM discovery.
-    TodRequest,
+    /// This is an ArtTodRequest packet. It is used to request a Table of Devices (ToD) for RDM discovery.
+    TodRequest(TodRequest),
 
-    /// [Not implemented] This is an ArtTodData packet. It is used to send a Table of Devices (ToD) for RDM discovery
-    TodData,
+    /// This is an ArtTodData packet. It is used to send a Table of Devices (ToD) for RDM discovery
+    TodData(TodData),
 
-    /// [Not implemented] This is an ArtTodControl packet. It is used to send RDM discovery control messages.
-    TodControl,
+    /// This is an ArtTodControl packet. It is used to send RDM discovery control messages.
+    TodControl(TodControl),
 
     /// [Not implemented] This is an ArtRdm packet. It is used to send all non discovery RDM messages
     Rdm,
@@ -59,20 +122,20 @@ pub enum ArtCommand {
     /// [Not implemented] This is an ArtRdmSub packet. It is used to send compressed, RDM Sub-Device data.
     RdmSub,
 
-    /// [Not implemented] This is an ArtVideoSetup packet. It contains video screen setup information for nodes that implement the extended video features.
-    VideoSetup,
+    /// This is an ArtVideoSetup packet. It contains video screen setup information for nodes that implement the extended video features.
+    VideoSetup(VideoSetup),
 
-    /// [Not implemented] This is an ArtVideoPalette packet. It contains colour palette setup information for nodes that implement the extended video features.
-    VideoPalette,
+    /// This is an ArtVideoPalette packet. It contains colour palette setup information for nodes that implement the extended video features.
+    VideoPalette(VideoPalette),
 
-    /// [Not implemented] This is an ArtVideoData packet. It contains display data for nodes that implement the extended video features.
-    VideoData,
+    /// This is an ArtVideoData packet. It contains display data for nodes that implement the extended video features.
+    VideoData(VideoData),
 
-    /// [Not implemented] This packet is deprecated
-    MacMaster,
+    /// This packet is deprecated. Its payload is kept as-is, because the layout is not documented.
+    MacMaster(MacMaster),
 
-    /// [Not implemented] This packet is deprecated
-    MacSlave,
+    /// This packet is deprecated. Its payload is kept as-is, because the layout is not documented.
+    MacSlave(MacSlave),
 
     /// [Not implemented] This is an ArtFirmwareMaster packet. It is used to upload new firmware or firmware extensions to the Node.
     FirmwareMaster,
@@ -80,78 +143,416 @@ pub enum ArtCommand {
     /// [Not implemented] This is an ArtFirmwareReply packet. It is returned by the node to acknowledge receipt of an ArtFirmwareMaster packet or ArtFileTnMaster packet.
     FirmwareReply,
 
-    /// [Not implemented] Uploads user file to node.
-    FileTnMaster,
+    /// Uploads user file to node.
+    FileTnMaster(Box<FileTnMaster>),
 
-    /// [Not implemented] Downloads user file from node
-    FileFnMaster,
+    /// Downloads user file from node
+    FileFnMaster(Box<FileFnMaster>),
 
-    /// [Not implemented] Server to Node acknowledge for download packets
-    FileFnReply,
+    /// Server to Node acknowledge for download packets
+    FileFnReply(FileFnReply),
 
-    /// [Not implemented] This is an ArtIpProg packet. It is used to reprogramme the IP address and Mask of the Node
-    OpIpProg,
+    /// This is an ArtIpProg packet. It is used to reprogramme the IP address and Mask of the Node
+    OpIpProg(IpProg),
 
-    /// [Not implemented] This is an ArtIpProgReply packet. It is returned by the node to acknowledge receipt of an ArtIpProg packet.
-    OpIpProgReply,
+    /// This is an ArtIpProgReply packet. It is returned by the node to acknowledge receipt of an ArtIpProg packet.
+    OpIpProgReply(IpProgReply),
 
-    /// [Not implemented] This is an ArtMedia packet. It is Unicast by a Media Server and acted upon by a Controller
-    OpMedia,
+    /// This is an ArtMedia packet. It is Unicast by a Media Server and acted upon by a Controller
+    OpMedia(Media),
 
-    /// [Not implemented] This is an ArtMediaPatch packet. It is Unicast by a Controller and acted upon by a Media Server
-    OpMediaPatch,
+    /// This is an ArtMediaPatch packet. It is Unicast by a Controller and acted upon by a Media Server
+    OpMediaPatch(MediaPatch),
 
-    /// [Not implemented] This is an ArtMediaControl packet. It is Unicast by a Controller and acted upon by a Media Server.
-    OpMediaControl,
+    /// This is an ArtMediaControl packet. It is Unicast by a Controller and acted upon by a Media Server.
+    OpMediaControl(MediaControl),
 
-    /// [Not implemented] This is an ArtMediaControlReply packet. It is Unicast by a Media Server and acted upon by a Controller
-    OpMediaControlReply,
+    /// This is an ArtMediaControlReply packet. It is Unicast by a Media Server and acted upon by a Controller
+    OpMediaControlReply(MediaControlReply),
 
-    /// [Not implemented] This is an ArtTimeCode packet. It is used to transport time code over the network
+    /// This is an ArtTimeCode packet. It is used to transport time code over the network
     OpTimeCode(Timecode),
 
-    /// [Not implemented] Used to synchronise real time date and clock
-    OpTimeSync,
+    /// Used to synchronise real time date and clock
+    OpTimeSync(TimeSync),
+
+    /// Used to send trigger macros
+    OpTrigger(Box<Trigger>),
 
-    /// [Not implemented] Used to send trigger macros
-    OpTrigger,
+    /// Requests a node's file list
+    OpDirectory(Directory),
 
-    /// [Not implemented] Requests a node's file list
-    OpDirectory,
+    /// Replies to OpDirectory with file list
+    OpDirectoryReply(DirectoryReply),
 
-    /// [Not implemented] Replies to OpDirectory with file list
-    OpDirectoryReply,
+    /// A message with an opcode that this library does not know. The payload is kept as-is, so the message can be forwarded unchanged.
+    Unknown {
+        /// The opcode of the message
+        opcode: u16,
+
+        /// Everything in the message after the opcode
+        payload: Vec<u8>,
+    },
+}
+
+/// A parsed ArtCommand that borrows from the buffer it was parsed from, see [ArtCommand::from_buffer_ref](enum.ArtCommand.html#method.from_buffer_ref).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArtCommandRef<'a> {
+    /// An ArtDmx data packet, with the data borrowed from the buffer
+    Output(OutputRef<'a>),
+
+    /// Any other command, which is parsed the same way as [ArtCommand::from_buffer](enum.ArtCommand.html#method.from_buffer)
+    Other(ArtCommand),
 }
 
 /// The ArtNet header. This is the first 8 bytes of each message, and contains the text "Art-Net\0"
 pub const ARTNET_HEADER: &[u8; 8] = b"Art-Net\0";
 
+/// Emit a warning for a buffer that could not be parsed. The opcode is only included if the buffer is long enough to contain one.
+#[cfg(feature = "tracing")]
+fn trace_parse_error(buffer: &[u8], error: &Error) {
+    let opcode = buffer.get(8..HEADER_LENGTH).map(LittleEndian::read_u16);
+    tracing::warn!(
+        opcode = ?opcode,
+        length = buffer.len(),
+        error = %error,
+        "Could not parse Art-Net packet"
+    );
+}
+
+/// The length of the header and the opcode that every message starts with
+const HEADER_LENGTH: usize = 10;
+
+/// The length of the shortest message that is parsed
+const MIN_BUFFER_LENGTH: usize = 14;
+
 /// The protocol version. Anything above [4, 0] seems to work for the devices that this library was tested on.
 ///
 /// If you need a different or configurable protocol version, please open a PR.
 pub const ARTNET_PROTOCOL_VERSION: [u8; 2] = [0, 14];
 
+/// The UDP port that all Art-Net traffic is sent to
+pub const ARTNET_PORT: u16 = 6454;
+
+/// Options that change how strict [ArtCommand::from_buffer_with](enum.ArtCommand.html#method.from_buffer_with) is when parsing a packet
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Reject packets with a protocol version below 14 with `Error::UnsupportedVersion`.
+    ///
+    /// Art-Net is backwards compatible, so these are accepted by default.
+    pub strict_version: bool,
+}
+
+/// The fields at the start of a packet, see [ArtCommand::parse_header](enum.ArtCommand.html#method.parse_header)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArtHeader {
+    /// The opcode of the packet, which can be an unknown opcode
+    pub opcode: u16,
+    /// The protocol version of the packet as a number, e.g. 14. This is `None` for an ArtPollReply and for unknown opcodes.
+    pub version: Option<u16>,
+}
+
 impl ArtCommand {
     /// Convert an ArtCommand in a byte buffer, which can be send to an UDP socket.
-    pub fn write_to_buffer(self) -> Result<Vec<u8>> {
+    pub fn write_to_buffer(&self) -> Result<Vec<u8>> {
         let mut result = Vec::new();
-        let (opcode, data) = self.get_opcode()?;
+        self.write_to(&mut result)?;
+        Ok(result)
+    }
 
-        // Append Art-Net\0 header
-        result.extend_from_slice(ARTNET_HEADER);
-        // Append the opcode of this enum
-        result
-            .write_u16::<LittleEndian>(opcode)
-            .map_err(Error::CursorEof)?;
+    /// Write this command into an existing buffer, which can be send to an UDP socket. The buffer is cleared first.
+    ///
+    /// Reusing the same buffer for every message avoids an allocation per message.
+    pub fn write_to(&self, buffer: &mut Vec<u8>) -> Result<()> {
+        buffer.clear();
+        self.append_to_buffer(buffer)
+    }
 
-        result.extend_from_slice(&data);
+    /// Append this command to the end of the buffer, e.g. to pack several commands in one datagram that is parsed with `from_buffer_multi`.
+    ///
+    /// If the command can not be serialized, the buffer is left unchanged.
+    pub fn append_to_buffer(&self, buffer: &mut Vec<u8>) -> Result<()> {
+        let start = buffer.len();
+        buffer.reserve(self.serialized_len());
 
-        Ok(result)
+        // Append Art-Net\0 header
+        buffer.extend_from_slice(ARTNET_HEADER);
+        // Reserve space for the opcode of this enum, which is known after writing the payload
+        buffer.extend_from_slice(&[0, 0]);
+
+        match self.write_payload(buffer) {
+            Ok(opcode) => {
+                buffer[start + 8..start + HEADER_LENGTH].copy_from_slice(&opcode.to_le_bytes());
+                Ok(())
+            }
+            Err(e) => {
+                buffer.truncate(start);
+                Err(e)
+            }
+        }
     }
 
     /// Convert an a byte buffer to a command.
+    ///
+    /// With the `tracing` feature, a warning is emitted for every buffer that can not be parsed.
     pub fn from_buffer(buffer: &[u8]) -> Result<ArtCommand> {
-        const MIN_BUFFER_LENGTH: usize = 14;
+        let result = ArtCommand::split_header(buffer).and_then(|(opcode, remaining)| {
+            ArtCommand::opcode_to_enum(opcode, remaining).map_err(|e| e.offset_by(HEADER_LENGTH))
+        });
+
+        #[cfg(feature = "tracing")]
+        if let Err(error) = &result {
+            trace_parse_error(buffer, error);
+        }
+
+        result
+    }
+
+    /// Convert a byte buffer that may not contain the whole packet yet, e.g. when reading from a stream.
+    ///
+    /// Returns `Error::Incomplete` if the buffer ends before the packet does, so the caller can wait for more bytes and try again.
+    /// Every other error is wrapped in `Error::Malformed`, and means that the buffer should be discarded.
+    /// The buffer may contain more bytes after the packet, use `serialized_len` of the result to find where the next packet starts.
+    pub fn try_from_buffer(buffer: &[u8]) -> Result<ArtCommand> {
+        let result = ArtCommand::parse_partial(buffer);
+
+        #[cfg(feature = "tracing")]
+        if let Err(Error::Malformed(error)) = &result {
+            trace_parse_error(buffer, error);
+        }
+
+        result
+    }
+
+    /// Read a single command from a stream, e.g. a TCP tunnel or a file, without buffering the stream first.
+    ///
+    /// This reads the header and the length field, and then the rest of the packet at once, so the next command can be read afterwards.
+    /// Packets without a length field are read with their fixed size.
+    /// Packets that have neither return `Error::UnknownPacketLength`, because their end can not be found in a stream. This includes `Poll` and `PollReply`,
+    /// which can be longer than the fields this crate parses, and `MacMaster` or a packet with an unknown opcode. The stream can not be read any further after this error.
+    #[cfg(feature = "std")]
+    pub fn from_reader<R: std::io::Read>(reader: &mut R) -> Result<ArtCommand> {
+        let mut buffer = alloc::vec![0; MIN_BUFFER_LENGTH];
+        reader.read_exact(&mut buffer).map_err(Error::Io)?;
+        let mut read_to = |buffer: &mut Vec<u8>, length: usize| {
+            let start = buffer.len();
+            if length > start {
+                buffer.resize(length, 0);
+                reader.read_exact(&mut buffer[start..]).map_err(Error::Io)?;
+            }
+            Ok(())
+        };
+
+        let (opcode, _) = ArtCommand::split_header(&buffer)?;
+        let length = match (
+            ArtCommand::length_field_end(opcode),
+            ArtCommand::fixed_length(opcode),
+        ) {
+            (Some(end), _) => {
+                read_to(&mut buffer, end)?;
+                ArtCommand::packet_length(&buffer)?.unwrap_or(end)
+            }
+            (None, Some(length)) => length,
+            (None, None) => return Err(Error::UnknownPacketLength(opcode)),
+        };
+        read_to(&mut buffer, length)?;
+        ArtCommand::from_buffer(&buffer)
+    }
+
+    fn parse_partial(buffer: &[u8]) -> Result<ArtCommand> {
+        ArtCommand::check_header(buffer).map_err(|e| Error::Malformed(Box::new(e)))?;
+        if buffer.len() < MIN_BUFFER_LENGTH {
+            return Err(Error::Incomplete {
+                needed: MIN_BUFFER_LENGTH - buffer.len(),
+            });
+        }
+        let length =
+            ArtCommand::packet_length(buffer).map_err(|e| Error::Malformed(Box::new(e)))?;
+        let buffer = match length {
+            Some(length) if length > buffer.len() => {
+                return Err(Error::Incomplete {
+                    needed: length - buffer.len(),
+                })
+            }
+            Some(length) => &buffer[..length],
+            None => {
+                let (opcode, _) =
+                    ArtCommand::split_header(buffer).map_err(|e| Error::Malformed(Box::new(e)))?;
+                let known =
+                    ArtCommand::length_field_end(opcode).or_else(|| ArtCommand::min_length(opcode));
+                if let Some(length) = known.filter(|&length| length > buffer.len()) {
+                    return Err(Error::Incomplete {
+                        needed: length - buffer.len(),
+                    });
+                }
+                buffer
+            }
+        };
+        ArtCommand::split_header(buffer)
+            .and_then(|(opcode, remaining)| ArtCommand::opcode_to_enum(opcode, remaining))
+            .map_err(|e| {
+                if e.is_cursor_eof() {
+                    Error::Incomplete { needed: 1 }
+                } else {
+                    Error::Malformed(Box::new(e.offset_by(HEADER_LENGTH)))
+                }
+            })
+    }
+
+    /// Convert a byte buffer to a command, using the given options.
+    ///
+    /// `from_buffer` is the same as this function with the default options.
+    pub fn from_buffer_with(buffer: &[u8], options: &ParseOptions) -> Result<ArtCommand> {
+        if options.strict_version {
+            let (opcode, remaining) = ArtCommand::split_header(buffer)?;
+            // ArtPollReply is the only packet that does not start with the protocol version
+            if !matches!(OpCode::from_u16(opcode), None | Some(OpCode::PollReply)) {
+                let version = BigEndian::read_u16(&remaining[..2]);
+                if version < ProtocolVersion::CURRENT.0 {
+                    return Err(Error::UnsupportedVersion { got: version });
+                }
+            }
+        }
+        ArtCommand::from_buffer(buffer)
+    }
+
+    /// Convert a byte buffer to a command, without copying the DMX512 data of an ArtDmx packet.
+    ///
+    /// This is useful when receiving a high rate of ArtDmx packets. All other commands are parsed like `from_buffer` does.
+    pub fn from_buffer_ref(buffer: &[u8]) -> Result<ArtCommandRef<'_>> {
+        let result = ArtCommand::split_header(buffer).and_then(|(opcode, remaining)| {
+            let command = match OpCode::from_u16(opcode) {
+                Some(OpCode::Output) => OutputRef::from(remaining)
+                    .map(ArtCommandRef::Output)
+                    .map_err(|e| Error::OpcodeError("Output", Box::new(e))),
+                _ => ArtCommand::opcode_to_enum(opcode, remaining).map(ArtCommandRef::Other),
+            };
+            command.map_err(|e| e.offset_by(HEADER_LENGTH))
+        });
+
+        #[cfg(feature = "tracing")]
+        if let Err(error) = &result {
+            trace_parse_error(buffer, error);
+        }
+
+        result
+    }
+
+    /// Convert a byte buffer that contains several concatenated packets to a list of commands.
+    ///
+    /// Packets with a length field, like `Output`, use it to find the start of the next packet. Packets with a fixed size, like `Sync`, take up that size.
+    /// Every other packet, e.g. `Poll`, `MacMaster` or a packet with an unknown opcode, ends where the next Art-Net header starts, or at the end of the buffer.
+    pub fn from_buffer_multi(buffer: &[u8]) -> Result<Vec<ArtCommand>> {
+        let mut commands = Vec::new();
+        let mut remaining = buffer;
+        while !remaining.is_empty() {
+            let length = match ArtCommand::packet_length(remaining)? {
+                Some(length) if length > remaining.len() => {
+                    return Err(Error::MessageTooShort {
+                        message: remaining.to_vec(),
+                        min_len: length,
+                    })
+                }
+                Some(length) => length,
+                None => {
+                    let (opcode, _) = ArtCommand::split_header(remaining)?;
+                    match ArtCommand::fixed_length(opcode) {
+                        Some(length) => length.min(remaining.len()),
+                        None => remaining[HEADER_LENGTH..]
+                            .windows(ARTNET_HEADER.len())
+                            .position(|window| window == ARTNET_HEADER)
+                            .map_or(remaining.len(), |start| HEADER_LENGTH + start),
+                    }
+                }
+            };
+            commands.push(ArtCommand::from_buffer(&remaining[..length])?);
+            remaining = &remaining[length..];
+        }
+        Ok(commands)
+    }
+
+    /// The length of a packet that always has the same size, including the header. Returns `None` for packets with a variable length.
+    ///
+    /// ArtPoll and ArtPollReply have grown with new versions of the specification, which tells receivers to accept longer packets, so their size is not fixed.
+    fn fixed_length(opcode: u16) -> Option<usize> {
+        let payload = match OpCode::from_u16(opcode)? {
+            OpCode::DataRequest => DataRequest::default().serialized_len(),
+            OpCode::Sync => Sync::default().serialized_len(),
+            OpCode::Address => Address::default().serialized_len(),
+            OpCode::Input => Input::default().serialized_len(),
+            OpCode::TodRequest => TodRequest::default().serialized_len(),
+            OpCode::TodControl => TodControl::default().serialized_len(),
+            OpCode::VideoPalette => VideoPalette::default().serialized_len(),
+            OpCode::FileTnMaster => FileTnMaster::default().serialized_len(),
+            OpCode::FileFnMaster => FileFnMaster::default().serialized_len(),
+            OpCode::FileFnReply => FileFnReply::default().serialized_len(),
+            OpCode::IpProg => IpProg::default().serialized_len(),
+            OpCode::IpProgReply => IpProgReply::default().serialized_len(),
+            OpCode::TimeCode => Timecode::default().serialized_len(),
+            OpCode::TimeSync => TimeSync::default().serialized_len(),
+            OpCode::Trigger => Trigger::default().serialized_len(),
+            OpCode::Directory => Directory::default().serialized_len(),
+            OpCode::DirectoryReply => DirectoryReply::default().serialized_len(),
+            _ => return None,
+        };
+        Some(HEADER_LENGTH + payload)
+    }
+
+    /// The length of a packet with a variable length, read from its length field. Returns `None` for packets with a fixed length,
+    /// and for buffers that end before the length field.
+    fn packet_length(buffer: &[u8]) -> Result<Option<usize>> {
+        let (opcode, _) = ArtCommand::split_header(buffer)?;
+        let end = match ArtCommand::length_field_end(opcode) {
+            Some(end) => end,
+            None => return Ok(None),
+        };
+        let header = match buffer.get(..end) {
+            Some(header) => header,
+            None => return Ok(None),
+        };
+        Ok(Some(match OpCode::from_u16(opcode) {
+            Some(OpCode::TodData) => end + 6 * header[27] as usize,
+            Some(OpCode::VideoData) => end + 2 * header[16] as usize * header[17] as usize,
+            _ => end + BigEndian::read_u16(&header[end - 2..]) as usize,
+        }))
+    }
+
+    /// The smallest length of a packet whose fields have a fixed size, including the header. This is the fixed length,
+    /// or the length of the fields that this crate parses for ArtPoll and ArtPollReply, which can be longer.
+    fn min_length(opcode: u16) -> Option<usize> {
+        let payload = match OpCode::from_u16(opcode)? {
+            OpCode::Poll => Poll::default().serialized_len(),
+            OpCode::PollReply => PollReply::default().serialized_len(),
+            _ => return ArtCommand::fixed_length(opcode),
+        };
+        Some(HEADER_LENGTH + payload)
+    }
+
+    /// The number of bytes up to and including the field that determines the length of a packet with a variable length.
+    fn length_field_end(opcode: u16) -> Option<usize> {
+        match OpCode::from_u16(opcode)? {
+            OpCode::Output | OpCode::Nzs | OpCode::DiagData | OpCode::VideoData => Some(18),
+            OpCode::Command => Some(16),
+            OpCode::DataReply => Some(20),
+            OpCode::TodData => Some(28),
+            _ => None,
+        }
+    }
+
+    /// Check that the buffer starts with the Art-Net ID, or with the start of it if the buffer is shorter.
+    fn check_header(buffer: &[u8]) -> Result<()> {
+        let start = &buffer[..buffer.len().min(ARTNET_HEADER.len())];
+        if ARTNET_HEADER.starts_with(start) {
+            return Ok(());
+        }
+        let mut found = [0; 8];
+        found[..start.len()].copy_from_slice(start);
+        Err(Error::InvalidHeader { found })
+    }
+
+    fn split_header(buffer: &[u8]) -> Result<(u16, &[u8])> {
+        // Check the header before anything else, so that a short non-artnet message is not reported as too short
+        ArtCommand::check_header(buffer)?;
 
         if buffer.len() < MIN_BUFFER_LENGTH {
             return Err(Error::MessageTooShort {
@@ -160,104 +561,554 @@ impl ArtCommand {
             });
         }
 
-        if !buffer.starts_with(ARTNET_HEADER) {
-            return Err(Error::InvalidArtnetHeader(buffer.to_vec()));
-        }
-
-        let opcode = LittleEndian::read_u16(&buffer[8..10]);
-        let remaining = &buffer[10..];
+        let opcode = LittleEndian::read_u16(&buffer[8..HEADER_LENGTH]);
+        Ok((opcode, &buffer[HEADER_LENGTH..]))
+    }
 
-        let command = ArtCommand::opcode_to_enum(opcode, remaining)?;
+    /// Read the OpCode of a byte buffer, without parsing the rest of the packet.
+    ///
+    /// This validates the Art-Net header, and returns an error if the OpCode is unknown.
+    pub fn peek_opcode(buffer: &[u8]) -> Result<OpCode> {
+        let (opcode, _) = ArtCommand::split_header(buffer)?;
+        OpCode::from_u16(opcode).ok_or(Error::UnknownOpcode(opcode))
+    }
 
-        Ok(command)
+    /// Read the opcode and protocol version of a byte buffer, without parsing the rest of the packet, e.g. to classify packets.
+    ///
+    /// The protocol version follows the opcode in every known packet except ArtPollReply, which sends the firmware version of the node there.
+    /// This validates the Art-Net header, and returns an error if the buffer is too short for the opcode, or for the version of a packet that has one.
+    pub fn parse_header(buffer: &[u8]) -> Result<ArtHeader> {
+        ArtCommand::check_header(buffer)?;
+        let opcode = buffer
+            .get(8..HEADER_LENGTH)
+            .map(LittleEndian::read_u16)
+            .ok_or_else(|| Error::MessageTooShort {
+                message: buffer.to_vec(),
+                min_len: HEADER_LENGTH,
+            })?;
+        let version = match OpCode::from_u16(opcode) {
+            None | Some(OpCode::PollReply) => None,
+            Some(_) => Some(
+                buffer
+                    .get(HEADER_LENGTH..HEADER_LENGTH + 2)
+                    .map(BigEndian::read_u16)
+                    .ok_or_else(|| Error::MessageTooShort {
+                        message: buffer.to_vec(),
+                        min_len: HEADER_LENGTH + 2,
+                    })?,
+            ),
+        };
+        Ok(ArtHeader { opcode, version })
     }
 
     fn opcode_to_enum(code: u16, data: &[u8]) -> Result<ArtCommand> {
-        Ok(match code {
-            0x2000 => ArtCommand::Poll(
+        let unknown = || ArtCommand::Unknown {
+            opcode: code,
+            payload: data.to_vec(),
+        };
+        let opcode = match OpCode::from_u16(code) {
+            Some(opcode) => opcode,
+            None => return Ok(unknown()),
+        };
+        Ok(match opcode {
+            OpCode::Poll => ArtCommand::Poll(
                 Poll::from(data).map_err(|e| Error::OpcodeError("Poll", Box::new(e)))?,
             ),
-            0x2100 => ArtCommand::PollReply(Box::new(
-                PollReply::from(data).map_err(|e| Error::OpcodeError("PollReply", Box::new(e)))?,
-            )),
-            0x2300 => ArtCommand::DiagData,
-            0x2400 => ArtCommand::Command,
-            0x5000 => ArtCommand::Output(
+            OpCode::PollReply => {
+                let reply = PollReply::from(data)
+                    .map_err(|e| Error::OpcodeError("PollReply", Box::new(e)))?;
+                #[cfg(feature = "tracing")]
+                if reply.raw_num_ports() > 4 {
+                    tracing::warn!(
+                        num_ports = reply.raw_num_ports(),
+                        "ArtPollReply reports more than 4 ports, only the first 4 are used"
+                    );
+                }
+                ArtCommand::PollReply(Box::new(reply))
+            }
+            OpCode::DiagData => ArtCommand::DiagData(
+                DiagData::from(data).map_err(|e| Error::OpcodeError("DiagData", Box::new(e)))?,
+            ),
+            OpCode::Command => ArtCommand::CommandText(
+                CommandText::from(data)
+                    .map_err(|e| Error::OpcodeError("CommandText", Box::new(e)))?,
+            ),
+            OpCode::DataRequest => ArtCommand::DataRequest(
+                DataRequest::from(data)
+                    .map_err(|e| Error::OpcodeError("DataRequest", Box::new(e)))?,
+            ),
+            OpCode::DataReply => ArtCommand::DataReply(
+                DataReply::from(data).map_err(|e| Error::OpcodeError("DataReply", Box::new(e)))?,
+            ),
+            OpCode::Output => ArtCommand::Output(
                 Output::from(data).map_err(|e| Error::OpcodeError("Output", Box::new(e)))?,
             ),
-            0x5100 => ArtCommand::Nzs,
-            0x5200 => ArtCommand::Sync,
-            0x6000 => ArtCommand::Address,
-            0x7000 => ArtCommand::Input,
-            0x8000 => ArtCommand::TodRequest,
-            0x8100 => ArtCommand::TodData,
-            0x8200 => ArtCommand::TodControl,
-            0x8300 => ArtCommand::Rdm,
-            0x8400 => ArtCommand::RdmSub,
-            0xA010 => ArtCommand::VideoSetup,
-            0xA020 => ArtCommand::VideoPalette,
-            0xA040 => ArtCommand::VideoData,
-            0xF000 => ArtCommand::MacMaster,
-            0xF100 => ArtCommand::MacSlave,
-            0xF200 => ArtCommand::FirmwareMaster,
-            0xF300 => ArtCommand::FirmwareReply,
-            0xF400 => ArtCommand::FileTnMaster,
-            0xF500 => ArtCommand::FileFnMaster,
-            0xF600 => ArtCommand::FileFnReply,
-            0xF800 => ArtCommand::OpIpProg,
-            0xF900 => ArtCommand::OpIpProgReply,
-            0x9000 => ArtCommand::OpMedia,
-            0x9100 => ArtCommand::OpMediaPatch,
-            0x9200 => ArtCommand::OpMediaControl,
-            0x9300 => ArtCommand::OpMediaControlReply,
-            0x9700 => ArtCommand::OpTimeCode(
+            OpCode::Nzs => ArtCommand::Nzs(
+                Nzs::from(data).map_err(|e| Error::OpcodeError("Nzs", Box::new(e)))?,
+            ),
+            OpCode::Sync => ArtCommand::Sync(
+                Sync::from(data).map_err(|e| Error::OpcodeError("Sync", Box::new(e)))?,
+            ),
+            OpCode::Address => ArtCommand::Address(
+                Address::from(data).map_err(|e| Error::OpcodeError("Address", Box::new(e)))?,
+            ),
+            OpCode::Input => ArtCommand::Input(
+                Input::from(data).map_err(|e| Error::OpcodeError("Input", Box::new(e)))?,
+            ),
+            OpCode::TodRequest => ArtCommand::TodRequest(
+                TodRequest::from(data)
+                    .map_err(|e| Error::OpcodeError("TodRequest", Box::new(e)))?,
+            ),
+            OpCode::TodData => ArtCommand::TodData(
+                TodData::from(data).map_err(|e| Error::OpcodeError("TodData", Box::new(e)))?,
+            ),
+            OpCode::TodControl => ArtCommand::TodControl(
+                TodControl::from(data)
+                    .map_err(|e| Error::OpcodeError("TodControl", Box::new(e)))?,
+            ),
+            OpCode::Rdm => ArtCommand::Rdm,
+            OpCode::RdmSub => ArtCommand::RdmSub,
+            OpCode::VideoSetup => ArtCommand::VideoSetup(
+                VideoSetup::from(data)
+                    .map_err(|e| Error::OpcodeError("VideoSetup", Box::new(e)))?,
+            ),
+            OpCode::VideoPalette => ArtCommand::VideoPalette(
+                VideoPalette::from(data)
+                    .map_err(|e| Error::OpcodeError("VideoPalette", Box::new(e)))?,
+            ),
+            OpCode::VideoData => ArtCommand::VideoData(
+                VideoData::from(data).map_err(|e| Error::OpcodeError("VideoData", Box::new(e)))?,
+            ),
+            OpCode::MacMaster => ArtCommand::MacMaster(
+                MacMaster::from(data).map_err(|e| Error::OpcodeError("MacMaster", Box::new(e)))?,
+            ),
+            OpCode::MacSlave => ArtCommand::MacSlave(
+                MacSlave::from(data).map_err(|e| Error::OpcodeError("MacSlave", Box::new(e)))?,
+            ),
+            OpCode::FirmwareMaster => ArtCommand::FirmwareMaster,
+            OpCode::FirmwareReply => ArtCommand::FirmwareReply,
+            OpCode::FileTnMaster => ArtCommand::FileTnMaster(Box::new(
+                FileTnMaster::from(data)
+                    .map_err(|e| Error::OpcodeError("FileTnMaster", Box::new(e)))?,
+            )),
+            OpCode::FileFnMaster => ArtCommand::FileFnMaster(Box::new(
+                FileFnMaster::from(data)
+                    .map_err(|e| Error::OpcodeError("FileFnMaster", Box::new(e)))?,
+            )),
+            OpCode::FileFnReply => ArtCommand::FileFnReply(
+                FileFnReply::from(data)
+                    .map_err(|e| Error::OpcodeError("FileFnReply", Box::new(e)))?,
+            ),
+            OpCode::IpProg => ArtCommand::OpIpProg(
+                IpProg::from(data).map_err(|e| Error::OpcodeError("IpProg", Box::new(e)))?,
+            ),
+            OpCode::IpProgReply => ArtCommand::OpIpProgReply(
+                IpProgReply::from(data)
+                    .map_err(|e| Error::OpcodeError("IpProgReply", Box::new(e)))?,
+            ),
+            OpCode::Media => ArtCommand::OpMedia(
+                Media::from(data).map_err(|e| Error::OpcodeError("Media", Box::new(e)))?,
+            ),
+            OpCode::MediaPatch => ArtCommand::OpMediaPatch(
+                MediaPatch::from(data)
+                    .map_err(|e| Error::OpcodeError("MediaPatch", Box::new(e)))?,
+            ),
+            OpCode::MediaControl => ArtCommand::OpMediaControl(
+                MediaControl::from(data)
+                    .map_err(|e| Error::OpcodeError("MediaControl", Box::new(e)))?,
+            ),
+            OpCode::MediaControlReply => ArtCommand::OpMediaControlReply(
+                MediaControlReply::from(data)
+                    .map_err(|e| Error::OpcodeError("MediaControlReply", Box::new(e)))?,
+            ),
+            OpCode::TimeCode => ArtCommand::OpTimeCode(
                 Timecode::from(data).map_err(|e| Error::OpcodeError("Timecode", Box::new(e)))?,
             ),
-            0x9800 => ArtCommand::OpTimeSync,
-            0x9900 => ArtCommand::OpTrigger,
-            0x9A00 => ArtCommand::OpDirectory,
-            0x9B00 => ArtCommand::OpDirectoryReply,
-            _ => return Err(Error::UnknownOpcode(code)),
+            OpCode::TimeSync => ArtCommand::OpTimeSync(
+                TimeSync::from(data).map_err(|e| Error::OpcodeError("TimeSync", Box::new(e)))?,
+            ),
+            OpCode::Trigger => ArtCommand::OpTrigger(Box::new(
+                Trigger::from(data).map_err(|e| Error::OpcodeError("Trigger", Box::new(e)))?,
+            )),
+            OpCode::Directory => ArtCommand::OpDirectory(
+                Directory::from(data).map_err(|e| Error::OpcodeError("Directory", Box::new(e)))?,
+            ),
+            OpCode::DirectoryReply => ArtCommand::OpDirectoryReply(
+                DirectoryReply::from(data)
+                    .map_err(|e| Error::OpcodeError("DirectoryReply", Box::new(e)))?,
+            ),
         })
     }
 
-    fn get_opcode(&self) -> Result<(u16, Vec<u8>)> {
-        Ok(match self {
-            ArtCommand::Poll(poll) => (0x2000, poll.to_bytes()?),
-            ArtCommand::PollReply(reply) => (0x2100, reply.to_bytes()?),
-            ArtCommand::DiagData => (0x2300, Vec::new()),
-            ArtCommand::Command => (0x2400, Vec::new()),
-            ArtCommand::Output(output) => (0x5000, output.to_bytes()?),
-            ArtCommand::Nzs => (0x5100, Vec::new()),
-            ArtCommand::Sync => (0x5200, Vec::new()),
-            ArtCommand::Address => (0x6000, Vec::new()),
-            ArtCommand::Input => (0x7000, Vec::new()),
-            ArtCommand::TodRequest => (0x8000, Vec::new()),
-            ArtCommand::TodData => (0x8100, Vec::new()),
-            ArtCommand::TodControl => (0x8200, Vec::new()),
-            ArtCommand::Rdm => (0x8300, Vec::new()),
-            ArtCommand::RdmSub => (0x8400, Vec::new()),
-            ArtCommand::VideoSetup => (0xA010, Vec::new()),
-            ArtCommand::VideoPalette => (0xA020, Vec::new()),
-            ArtCommand::VideoData => (0xA040, Vec::new()),
-            ArtCommand::MacMaster => (0xF000, Vec::new()),
-            ArtCommand::MacSlave => (0xF100, Vec::new()),
-            ArtCommand::FirmwareMaster => (0xF200, Vec::new()),
-            ArtCommand::FirmwareReply => (0xF300, Vec::new()),
-            ArtCommand::FileTnMaster => (0xF400, Vec::new()),
-            ArtCommand::FileFnMaster => (0xF500, Vec::new()),
-            ArtCommand::FileFnReply => (0xF600, Vec::new()),
-            ArtCommand::OpIpProg => (0xF800, Vec::new()),
-            ArtCommand::OpIpProgReply => (0xF900, Vec::new()),
-            ArtCommand::OpMedia => (0x9000, Vec::new()),
-            ArtCommand::OpMediaPatch => (0x9100, Vec::new()),
-            ArtCommand::OpMediaControl => (0x9200, Vec::new()),
-            ArtCommand::OpMediaControlReply => (0x9300, Vec::new()),
-            ArtCommand::OpTimeCode(timecode) => (0x9700, timecode.to_bytes()?),
-            ArtCommand::OpTimeSync => (0x9800, Vec::new()),
-            ArtCommand::OpTrigger => (0x9900, Vec::new()),
-            ArtCommand::OpDirectory => (0x9A00, Vec::new()),
-            ArtCommand::OpDirectoryReply => (0x9B00, Vec::new()),
+    /// The number of bytes that `write_to_buffer` will return.
+    pub fn serialized_len(&self) -> usize {
+        let payload = match self {
+            ArtCommand::Poll(poll) => poll.serialized_len(),
+            ArtCommand::PollReply(reply) => reply.serialized_len(),
+            ArtCommand::DiagData(diag) => diag.serialized_len(),
+            ArtCommand::CommandText(command) => command.serialized_len(),
+            ArtCommand::DataRequest(request) => request.serialized_len(),
+            ArtCommand::DataReply(reply) => reply.serialized_len(),
+            ArtCommand::Output(output) => output.serialized_len(),
+            ArtCommand::Nzs(nzs) => nzs.serialized_len(),
+            ArtCommand::Sync(sync) => sync.serialized_len(),
+            ArtCommand::Address(address) => address.serialized_len(),
+            ArtCommand::Input(input) => input.serialized_len(),
+            ArtCommand::TodRequest(request) => request.serialized_len(),
+            ArtCommand::TodData(data) => data.serialized_len(),
+            ArtCommand::TodControl(control) => control.serialized_len(),
+            ArtCommand::MacMaster(mac) => mac.serialized_len(),
+            ArtCommand::MacSlave(mac) => mac.serialized_len(),
+            ArtCommand::OpMedia(media) => media.serialized_len(),
+            ArtCommand::OpMediaPatch(media) => media.serialized_len(),
+            ArtCommand::OpMediaControl(media) => media.serialized_len(),
+            ArtCommand::OpMediaControlReply(media) => media.serialized_len(),
+            ArtCommand::OpIpProg(prog) => prog.serialized_len(),
+            ArtCommand::OpIpProgReply(reply) => reply.serialized_len(),
+            ArtCommand::OpTimeCode(timecode) => timecode.serialized_len(),
+            ArtCommand::OpTimeSync(time_sync) => time_sync.serialized_len(),
+            ArtCommand::OpTrigger(trigger) => trigger.serialized_len(),
+            ArtCommand::OpDirectory(directory) => directory.serialized_len(),
+            ArtCommand::OpDirectoryReply(reply) => reply.serialized_len(),
+            ArtCommand::FileTnMaster(master) => master.serialized_len(),
+            ArtCommand::FileFnMaster(master) => master.serialized_len(),
+            ArtCommand::FileFnReply(reply) => reply.serialized_len(),
+            ArtCommand::VideoSetup(setup) => setup.serialized_len(),
+            ArtCommand::VideoPalette(palette) => palette.serialized_len(),
+            ArtCommand::VideoData(data) => data.serialized_len(),
+            ArtCommand::Unknown { payload, .. } => payload.len(),
+            ArtCommand::Rdm
+            | ArtCommand::RdmSub
+            | ArtCommand::FirmwareMaster
+            | ArtCommand::FirmwareReply => 0,
+        };
+        HEADER_LENGTH + payload
+    }
+
+    /// The opcode of this command, as it is sent in the packet
+    pub fn opcode(&self) -> u16 {
+        match self {
+            ArtCommand::Unknown { opcode, .. } => *opcode,
+            command => command.known_opcode().as_u16(),
+        }
+    }
+
+    /// The name of the packet type of this command as used by the Art-Net specification, e.g. "ArtDmx" for an `Output`.
+    ///
+    /// Returns "Unknown" for `ArtCommand::Unknown`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ArtCommand::Unknown { .. } => "Unknown",
+            command => command.known_opcode().name(),
+        }
+    }
+
+    /// The protocol version of the packet, e.g. to detect nodes that speak an older version than [ProtocolVersion::CURRENT](struct.ProtocolVersion.html#associatedconstant.CURRENT).
+    ///
+    /// Returns `None` for packets that do not carry a protocol version: `PollReply` (its `version` is the firmware version of the node), unknown packets, and packets that are not implemented.
+    pub fn protocol_version(&self) -> Option<ProtocolVersion> {
+        Some(match self {
+            ArtCommand::Poll(packet) => packet.protocol_version(),
+            ArtCommand::DiagData(packet) => packet.protocol_version(),
+            ArtCommand::CommandText(packet) => packet.protocol_version(),
+            ArtCommand::DataRequest(packet) => packet.protocol_version(),
+            ArtCommand::DataReply(packet) => packet.protocol_version(),
+            ArtCommand::Output(packet) => packet.protocol_version(),
+            ArtCommand::Nzs(packet) => packet.protocol_version(),
+            ArtCommand::Sync(packet) => packet.protocol_version(),
+            ArtCommand::Address(packet) => packet.protocol_version(),
+            ArtCommand::Input(packet) => packet.protocol_version(),
+            ArtCommand::TodRequest(packet) => packet.protocol_version(),
+            ArtCommand::TodData(packet) => packet.protocol_version(),
+            ArtCommand::TodControl(packet) => packet.protocol_version(),
+            ArtCommand::MacMaster(packet) => packet.protocol_version(),
+            ArtCommand::MacSlave(packet) => packet.protocol_version(),
+            ArtCommand::OpIpProg(packet) => packet.protocol_version(),
+            ArtCommand::OpIpProgReply(packet) => packet.protocol_version(),
+            ArtCommand::OpMedia(packet) => packet.protocol_version(),
+            ArtCommand::OpMediaPatch(packet) => packet.protocol_version(),
+            ArtCommand::OpMediaControl(packet) => packet.protocol_version(),
+            ArtCommand::OpMediaControlReply(packet) => packet.protocol_version(),
+            ArtCommand::OpTimeCode(packet) => packet.protocol_version(),
+            ArtCommand::OpTimeSync(packet) => packet.protocol_version(),
+            ArtCommand::OpTrigger(packet) => packet.protocol_version(),
+            ArtCommand::OpDirectory(packet) => packet.protocol_version(),
+            ArtCommand::OpDirectoryReply(packet) => packet.protocol_version(),
+            ArtCommand::FileTnMaster(packet) => packet.protocol_version(),
+            ArtCommand::FileFnMaster(packet) => packet.protocol_version(),
+            ArtCommand::FileFnReply(packet) => packet.protocol_version(),
+            ArtCommand::VideoSetup(packet) => packet.protocol_version(),
+            ArtCommand::VideoPalette(packet) => packet.protocol_version(),
+            ArtCommand::VideoData(packet) => packet.protocol_version(),
+            _ => return None,
         })
     }
+
+    /// Whether the specification allows this command to be broadcast to all devices on the network.
+    ///
+    /// Discovery, diagnostics, synchronisation, timecode and trigger packets are meant to be broadcast.
+    /// Everything else, including the `Output` DMX data, should be sent unicast to the nodes that need it, so that nodes are not flooded with data they ignore.
+    pub fn is_broadcast_appropriate(&self) -> bool {
+        matches!(
+            self,
+            ArtCommand::Poll(_)
+                | ArtCommand::PollReply(_)
+                | ArtCommand::DiagData(_)
+                | ArtCommand::CommandText(_)
+                | ArtCommand::Sync(_)
+                | ArtCommand::OpTimeCode(_)
+                | ArtCommand::OpTimeSync(_)
+                | ArtCommand::OpTrigger(_)
+        )
+    }
+
+    /// Serialize this command and format the bytes as an offset, hex and ASCII table like `hexdump -C`, e.g. for test failures and logs.
+    ///
+    /// If the command can not be serialized, the error is returned as text instead.
+    pub fn hex_dump(&self) -> String {
+        match self.write_to_buffer() {
+            Ok(bytes) => hex_dump(&bytes),
+            Err(e) => format!("Could not serialize {}: {}", self.name(), e),
+        }
+    }
+
+    /// The OpCode of every command, except for `ArtCommand::Unknown`
+    fn known_opcode(&self) -> OpCode {
+        match self {
+            ArtCommand::Poll(_) => OpCode::Poll,
+            ArtCommand::PollReply(_) => OpCode::PollReply,
+            ArtCommand::DiagData(_) => OpCode::DiagData,
+            ArtCommand::CommandText(_) => OpCode::Command,
+            ArtCommand::DataRequest(_) => OpCode::DataRequest,
+            ArtCommand::DataReply(_) => OpCode::DataReply,
+            ArtCommand::Output(_) => OpCode::Output,
+            ArtCommand::Nzs(_) => OpCode::Nzs,
+            ArtCommand::Sync(_) => OpCode::Sync,
+            ArtCommand::Address(_) => OpCode::Address,
+            ArtCommand::Input(_) => OpCode::Input,
+            ArtCommand::TodRequest(_) => OpCode::TodRequest,
+            ArtCommand::TodData(_) => OpCode::TodData,
+            ArtCommand::TodControl(_) => OpCode::TodControl,
+            ArtCommand::Rdm => OpCode::Rdm,
+            ArtCommand::RdmSub => OpCode::RdmSub,
+            ArtCommand::VideoSetup(_) => OpCode::VideoSetup,
+            ArtCommand::VideoPalette(_) => OpCode::VideoPalette,
+            ArtCommand::VideoData(_) => OpCode::VideoData,
+            ArtCommand::MacMaster(_) => OpCode::MacMaster,
+            ArtCommand::MacSlave(_) => OpCode::MacSlave,
+            ArtCommand::FirmwareMaster => OpCode::FirmwareMaster,
+            ArtCommand::FirmwareReply => OpCode::FirmwareReply,
+            ArtCommand::FileTnMaster(_) => OpCode::FileTnMaster,
+            ArtCommand::FileFnMaster(_) => OpCode::FileFnMaster,
+            ArtCommand::FileFnReply(_) => OpCode::FileFnReply,
+            ArtCommand::OpIpProg(_) => OpCode::IpProg,
+            ArtCommand::OpIpProgReply(_) => OpCode::IpProgReply,
+            ArtCommand::OpMedia(_) => OpCode::Media,
+            ArtCommand::OpMediaPatch(_) => OpCode::MediaPatch,
+            ArtCommand::OpMediaControl(_) => OpCode::MediaControl,
+            ArtCommand::OpMediaControlReply(_) => OpCode::MediaControlReply,
+            ArtCommand::OpTimeCode(_) => OpCode::TimeCode,
+            ArtCommand::OpTimeSync(_) => OpCode::TimeSync,
+            ArtCommand::OpTrigger(_) => OpCode::Trigger,
+            ArtCommand::OpDirectory(_) => OpCode::Directory,
+            ArtCommand::OpDirectoryReply(_) => OpCode::DirectoryReply,
+            ArtCommand::Unknown { .. } => unreachable!("unknown commands have no OpCode"),
+        }
+    }
+
+    /// Append the payload of this command to the buffer, and return the opcode
+    fn write_payload(&self, buffer: &mut Vec<u8>) -> Result<u16> {
+        let opcode = match self {
+            ArtCommand::Unknown { opcode, payload } => {
+                buffer.extend_from_slice(payload);
+                return Ok(*opcode);
+            }
+            ArtCommand::Poll(poll) => {
+                poll.write_bytes(buffer)?;
+                OpCode::Poll
+            }
+            ArtCommand::PollReply(reply) => {
+                reply.write_bytes(buffer)?;
+                OpCode::PollReply
+            }
+            ArtCommand::DiagData(diag) => {
+                diag.write_bytes(buffer)?;
+                OpCode::DiagData
+            }
+            ArtCommand::CommandText(command) => {
+                command.write_bytes(buffer)?;
+                OpCode::Command
+            }
+            ArtCommand::DataRequest(request) => {
+                request.write_bytes(buffer)?;
+                OpCode::DataRequest
+            }
+            ArtCommand::DataReply(reply) => {
+                reply.write_bytes(buffer)?;
+                OpCode::DataReply
+            }
+            ArtCommand::Output(output) => {
+                output.write_bytes(buffer)?;
+                OpCode::Output
+            }
+            ArtCommand::Nzs(nzs) => {
+                nzs.write_bytes(buffer)?;
+                OpCode::Nzs
+            }
+            ArtCommand::Sync(sync) => {
+                sync.write_bytes(buffer)?;
+                OpCode::Sync
+            }
+            ArtCommand::Address(address) => {
+                address.write_bytes(buffer)?;
+                OpCode::Address
+            }
+            ArtCommand::Input(input) => {
+                input.write_bytes(buffer)?;
+                OpCode::Input
+            }
+            ArtCommand::TodRequest(request) => {
+                request.write_bytes(buffer)?;
+                OpCode::TodRequest
+            }
+            ArtCommand::TodData(data) => {
+                data.write_bytes(buffer)?;
+                OpCode::TodData
+            }
+            ArtCommand::TodControl(control) => {
+                control.write_bytes(buffer)?;
+                OpCode::TodControl
+            }
+            ArtCommand::Rdm => OpCode::Rdm,
+            ArtCommand::RdmSub => OpCode::RdmSub,
+            ArtCommand::VideoSetup(video) => {
+                video.write_bytes(buffer)?;
+                OpCode::VideoSetup
+            }
+            ArtCommand::VideoPalette(video) => {
+                video.write_bytes(buffer)?;
+                OpCode::VideoPalette
+            }
+            ArtCommand::VideoData(video) => {
+                video.write_bytes(buffer)?;
+                OpCode::VideoData
+            }
+            ArtCommand::MacMaster(mac) => {
+                mac.write_bytes(buffer)?;
+                OpCode::MacMaster
+            }
+            ArtCommand::MacSlave(mac) => {
+                mac.write_bytes(buffer)?;
+                OpCode::MacSlave
+            }
+            ArtCommand::FirmwareMaster => OpCode::FirmwareMaster,
+            ArtCommand::FirmwareReply => OpCode::FirmwareReply,
+            ArtCommand::FileTnMaster(master) => {
+                master.write_bytes(buffer)?;
+                OpCode::FileTnMaster
+            }
+            ArtCommand::FileFnMaster(master) => {
+                master.write_bytes(buffer)?;
+                OpCode::FileFnMaster
+            }
+            ArtCommand::FileFnReply(reply) => {
+                reply.write_bytes(buffer)?;
+                OpCode::FileFnReply
+            }
+            ArtCommand::OpIpProg(prog) => {
+                prog.write_bytes(buffer)?;
+                OpCode::IpProg
+            }
+            ArtCommand::OpIpProgReply(reply) => {
+                reply.write_bytes(buffer)?;
+                OpCode::IpProgReply
+            }
+            ArtCommand::OpMedia(media) => {
+                media.write_bytes(buffer)?;
+                OpCode::Media
+            }
+            ArtCommand::OpMediaPatch(media) => {
+                media.write_bytes(buffer)?;
+                OpCode::MediaPatch
+            }
+            ArtCommand::OpMediaControl(media) => {
+                media.write_bytes(buffer)?;
+                OpCode::MediaControl
+            }
+            ArtCommand::OpMediaControlReply(media) => {
+                media.write_bytes(buffer)?;
+                OpCode::MediaControlReply
+            }
+            ArtCommand::OpTimeCode(timecode) => {
+                timecode.validate()?;
+                timecode.write_bytes(buffer)?;
+                OpCode::TimeCode
+            }
+            ArtCommand::OpTimeSync(time_sync) => {
+                time_sync.write_bytes(buffer)?;
+                OpCode::TimeSync
+            }
+            ArtCommand::OpTrigger(trigger) => {
+                trigger.write_bytes(buffer)?;
+                OpCode::Trigger
+            }
+            ArtCommand::OpDirectory(directory) => {
+                directory.write_bytes(buffer)?;
+                OpCode::Directory
+            }
+            ArtCommand::OpDirectoryReply(reply) => {
+                reply.write_bytes(buffer)?;
+                OpCode::DirectoryReply
+            }
+        };
+        Ok(opcode.as_u16())
+    }
+}
+
+/// Parse a buffer with [ArtCommand::from_buffer](enum.ArtCommand.html#method.from_buffer)
+impl TryFrom<&[u8]> for ArtCommand {
+    type Error = Error;
+
+    fn try_from(buffer: &[u8]) -> Result<ArtCommand> {
+        ArtCommand::from_buffer(buffer)
+    }
+}
+
+/// Serialize a command with [ArtCommand::write_to_buffer](enum.ArtCommand.html#method.write_to_buffer)
+impl TryFrom<ArtCommand> for Vec<u8> {
+    type Error = Error;
+
+    fn try_from(command: ArtCommand) -> Result<Vec<u8>> {
+        command.write_to_buffer()
+    }
+}
+
+/// Format the bytes as rows of 16, with the offset, the hex values and the printable ASCII characters
+fn hex_dump(bytes: &[u8]) -> String {
+    let mut dump = String::new();
+    for (row, chunk) in bytes.chunks(16).enumerate() {
+        let _ = write!(dump, "{:08x} ", row * 16);
+        for index in 0..16 {
+            if index % 8 == 0 {
+                dump.push(' ');
+            }
+            match chunk.get(index) {
+                Some(byte) => {
+                    let _ = write!(dump, "{:02x} ", byte);
+                }
+                None => dump.push_str("   "),
+            }
+        }
+        dump.push_str(" |");
+        for &byte in chunk {
+            dump.push(if byte.is_ascii_graphic() || byte == b' ' {
+                char::from(byte)
+            } else {
+                '.'
+            });
+        }
+        dump.push_str("|\n");
+    }
+    let _ = writeln!(dump, "{:08x}", bytes.len());
+    dump
 }
@@ -0,0 +1,93 @@
+use crate::command::output::{BigEndianLength, HasPayloadLen};
+use crate::{command::ARTNET_PROTOCOL_VERSION, PaddedData, PortAddress, Result};
+
+data_structure! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[doc = "ArtNzs is the data packet used to transfer DMX512 data with a non-zero start code (except RDM)."]
+    #[doc = ""]
+    #[doc = "The format is identical to ArtDmx, except that the physical port is replaced by the start code of the data."]
+    pub struct Nzs {
+        #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
+        pub version: [u8; 2],
+        #[doc = "The sequence number is used to ensure that ArtNzs packets are used in the correct order. This field is incremented in the range 0x01 to 0xff to allow the receiving node to resequence packets."]
+        #[doc = ""]
+        #[doc = "The Sequence field is set to 0x00 to disable this feature"]
+        pub sequence: u8,
+        #[doc = "The DMX512 start code of this packet. Must not be zero or RDM (0xcc)"]
+        #[doc = ""]
+        #[doc = "There is no start code that suits every packet, so `Nzs::default()` sets this to zero. Callers must set it before the packet is sent."]
+        pub start_code: u8,
+        #[doc = "The 15 bit Port-Address to which this packet is destined"]
+        pub port_address: PortAddress,
+        #[doc = "The length of the message, set by the artnet library itself"]
+        #[cfg_attr(feature = "serde", serde(skip))]
+        pub length: BigEndianLength<Nzs>,
+        #[doc = "A variable length array of DMX512 data"]
+        pub data: PaddedData,
+    }
+}
+
+impl Default for Nzs {
+    fn default() -> Nzs {
+        Nzs {
+            version: ARTNET_PROTOCOL_VERSION,
+            sequence: 0,
+            start_code: 0,
+            port_address: 1.into(),
+            length: BigEndianLength::default(),
+            data: PaddedData::default(),
+        }
+    }
+}
+
+impl HasPayloadLen for Nzs {
+    fn payload_len(&self) -> Result<usize> {
+        self.data.validate()?;
+        Ok(self.data.len_rounded_up())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArtCommand;
+
+    #[test]
+    fn round_trip_with_start_code() {
+        let command = ArtCommand::Nzs(Nzs {
+            sequence: 3,
+            start_code: 0x17,
+            port_address: 5.into(),
+            data: vec![1, 2, 3].into(),
+            ..Nzs::default()
+        });
+        let bytes = command.write_to_buffer().unwrap();
+        assert_eq!(
+            bytes,
+            vec![65, 114, 116, 45, 78, 101, 116, 0, 0, 81, 0, 14, 3, 0x17, 5, 0, 0, 4, 1, 2, 3, 0]
+        );
+
+        match ArtCommand::from_buffer(&bytes).unwrap() {
+            ArtCommand::Nzs(nzs) => {
+                assert_eq!(nzs.version, ARTNET_PROTOCOL_VERSION);
+                assert_eq!(nzs.sequence, 3);
+                assert_eq!(nzs.start_code, 0x17);
+                assert_eq!(nzs.port_address, 5.into());
+                assert_eq!(*nzs.length, 4);
+                assert_eq!(nzs.data.as_ref(), &vec![1, 2, 3, 0]);
+            }
+            command => panic!("Expected a Nzs, got {:?}", command),
+        }
+    }
+
+    #[test]
+    fn default_start_code_is_not_set() {
+        let nzs = Nzs {
+            data: vec![1, 2].into(),
+            ..Nzs::default()
+        };
+        assert_eq!(nzs.start_code, 0);
+        let bytes = ArtCommand::Nzs(nzs).write_to_buffer().unwrap();
+        assert_eq!(bytes[13], 0);
+    }
+}
@@ -0,0 +1,122 @@
+use crate::command::ARTNET_PROTOCOL_VERSION;
+use alloc::vec::Vec;
+
+/// Define a packet of which the specification only documents the protocol version. The rest of the packet is kept in `data`, so it round-trips byte for byte.
+///
+/// Every packet is defined in its own module, see `data_structure!`.
+macro_rules! opaque_packet {
+    ($module:ident, $name:ident, $(#[doc = $doc:literal])*) => {
+        data_structure! {
+            mod $module;
+            #[derive(Debug, Clone, PartialEq, Eq)]
+            $(#[doc = $doc])*
+            #[doc = ""]
+            #[doc = "The specification only documents the protocol version of this packet, everything after it is kept as-is in `data`."]
+            #[doc = "It has no length field, so it can not be read from a stream, and in a datagram with other packets it ends at the next Art-Net header."]
+            pub struct $name {
+                #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
+                pub version: [u8; 2],
+                #[remainder]
+                #[doc = "The undocumented payload of the packet"]
+                pub data: Vec<u8>,
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> $name {
+                $name {
+                    version: ARTNET_PROTOCOL_VERSION,
+                    data: Vec::new(),
+                }
+            }
+        }
+    };
+}
+
+opaque_packet!(
+    mac_master,
+    MacMaster,
+    #[doc = "ArtMacMaster is a deprecated packet that configured the MAC layer of a node."]
+);
+
+opaque_packet!(
+    mac_slave,
+    MacSlave,
+    #[doc = "ArtMacSlave is a deprecated packet, the reply of a node to an ArtMacMaster."]
+);
+
+opaque_packet!(
+    media,
+    Media,
+    #[doc = "ArtMedia is sent by a media server to a controller."]
+);
+
+opaque_packet!(
+    media_patch,
+    MediaPatch,
+    #[doc = "ArtMediaPatch is sent by a controller to patch the outputs of a media server."]
+);
+
+opaque_packet!(
+    media_control,
+    MediaControl,
+    #[doc = "ArtMediaControl is sent by a controller to control a media server."]
+);
+
+opaque_packet!(
+    media_control_reply,
+    MediaControlReply,
+    #[doc = "ArtMediaControlReply is sent by a media server in reply to an ArtMediaControl."]
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::ArtCommand;
+
+    /// Parse a packet with the given opcode and payload, check its data, and write it back
+    fn round_trip(opcode: u16, data_of: fn(&ArtCommand) -> Option<&[u8]>) {
+        let mut packet = b"Art-Net\0".to_vec();
+        packet.extend_from_slice(&opcode.to_le_bytes());
+        packet.extend_from_slice(&[0, 14, 1, 2, 3, 0, 0xff]);
+        let command = ArtCommand::from_buffer(&packet).unwrap();
+        assert_eq!(
+            data_of(&command),
+            Some(&[1, 2, 3, 0, 0xff][..]),
+            "{:?}",
+            command
+        );
+        assert_eq!(command.write_to_buffer().unwrap(), packet);
+    }
+
+    #[test]
+    fn mac_round_trip() {
+        round_trip(0xf000, |command| match command {
+            ArtCommand::MacMaster(mac) => Some(&mac.data),
+            _ => None,
+        });
+        round_trip(0xf100, |command| match command {
+            ArtCommand::MacSlave(mac) => Some(&mac.data),
+            _ => None,
+        });
+    }
+
+    #[test]
+    fn media_round_trip() {
+        round_trip(0x9000, |command| match command {
+            ArtCommand::OpMedia(media) => Some(&media.data),
+            _ => None,
+        });
+        round_trip(0x9100, |command| match command {
+            ArtCommand::OpMediaPatch(media) => Some(&media.data),
+            _ => None,
+        });
+        round_trip(0x9200, |command| match command {
+            ArtCommand::OpMediaControl(media) => Some(&media.data),
+            _ => None,
+        });
+        round_trip(0x9300, |command| match command {
+            ArtCommand::OpMediaControlReply(media) => Some(&media.data),
+            _ => None,
+        });
+    }
+}
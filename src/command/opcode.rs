@@ -0,0 +1,265 @@
+use crate::command::*;
+
+macro_rules! opcodes {
+    ($($(#[$meta:meta])* $name:ident = $value:literal,)*) => {
+        /// The OpCode of an Art-Net packet, which determines the type of the packet.
+        ///
+        /// This can be read from a buffer with [ArtCommand::peek_opcode](enum.ArtCommand.html#method.peek_opcode), without parsing the rest of the packet.
+        #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+        #[repr(u16)]
+        pub enum OpCode {
+            $(
+                $(#[$meta])*
+                $name = $value,
+            )*
+        }
+
+        impl OpCode {
+            /// Get the OpCode for the given value, or `None` if the value is not a known OpCode
+            pub fn from_u16(value: u16) -> Option<OpCode> {
+                match value {
+                    $($value => Some(OpCode::$name),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+opcodes! {
+    /// OpPoll, used to discover devices on the network
+    Poll = 0x2000,
+    /// OpPollReply, the reply to an OpPoll
+    PollReply = 0x2100,
+    /// OpDiagData, diagnostics and data logging
+    DiagData = 0x2300,
+    /// OpCommand, text based parameter commands
+    Command = 0x2400,
+    /// OpDataRequest, requests product data from a node
+    DataRequest = 0x2700,
+    /// OpDataReply, the reply to an OpDataRequest
+    DataReply = 0x2800,
+    /// OpOutput / OpDmx, DMX512 data
+    Output = 0x5000,
+    /// OpNzs, DMX512 data with a non-zero start code
+    Nzs = 0x5100,
+    /// OpSync, forces synchronous output of DMX512 data
+    Sync = 0x5200,
+    /// OpAddress, remote programming of a node
+    Address = 0x6000,
+    /// OpInput, enables and disables DMX inputs
+    Input = 0x7000,
+    /// OpTodRequest, requests the RDM Table of Devices
+    TodRequest = 0x8000,
+    /// OpTodData, the RDM Table of Devices
+    TodData = 0x8100,
+    /// OpTodControl, RDM discovery control messages
+    TodControl = 0x8200,
+    /// OpRdm, non discovery RDM messages
+    Rdm = 0x8300,
+    /// OpRdmSub, compressed RDM sub-device data
+    RdmSub = 0x8400,
+    /// OpVideoSetup, extended video features setup
+    VideoSetup = 0xA010,
+    /// OpVideoPalette, extended video features colour palette
+    VideoPalette = 0xA020,
+    /// OpVideoData, extended video features display data
+    VideoData = 0xA040,
+    /// OpMacMaster, deprecated
+    MacMaster = 0xF000,
+    /// OpMacSlave, deprecated
+    MacSlave = 0xF100,
+    /// OpFirmwareMaster, uploads firmware to a node
+    FirmwareMaster = 0xF200,
+    /// OpFirmwareReply, acknowledges an OpFirmwareMaster or OpFileTnMaster
+    FirmwareReply = 0xF300,
+    /// OpFileTnMaster, uploads a user file to a node
+    FileTnMaster = 0xF400,
+    /// OpFileFnMaster, downloads a user file from a node
+    FileFnMaster = 0xF500,
+    /// OpFileFnReply, acknowledges OpFileFnMaster packets
+    FileFnReply = 0xF600,
+    /// OpIpProg, reprograms the IP configuration of a node
+    IpProg = 0xF800,
+    /// OpIpProgReply, the reply to an OpIpProg
+    IpProgReply = 0xF900,
+    /// OpMedia, sent by a media server
+    Media = 0x9000,
+    /// OpMediaPatch, sent by a controller to a media server
+    MediaPatch = 0x9100,
+    /// OpMediaControl, sent by a controller to a media server
+    MediaControl = 0x9200,
+    /// OpMediaControlReply, sent by a media server to a controller
+    MediaControlReply = 0x9300,
+    /// OpTimeCode, transports time code
+    TimeCode = 0x9700,
+    /// OpTimeSync, synchronises the real time clock
+    TimeSync = 0x9800,
+    /// OpTrigger, sends trigger macros
+    Trigger = 0x9900,
+    /// OpDirectory, requests the file list of a node
+    Directory = 0x9A00,
+    /// OpDirectoryReply, the file list of a node
+    DirectoryReply = 0x9B00,
+}
+
+impl OpCode {
+    /// The value of this OpCode, as it is sent (little endian) in the packet
+    pub fn as_u16(self) -> u16 {
+        self as u16
+    }
+
+    /// The name of the packet with this OpCode in the Art-Net specification, e.g. "ArtDmx" for `OpCode::Output`
+    pub fn name(self) -> &'static str {
+        match self {
+            OpCode::Poll => "ArtPoll",
+            OpCode::PollReply => "ArtPollReply",
+            OpCode::DiagData => "ArtDiagData",
+            OpCode::Command => "ArtCommand",
+            OpCode::DataRequest => "ArtDataRequest",
+            OpCode::DataReply => "ArtDataReply",
+            OpCode::Output => "ArtDmx",
+            OpCode::Nzs => "ArtNzs",
+            OpCode::Sync => "ArtSync",
+            OpCode::Address => "ArtAddress",
+            OpCode::Input => "ArtInput",
+            OpCode::TodRequest => "ArtTodRequest",
+            OpCode::TodData => "ArtTodData",
+            OpCode::TodControl => "ArtTodControl",
+            OpCode::Rdm => "ArtRdm",
+            OpCode::RdmSub => "ArtRdmSub",
+            OpCode::VideoSetup => "ArtVideoSetup",
+            OpCode::VideoPalette => "ArtVideoPalette",
+            OpCode::VideoData => "ArtVideoData",
+            OpCode::MacMaster => "ArtMacMaster",
+            OpCode::MacSlave => "ArtMacSlave",
+            OpCode::FirmwareMaster => "ArtFirmwareMaster",
+            OpCode::FirmwareReply => "ArtFirmwareReply",
+            OpCode::FileTnMaster => "ArtFileTnMaster",
+            OpCode::FileFnMaster => "ArtFileFnMaster",
+            OpCode::FileFnReply => "ArtFileFnReply",
+            OpCode::IpProg => "ArtIpProg",
+            OpCode::IpProgReply => "ArtIpProgReply",
+            OpCode::Media => "ArtMedia",
+            OpCode::MediaPatch => "ArtMediaPatch",
+            OpCode::MediaControl => "ArtMediaControl",
+            OpCode::MediaControlReply => "ArtMediaControlReply",
+            OpCode::TimeCode => "ArtTimeCode",
+            OpCode::TimeSync => "ArtTimeSync",
+            OpCode::Trigger => "ArtTrigger",
+            OpCode::Directory => "ArtDirectory",
+            OpCode::DirectoryReply => "ArtDirectoryReply",
+        }
+    }
+}
+
+impl From<OpCode> for u16 {
+    fn from(value: OpCode) -> u16 {
+        value.as_u16()
+    }
+}
+
+macro_rules! packet_opcodes {
+    ($($packet:ty => $opcode:ident,)*) => {
+        $(
+            impl $packet {
+                /// The OpCode of this packet, e.g. for matching on it without an `ArtCommand`
+                pub const OPCODE: u16 = OpCode::$opcode as u16;
+            }
+        )*
+    };
+}
+
+packet_opcodes! {
+    Address => Address,
+    CommandText => Command,
+    DataReply => DataReply,
+    DataRequest => DataRequest,
+    DiagData => DiagData,
+    Directory => Directory,
+    DirectoryReply => DirectoryReply,
+    FileFnMaster => FileFnMaster,
+    FileFnReply => FileFnReply,
+    FileTnMaster => FileTnMaster,
+    Input => Input,
+    IpProg => IpProg,
+    IpProgReply => IpProgReply,
+    MacMaster => MacMaster,
+    MacSlave => MacSlave,
+    Media => Media,
+    MediaControl => MediaControl,
+    MediaControlReply => MediaControlReply,
+    MediaPatch => MediaPatch,
+    Nzs => Nzs,
+    Output => Output,
+    Poll => Poll,
+    PollReply => PollReply,
+    Sync => Sync,
+    TimeSync => TimeSync,
+    Timecode => TimeCode,
+    TodControl => TodControl,
+    TodData => TodData,
+    TodRequest => TodRequest,
+    Trigger => Trigger,
+    VideoData => VideoData,
+    VideoPalette => VideoPalette,
+    VideoSetup => VideoSetup,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ArtCommand, Directory, Error, Output, Poll, Timecode};
+
+    #[test]
+    fn opcode_conversion() {
+        assert_eq!(OpCode::from_u16(0x5000), Some(OpCode::Output));
+        assert_eq!(OpCode::Output.as_u16(), 0x5000);
+        assert_eq!(OpCode::from_u16(0xA040), Some(OpCode::VideoData));
+        assert_eq!(OpCode::from_u16(0x1234), None);
+    }
+
+    #[test]
+    fn peek_known_opcode() {
+        let buffer = ArtCommand::Poll(Poll::default()).write_to_buffer().unwrap();
+        assert_eq!(ArtCommand::peek_opcode(&buffer).unwrap(), OpCode::Poll);
+    }
+
+    #[test]
+    fn peek_rejects_bad_header() {
+        let mut buffer = ArtCommand::Poll(Poll::default()).write_to_buffer().unwrap();
+        buffer[0] = b'B';
+        assert!(matches!(
+            ArtCommand::peek_opcode(&buffer),
+            Err(Error::InvalidHeader { .. })
+        ));
+    }
+
+    #[test]
+    fn command_opcode_and_name() {
+        let output = ArtCommand::Output(Output::new(1, vec![1, 2]));
+        assert_eq!(output.opcode(), 0x5000);
+        assert_eq!(output.name(), "ArtDmx");
+        let bytes = output.write_to_buffer().unwrap();
+        assert_eq!(ArtCommand::peek_opcode(&bytes).unwrap().as_u16(), 0x5000);
+
+        assert_eq!(ArtCommand::Poll(Poll::default()).name(), "ArtPoll");
+        assert_eq!(
+            ArtCommand::OpDirectory(Directory::default()).opcode(),
+            0x9A00
+        );
+        let unknown = ArtCommand::Unknown {
+            opcode: 0x1234,
+            payload: vec![],
+        };
+        assert_eq!(unknown.opcode(), 0x1234);
+        assert_eq!(unknown.name(), "Unknown");
+    }
+
+    #[test]
+    fn packet_opcode_constants() {
+        assert_eq!(Output::OPCODE, 0x5000);
+        assert_eq!(Timecode::OPCODE, 0x9700);
+        assert_eq!(Output::OPCODE, OpCode::Output.as_u16());
+    }
+}
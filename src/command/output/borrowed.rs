@@ -0,0 +1,128 @@
+use super::{BigEndianLength, Output};
+use crate::convert::Cursor;
+use crate::{convert::Convertable, PortAddress, Result};
+
+/// A borrowed view of an ArtDmx packet, see [Output](struct.Output.html).
+///
+/// This is returned by [ArtCommand::from_buffer_ref](enum.ArtCommand.html#method.from_buffer_ref), and references the DMX512 data in the received buffer instead of copying it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputRef<'a> {
+    /// The protocol version of the sender
+    pub version: [u8; 2],
+    /// The sequence number of this packet, or 0x00 if sequencing is disabled
+    pub sequence: u8,
+    /// The physical input port from which DMX512 data was input
+    pub physical: u8,
+    /// The 15 bit Port-Address to which this packet is destined
+    pub port_address: PortAddress,
+    /// The length of the data, as given in the packet
+    pub length: u16,
+    /// The DMX512 lighting data, borrowed from the received buffer
+    pub data: &'a [u8],
+}
+
+impl<'a> OutputRef<'a> {
+    pub(crate) fn from(data: &'a [u8]) -> Result<OutputRef<'a>> {
+        fn field<T: Convertable<Output>>(
+            cursor: &mut Cursor<&[u8]>,
+            name: &'static str,
+            message: &'static str,
+        ) -> Result<T> {
+            T::from_cursor(cursor).map_err(|e| e.in_field(name, message))
+        }
+
+        let mut cursor = Cursor::new(data);
+        let version = field(
+            &mut cursor,
+            "OutputRef::version",
+            "Could not deserialize field OutputRef::version",
+        )?;
+        let sequence = field(
+            &mut cursor,
+            "OutputRef::sequence",
+            "Could not deserialize field OutputRef::sequence",
+        )?;
+        let physical = field(
+            &mut cursor,
+            "OutputRef::physical",
+            "Could not deserialize field OutputRef::physical",
+        )?;
+        let port_address = field(
+            &mut cursor,
+            "OutputRef::port_address",
+            "Could not deserialize field OutputRef::port_address",
+        )?;
+        let length: BigEndianLength<Output> = field(
+            &mut cursor,
+            "OutputRef::length",
+            "Could not deserialize field OutputRef::length",
+        )?;
+        Ok(OutputRef {
+            version,
+            sequence,
+            physical,
+            port_address,
+            length: *length,
+            data: cursor.read_to_end()?,
+        })
+    }
+}
+
+impl From<OutputRef<'_>> for Output {
+    fn from(value: OutputRef<'_>) -> Output {
+        Output {
+            version: value.version,
+            sequence: value.sequence,
+            physical: value.physical,
+            port_address: value.port_address,
+            length: BigEndianLength {
+                parsed_length: Some(value.length),
+                _pd: core::marker::PhantomData,
+            },
+            data: value.data.to_vec().into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ArtCommand, ArtCommandRef, Output};
+
+    #[test]
+    fn data_is_borrowed_from_buffer() {
+        let buffer = ArtCommand::Output(Output {
+            data: vec![1, 2, 3, 4].into(),
+            port_address: 7.into(),
+            ..Output::default()
+        })
+        .write_to_buffer()
+        .unwrap();
+
+        let output = match ArtCommand::from_buffer_ref(&buffer).unwrap() {
+            ArtCommandRef::Output(output) => output,
+            command => panic!("Expected an Output, got {:?}", command),
+        };
+        assert_eq!(output.port_address, 7.into());
+        assert_eq!(output.length, 4);
+        assert_eq!(output.data, &[1, 2, 3, 4]);
+
+        // The data must point into the original buffer, so no allocation was made for it
+        let range = buffer.as_ptr_range();
+        assert!(range.contains(&output.data.as_ptr()));
+        assert_eq!(output.data.as_ptr_range().end, range.end);
+
+        let owned: Output = output.into();
+        assert_eq!(owned.data.as_ref(), &vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn other_commands_are_owned() {
+        let buffer = ArtCommand::Poll(Default::default())
+            .write_to_buffer()
+            .unwrap();
+        assert!(matches!(
+            ArtCommand::from_buffer_ref(&buffer).unwrap(),
+            ArtCommandRef::Other(ArtCommand::Poll(_))
+        ));
+    }
+}
@@ -0,0 +1,98 @@
+use super::{Output, PaddedData};
+use crate::{PortAddress, Result};
+
+/// A builder for an [Output](struct.Output.html) packet.
+///
+/// Unlike constructing an `Output` directly, the length of the data is validated when calling `build`, instead of when the packet is serialized.
+///
+/// ```
+/// use artnet_protocol::{ArtCommand, Output};
+///
+/// let output = Output::builder()
+///     .port_address(2)
+///     .sequence(1)
+///     .data(vec![255, 128, 0])
+///     .build()
+///     .unwrap();
+/// let bytes = ArtCommand::Output(output).write_to_buffer().unwrap();
+/// ```
+#[derive(Debug, Default)]
+pub struct OutputBuilder {
+    output: Output,
+}
+
+impl OutputBuilder {
+    /// The 15 bit Port-Address to which this packet is destined
+    pub fn port_address(mut self, port_address: impl Into<PortAddress>) -> Self {
+        self.output.port_address = port_address.into();
+        self
+    }
+
+    /// The sequence number of this packet. Set to 0x00 to disable sequencing.
+    pub fn sequence(mut self, sequence: u8) -> Self {
+        self.output.sequence = sequence;
+        self
+    }
+
+    /// The physical input port from which the DMX512 data was input
+    pub fn physical(mut self, physical: u8) -> Self {
+        self.output.physical = physical;
+        self
+    }
+
+    /// The DMX512 lighting data. This must contain between 1 and 512 channels.
+    pub fn data(mut self, data: impl Into<PaddedData>) -> Self {
+        self.output.data = data.into();
+        self.output.sync_length();
+        self
+    }
+
+    /// Build the `Output`. Returns an error if the data is empty or longer than 512 bytes.
+    pub fn build(self) -> Result<Output> {
+        self.output.data.validate()?;
+        Ok(self.output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ArtCommand, Error};
+
+    #[test]
+    fn build_output() {
+        let output = Output::builder()
+            .port_address(5)
+            .sequence(3)
+            .physical(1)
+            .data(vec![255])
+            .build()
+            .unwrap();
+        assert_eq!(output.port_address, 5.into());
+        assert_eq!(output.sequence, 3);
+        assert_eq!(output.physical, 1);
+
+        let bytes = ArtCommand::Output(output).write_to_buffer().unwrap();
+        assert_eq!(
+            bytes,
+            vec![65, 114, 116, 45, 78, 101, 116, 0, 0, 80, 0, 14, 3, 1, 5, 0, 0, 2, 255, 0]
+        );
+    }
+
+    #[test]
+    fn build_rejects_invalid_data() {
+        assert!(matches!(
+            Output::builder().build(),
+            Err(Error::MessageSizeInvalid { .. })
+        ));
+        assert!(matches!(
+            Output::builder().data(vec![]).build(),
+            Err(Error::MessageSizeInvalid { .. })
+        ));
+        assert!(matches!(
+            Output::builder().data(vec![0; 513]).build(),
+            Err(Error::MessageSizeInvalid { .. })
+        ));
+        assert!(Output::builder().data(vec![0; 512]).build().is_ok());
+    }
+}
@@ -0,0 +1,90 @@
+use super::Output;
+use crate::{Error, Result};
+use alloc::vec::Vec;
+
+impl Output {
+    /// Merge two packets in HTP (highest takes precedence) mode, taking the highest value of each channel.
+    ///
+    /// If the packets have a different length, the missing channels are treated as 0. Returns an error if the packets are for a different `port_address`.
+    pub fn merge_htp(&self, other: &Output) -> Result<Output> {
+        self.merge_with(other, |current, latest| {
+            current.unwrap_or(0).max(latest.unwrap_or(0))
+        })
+    }
+
+    /// Merge two packets in LTP (latest takes precedence) mode, where `other` is the latest packet.
+    ///
+    /// Every channel takes the value of `other`. If the packets have a different length, the channels that `other` does not contain are treated as 0, so the result has the length of the longer packet.
+    /// Returns an error if the packets are for a different `port_address`.
+    pub fn merge_ltp(&self, other: &Output) -> Result<Output> {
+        self.merge_with(other, |_, latest| latest.unwrap_or(0))
+    }
+
+    fn merge_with(
+        &self,
+        other: &Output,
+        merge: impl Fn(Option<u8>, Option<u8>) -> u8,
+    ) -> Result<Output> {
+        if self.port_address != other.port_address {
+            return Err(Error::PortAddressMismatch(
+                self.port_address,
+                other.port_address,
+            ));
+        }
+        let len = self.data.len().max(other.data.len());
+        let data: Vec<u8> = (0..len)
+            .map(|channel| merge(self.data.get(channel), other.data.get(channel)))
+            .collect();
+        Ok(Output {
+            version: other.version,
+            sequence: other.sequence,
+            physical: other.physical,
+            ..Output::new(other.port_address, data)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(data: Vec<u8>) -> Output {
+        Output {
+            data: data.into(),
+            ..Output::default()
+        }
+    }
+
+    #[test]
+    fn merge_htp_differing_lengths() {
+        let a = output(vec![10, 200, 30]);
+        let b = output(vec![100, 20]);
+        assert_eq!(a.merge_htp(&b).unwrap().data.as_slice(), &[100, 200, 30]);
+        assert_eq!(b.merge_htp(&a).unwrap().data.as_slice(), &[100, 200, 30]);
+    }
+
+    #[test]
+    fn merge_ltp_differing_lengths() {
+        let a = output(vec![10, 200, 30]);
+        let b = output(vec![100, 20]);
+        assert_eq!(a.merge_ltp(&b).unwrap().data.as_slice(), &[100, 20, 0]);
+        assert_eq!(b.merge_ltp(&a).unwrap().data.as_slice(), &[10, 200, 30]);
+    }
+
+    #[test]
+    fn merge_port_address_mismatch() {
+        let a = output(vec![1]);
+        let b = Output {
+            port_address: 2.into(),
+            ..output(vec![1])
+        };
+        assert!(matches!(
+            a.merge_htp(&b),
+            Err(Error::PortAddressMismatch(..))
+        ));
+        assert!(matches!(
+            a.merge_ltp(&b),
+            Err(Error::PortAddressMismatch(..))
+        ));
+    }
+}
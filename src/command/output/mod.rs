@@ -1,12 +1,24 @@
+mod borrowed;
+mod builder;
+mod merge;
 #[cfg(test)]
 mod tests;
+mod vlc;
 
+pub use self::borrowed::OutputRef;
+pub use self::builder::OutputBuilder;
+pub use self::vlc::Vlc;
+
+use crate::command::{OpCode, ARTNET_HEADER};
+use crate::convert::Cursor;
 use crate::{command::ARTNET_PROTOCOL_VERSION, convert::Convertable, Error, PortAddress, Result};
-use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
-use std::io::Cursor;
+use alloc::vec;
+use alloc::vec::Vec;
+use byteorder::BigEndian;
+use core::convert::TryFrom;
 
 data_structure! {
-    #[derive(Debug)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
     #[doc = "ArtDmx is the data packet used to transfer DMX512 data. The format is identical for Node to Controller, Node to Node and Controller to Node."]
     #[doc = ""]
     #[doc = "The Data is output through the DMX O/P port corresponding to the Universe setting. In the absence of received ArtDmx packets, each DMX O/P port re-transmits the same frame continuously. "]
@@ -23,19 +35,200 @@ data_structure! {
         pub version: [u8; 2],
         #[doc = "The sequence number is used to ensure that ArtDmx packets are used in the correct order. When Art-Net is carried over a medium such as the Internet, it is possible that ArtDmx packets will reach the receiver out of order. This field is incremented in the range 0x01 to 0xff to allow the receiving node to resequence packets."]
         #[doc = ""]
-        #[doc = "The Sequence field is set to 0x00 to disable this feature"]
+        #[doc = "The Sequence field is set to 0x00 to disable this feature. Use `with_sequence` to make sure that it is not disabled by accident."]
         pub sequence: u8,
         #[doc = "The physical input port from which DMX512 data was input. This field is for information only. Use Universe for data routing"]
         pub physical: u8,
         #[doc = "The 15 bit Port-Address to which this packet is destined"]
         pub port_address: PortAddress,
-        #[doc = "The length of the message, set by the artnet library itself"]
-        pub length: BigEndianLength<Output>,
+        #[doc = "The length of the message, set by the artnet library itself. Read it with `length`, it is computed from `data` when the packet is written."]
+        #[cfg_attr(feature = "serde", serde(skip))]
+        length: BigEndianLength<Output>,
         #[doc = "A variable length array of DMX512 lighting data"]
         pub data: PaddedData,
     }
 }
 
+impl Output {
+    /// Create an `Output` for the given port address and data, with the sequence and physical port set to 0.
+    ///
+    /// The length of the data is validated when the packet is serialized, see [OutputBuilder](struct.OutputBuilder.html) to validate it earlier.
+    pub fn new(port_address: impl Into<PortAddress>, data: impl Into<PaddedData>) -> Output {
+        let mut output = Output {
+            port_address: port_address.into(),
+            data: data.into(),
+            ..Output::default()
+        };
+        output.sync_length();
+        output
+    }
+
+    /// Create an `Output` that carries a full universe of 512 channels, which is always a valid length.
+    pub fn full_universe(port_address: impl Into<PortAddress>, data: [u8; 512]) -> Output {
+        Output::new(port_address, data)
+    }
+
+    /// The highest physical port of a typical node, which has 4 ports
+    pub const MAX_PHYSICAL: u8 = 3;
+
+    /// Set the physical input port, which is informational. Returns an error above [MAX_PHYSICAL](#associatedconstant.MAX_PHYSICAL), because some nodes are confused by other values.
+    ///
+    /// Use `set_physical_with_max` for nodes with more ports, or assign the `physical` field directly to skip the check.
+    pub fn set_physical(&mut self, port: u8) -> Result<()> {
+        self.set_physical_with_max(port, Output::MAX_PHYSICAL)
+    }
+
+    /// Set the physical input port, returning an error if it is above `max`
+    pub fn set_physical_with_max(&mut self, port: u8, max: u8) -> Result<()> {
+        if port > max {
+            return Err(Error::FieldOutOfRange {
+                field: "physical",
+                value: port,
+                max,
+            });
+        }
+        self.physical = port;
+        Ok(())
+    }
+
+    /// Set the sequence number, for receivers that reorder packets. Returns an error for 0x00, because that disables reordering.
+    ///
+    /// To disable reordering on purpose, assign 0x00 to the `sequence` field directly. A [SequenceCounter](struct.SequenceCounter.html) hands out valid numbers.
+    pub fn with_sequence(mut self, sequence: u8) -> Result<Output> {
+        if sequence == 0 {
+            return Err(Error::SequenceZero);
+        }
+        self.sequence = sequence;
+        Ok(self)
+    }
+
+    /// The length of the DMX512 data in bytes, which is always even. For a parsed packet, this is the `length` field of the packet.
+    ///
+    /// `Output::new` and every method of `Output` that changes the data keep this in sync with `data`. After changing `data` directly, call [sync_length](#method.sync_length).
+    pub fn length(&self) -> u16 {
+        self.length
+            .parsed_length
+            .unwrap_or(self.data.padded_len() as u16)
+    }
+
+    /// Store the length of the current data in `length`, e.g. after changing the data of a parsed packet.
+    ///
+    /// The length that is written to a packet is always computed from the data, so this only changes the value that `length` reports.
+    pub fn sync_length(&mut self) {
+        self.length = BigEndianLength::new(self.data.padded_len() as u16);
+    }
+
+    /// Encode the full packet, including the Art-Net header, into the given buffer without allocating. Returns the number of bytes that were written.
+    ///
+    /// This writes the same bytes as [ArtCommand::write_to_buffer](enum.ArtCommand.html#method.write_to_buffer). A buffer of 530 bytes fits every `Output`.
+    /// Returns an error if the data is not valid or if the buffer is too small, in which case the buffer is not changed.
+    pub fn encode_into(&self, buf: &mut [u8]) -> Result<usize> {
+        self.data.validate()?;
+        let data_len = self.data.len_rounded_up();
+        let required = 18 + data_len;
+        if buf.len() < required {
+            return Err(Error::BufferTooSmall {
+                required,
+                available: buf.len(),
+            });
+        }
+        buf[..8].copy_from_slice(ARTNET_HEADER);
+        buf[8..10].copy_from_slice(&OpCode::Output.as_u16().to_le_bytes());
+        buf[10..12].copy_from_slice(&self.version);
+        buf[12] = self.sequence;
+        buf[13] = self.physical;
+        buf[14..16].copy_from_slice(&u16::from(self.port_address).to_le_bytes());
+        buf[16..18].copy_from_slice(&(data_len as u16).to_be_bytes());
+        buf[18..18 + self.data.len()].copy_from_slice(&self.data);
+        // The padding byte, if there is one
+        buf[18 + self.data.len()..required].fill(0);
+        Ok(required)
+    }
+
+    /// Split data that is longer than a universe into packets of 512 channels, on consecutive port addresses starting at `base`.
+    ///
+    /// The last packet contains the remaining channels. Returns an error if a packet would need a port address above [PortAddress::MAX](struct.PortAddress.html#associatedconstant.MAX).
+    pub fn split(base: PortAddress, data: &[u8]) -> Result<Vec<Output>> {
+        data.chunks(512)
+            .enumerate()
+            .map(|(index, chunk)| {
+                let port_address =
+                    PortAddress::try_from(i32::from(u16::from(base)) + index as i32)?;
+                Ok(Output::new(port_address, chunk.to_vec()))
+            })
+            .collect()
+    }
+
+    /// The DMX512 data, limited to the `length` of a parsed packet.
+    ///
+    /// A parsed packet contains every byte after the header, even if the datagram is longer than the `length` field. That length is always even,
+    /// so the padding byte of a packet with an odd number of channels is included, because the packet does not say whether the last byte is padding.
+    /// If the packet was not parsed, this is all of the data.
+    pub fn dmx_data(&self) -> &[u8] {
+        match self.length.parsed_length {
+            Some(length) => &self.data[..self.data.len().min(length as usize)],
+            None => &self.data,
+        }
+    }
+
+    /// A 64 bit FNV-1a hash of the port address and the data as it is written to a packet, to detect changed frames without keeping a copy of the data.
+    ///
+    /// The hash covers all of `data` and the padding byte of an odd number of channels, so a changed channel changes the hash even if the `length` of a parsed packet was not synced.
+    /// Packets that are written as the same bytes have the same hash. This is not a cryptographic hash, different frames can have the same hash.
+    pub fn data_hash(&self) -> u64 {
+        const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        let padding: &[u8] = if self.data.len() == self.data.padded_len() {
+            &[]
+        } else {
+            &[0]
+        };
+        u16::from(self.port_address)
+            .to_le_bytes()
+            .iter()
+            .chain(self.data.as_slice())
+            .chain(padding)
+            .fold(OFFSET_BASIS, |hash, &byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+            })
+    }
+
+    /// Write `values` to the channels of `base` starting at `offset`, e.g. to apply an update of a range of channels.
+    ///
+    /// The data is extended with zeroes if it is too short, and the `length` is updated like [sync_length](#method.sync_length) does, so that [dmx_data](#method.dmx_data) includes the new channels.
+    /// Returns an error if a channel would be 512 or higher, see [PaddedData::patch](struct.PaddedData.html#method.patch).
+    pub fn patch(base: &mut Output, offset: usize, values: &[u8]) -> Result<()> {
+        base.data.patch(offset, values)?;
+        base.sync_length();
+        Ok(())
+    }
+
+    /// Start building an `Output`. See [OutputBuilder](struct.OutputBuilder.html) for more information.
+    pub fn builder() -> OutputBuilder {
+        OutputBuilder::default()
+    }
+
+    /// Iterate over the `(channel, value)` pairs of the [dmx_data](#method.dmx_data). Channels are indexed from 0.
+    ///
+    /// The padding byte of a packet that was built is not included. For a parsed packet, the iterator stops at the `length` field, which includes the padding byte of a packet with an odd number of channels.
+    pub fn channels(&self) -> Channels<'_> {
+        self.dmx_data().iter().copied().enumerate()
+    }
+}
+
+/// An iterator over the `(channel, value)` pairs of an `Output`, see [Output::channels](struct.Output.html#method.channels)
+pub type Channels<'a> = core::iter::Enumerate<core::iter::Copied<core::slice::Iter<'a, u8>>>;
+
+impl<'a> IntoIterator for &'a Output {
+    type Item = (usize, u8);
+    type IntoIter = Channels<'a>;
+
+    fn into_iter(self) -> Channels<'a> {
+        self.channels()
+    }
+}
+
 impl Default for Output {
     fn default() -> Output {
         Output {
@@ -49,19 +242,122 @@ impl Default for Output {
     }
 }
 
-#[derive(Default)]
+#[derive(Default, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 #[doc = "Data in an ArtDmx data packet."]
 pub struct PaddedData {
     inner: Vec<u8>,
 }
 
 impl PaddedData {
-    fn len(&self) -> usize {
+    /// Create DMX512 data, returning an error if it is longer than 512 channels.
+    ///
+    /// Converting with `From<Vec<u8>>` does not check the length, so data that is too long is only rejected when the packet is written.
+    /// Use this constructor to catch it where the data is created.
+    pub fn try_new(inner: Vec<u8>) -> Result<Self> {
+        if inner.len() > 512 {
+            return Err(Error::MessageSizeInvalid {
+                message: inner,
+                allowed_size: 0..513,
+            });
+        }
+        Ok(Self { inner })
+    }
+
+    /// The value of the given channel, or `None` if the data does not contain this channel.
+    ///
+    /// Channels are indexed from 0.
+    pub fn get(&self, channel: usize) -> Option<u8> {
+        self.inner.get(channel).copied()
+    }
+
+    /// Set the value of the given channel. Channels are indexed from 0.
+    ///
+    /// If the data is shorter than the channel, it is extended with zeroes. Returns an error if the channel is 512 or higher.
+    pub fn set(&mut self, channel: usize, value: u8) -> Result<()> {
+        self.patch(channel, &[value])
+    }
+
+    /// Write `values` to the channels starting at `offset`. Channels are indexed from 0.
+    ///
+    /// If the data is shorter than the last channel, it is extended with zeroes. Returns an error if a channel would be 512 or higher, in which case the data is not changed.
+    pub fn patch(&mut self, offset: usize, values: &[u8]) -> Result<()> {
+        let end = offset.saturating_add(values.len());
+        if end > 512 {
+            return Err(Error::InvalidChannel(end - 1));
+        }
+        if end > self.inner.len() {
+            self.inner.resize(end, 0);
+        }
+        self.inner[offset..end].copy_from_slice(values);
+        Ok(())
+    }
+
+    /// The DMX512 data as a slice
+    pub fn as_slice(&self) -> &[u8] {
+        &self.inner
+    }
+
+    /// The number of bytes in this data. For data that was created, this is the number of DMX512 channels, without the padding byte that is added when the length is odd.
+    ///
+    /// Parsed data contains every byte after the header of the packet, including the padding byte and any bytes after the `length` field.
+    /// Use [Output::dmx_data](struct.Output.html#method.dmx_data) for the channels up to the `length` field.
+    ///
+    /// ```
+    /// use artnet_protocol::ArtCommand;
+    ///
+    /// let bytes = [
+    ///     65, 114, 116, 45, 78, 101, 116, 0, 0, 80, 0, 14, 0, 0, 1, 0, 0, 4, 10, 20, 30, 40, 0xff, 0xff,
+    /// ];
+    /// match ArtCommand::from_buffer(&bytes).unwrap() {
+    ///     ArtCommand::Output(output) => {
+    ///         assert_eq!(output.data.len(), 6);
+    ///         assert_eq!(output.dmx_data().len(), 4);
+    ///         assert_eq!(output.data[2], 30);
+    ///     }
+    ///     command => panic!("Expected an Output, got {:?}", command),
+    /// }
+    /// ```
+    pub fn len(&self) -> usize {
         self.inner.len()
     }
-    fn len_rounded_up(&self) -> usize {
+
+    /// Whether this data contains no channels. Empty data can not be written to a packet.
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    /// The length of this data when it is written to a packet. This is always even, as odd lengths are padded with a zero byte.
+    pub fn padded_len(&self) -> usize {
+        self.len_rounded_up()
+    }
+
+    pub(crate) fn validate(&self) -> Result<()> {
+        let len = self.len();
+        if len == 0 {
+            // packets must be between 2 and 512 bytes, 1 gets padded up, but 0 is invalid
+            return Err(Error::MessageSizeInvalid {
+                message: vec![],
+                allowed_size: 1..513,
+            });
+        }
+        if len > 512 {
+            // packets must be between 2 and 512 bytes
+            let inner = self.inner.clone();
+            return Err(Error::MessageSizeInvalid {
+                message: inner,
+                allowed_size: 1..513,
+            });
+        }
+        Ok(())
+    }
+    pub(crate) fn len_rounded_up(&self) -> usize {
         let mut len = self.inner.len();
-        if len % 2 != 0 {
+        if len % 2 == 1 {
             len += 1;
         }
         len
@@ -74,6 +370,14 @@ impl From<PaddedData> for Vec<u8> {
     }
 }
 
+impl core::ops::Deref for PaddedData {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.inner
+    }
+}
+
 impl AsRef<Vec<u8>> for PaddedData {
     fn as_ref(&self) -> &Vec<u8> {
         self.inner.as_ref()
@@ -92,45 +396,59 @@ impl From<Vec<u8>> for PaddedData {
     }
 }
 
-impl std::fmt::Debug for PaddedData {
-    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+/// Copy the data from a slice, e.g. a part of a larger buffer:
+///
+/// ```
+/// use artnet_protocol::Output;
+///
+/// let frame = [255u8; 1024];
+/// let channels: &[u8] = &frame[512..];
+/// let output = Output::new(1, channels);
+/// assert_eq!(output.data.len(), 512);
+/// ```
+impl From<&[u8]> for PaddedData {
+    fn from(data: &[u8]) -> Self {
+        Self {
+            inner: data.to_vec(),
+        }
+    }
+}
+
+/// Like `From<Vec<u8>>`, the length is only checked when the packet is written, arrays of up to 512 channels are always valid.
+impl<const N: usize> From<[u8; N]> for PaddedData {
+    fn from(data: [u8; N]) -> Self {
+        Self {
+            inner: data.to_vec(),
+        }
+    }
+}
+
+impl core::fmt::Debug for PaddedData {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(fmt, "{:?}", self.inner)
     }
 }
 
 impl<T> Convertable<T> for PaddedData {
     fn from_cursor(cursor: &mut Cursor<&[u8]>) -> Result<Self> {
-        let remaining = cursor.get_ref();
-        let inner = remaining[cursor.position() as usize..].to_vec();
+        let inner = cursor.read_to_end()?.to_vec();
         Ok(Self { inner })
     }
 
     fn write_to_buffer(&self, buffer: &mut Vec<u8>, _: &T) -> Result<()> {
-        let len = self.len();
-        if len == 0 {
-            // packets must be between 2 and 512 bytes, 1 gets padded up, but 0 is invalid
-            return Err(Error::MessageSizeInvalid {
-                message: vec![],
-                allowed_size: 2..512,
-            });
-        }
-        if len > 512 {
-            // packets must be between 2 and 512 bytes
-            let inner = self.inner.clone();
-            return Err(Error::MessageSizeInvalid {
-                message: inner,
-                allowed_size: 2..512,
-            });
-        }
+        self.validate()?;
 
         buffer.extend_from_slice(&self.inner[..]);
-        if len % 2 != 0 {
+        if self.len() % 2 == 1 {
             // the data of an output needs to be an even size, so we add an additional 0-byte
             buffer.push(0);
         }
         Ok(())
     }
 
+    fn serialized_len(&self) -> usize {
+        self.len_rounded_up()
+    }
     #[cfg(test)]
     fn get_test_value() -> Self {
         PaddedData {
@@ -143,14 +461,22 @@ impl<T> Convertable<T> for PaddedData {
     }
 }
 
-#[derive(Default)]
 pub struct BigEndianLength<T> {
     parsed_length: Option<u16>,
-    _pd: std::marker::PhantomData<T>,
+    _pd: core::marker::PhantomData<T>,
 }
 
-impl<T> std::fmt::Debug for BigEndianLength<T> {
-    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl<T> Default for BigEndianLength<T> {
+    fn default() -> Self {
+        BigEndianLength {
+            parsed_length: None,
+            _pd: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<T> core::fmt::Debug for BigEndianLength<T> {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
         if let Some(len) = &self.parsed_length {
             write!(fmt, "{}", len)
         } else {
@@ -159,7 +485,25 @@ impl<T> std::fmt::Debug for BigEndianLength<T> {
     }
 }
 
-impl<T> std::ops::Deref for BigEndianLength<T> {
+impl<T> Clone for BigEndianLength<T> {
+    fn clone(&self) -> Self {
+        BigEndianLength {
+            parsed_length: self.parsed_length,
+            _pd: core::marker::PhantomData,
+        }
+    }
+}
+
+/// Lengths are equal if they were both parsed with the same value, or if they were both not parsed
+impl<T> PartialEq for BigEndianLength<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.parsed_length == other.parsed_length
+    }
+}
+
+impl<T> Eq for BigEndianLength<T> {}
+
+impl<T> core::ops::Deref for BigEndianLength<T> {
     type Target = u16;
 
     fn deref(&self) -> &u16 {
@@ -167,24 +511,31 @@ impl<T> std::ops::Deref for BigEndianLength<T> {
     }
 }
 
-impl Convertable<Output> for BigEndianLength<Output> {
-    fn from_cursor(cursor: &mut std::io::Cursor<&[u8]>) -> crate::Result<Self> {
-        let length = cursor.read_u16::<BigEndian>().map_err(Error::CursorEof)?;
-        Ok(BigEndianLength {
+impl<T> BigEndianLength<T> {
+    pub(crate) fn new(length: u16) -> Self {
+        BigEndianLength {
             parsed_length: Some(length),
-            _pd: std::marker::PhantomData,
-        })
+            _pd: core::marker::PhantomData,
+        }
     }
-    fn write_to_buffer(&self, buffer: &mut Vec<u8>, context: &Output) -> crate::Result<()> {
-        let len = context.data.len_rounded_up() as u16;
-        buffer.write_u16::<BigEndian>(len).map_err(Error::CursorEof)
+
+    pub(crate) fn read(cursor: &mut Cursor<&[u8]>) -> Result<Self> {
+        let length = cursor.read_u16::<BigEndian>()?;
+        Ok(BigEndianLength::new(length))
     }
-    #[cfg(test)]
-    fn get_test_value() -> Self {
-        Default::default()
+
+    pub(crate) fn write(buffer: &mut Vec<u8>, len: usize) -> Result<()> {
+        // Never truncate a length that does not fit in the field
+        let len = u16::try_from(len).map_err(|_| Error::MessageSizeInvalid {
+            message: vec![],
+            allowed_size: 0..usize::from(u16::MAX) + 1,
+        })?;
+        buffer.extend_from_slice(&len.to_be_bytes());
+        Ok(())
     }
+
     #[cfg(test)]
-    fn is_equal(&self, other: &Self) -> bool {
+    pub(crate) fn is_equal_after_parsing(&self, other: &Self) -> bool {
         if (self.parsed_length.is_none() && other.parsed_length.is_some())
             || (self.parsed_length.is_some() && other.parsed_length.is_none())
         {
@@ -197,3 +548,37 @@ impl Convertable<Output> for BigEndianLength<Output> {
         }
     }
 }
+
+/// A packet that is followed by a variable length payload, whose length is written by a `BigEndianLength`
+pub(crate) trait HasPayloadLen {
+    /// The number of bytes that will be written for the payload. Returns an error if the payload is not valid for the packet.
+    fn payload_len(&self) -> Result<usize>;
+}
+
+impl<T: HasPayloadLen> Convertable<T> for BigEndianLength<T> {
+    fn from_cursor(cursor: &mut crate::convert::Cursor<&[u8]>) -> crate::Result<Self> {
+        Self::read(cursor)
+    }
+    fn write_to_buffer(&self, buffer: &mut Vec<u8>, context: &T) -> crate::Result<()> {
+        Self::write(buffer, context.payload_len()?)
+    }
+    fn serialized_len(&self) -> usize {
+        2
+    }
+    #[cfg(test)]
+    fn get_test_value() -> Self {
+        Default::default()
+    }
+    #[cfg(test)]
+    fn is_equal(&self, other: &Self) -> bool {
+        self.is_equal_after_parsing(other)
+    }
+}
+
+impl HasPayloadLen for Output {
+    fn payload_len(&self) -> Result<usize> {
+        // Check the 2..=512 window before writing the length, the data is only checked after it
+        self.data.validate()?;
+        Ok(self.data.len_rounded_up())
+    }
+}
@@ -1,5 +1,5 @@
 use super::*;
-use crate::ArtCommand;
+use crate::{ArtCommand, ParseOptions, ProtocolVersion};
 
 mod serialization {
     use super::*;
@@ -31,6 +31,71 @@ mod serialization {
         .concat(); //is padded with zero to even length of two
         assert_eq!(bytes, comparison)
     }
+    #[test]
+    fn full_universe() {
+        let mut data = [0; 512];
+        data[0] = 1;
+        data[511] = 255;
+        let output = Output::full_universe(PortAddress::try_from(5).unwrap(), data);
+        assert_eq!(output.data.as_slice(), &data[..]);
+
+        let bytes = ArtCommand::Output(output).write_to_buffer().unwrap();
+        assert_eq!(bytes.len(), 512 + 18);
+        assert_eq!(&bytes[14..18], &[5, 0, 2, 0]);
+        assert_eq!(bytes[18], 1);
+        assert_eq!(bytes[529], 255);
+    }
+
+    #[test]
+    fn new_matches_default_spread() {
+        let output = Output::new(3, vec![1, 2, 3]);
+        let spread = Output {
+            port_address: 3.into(),
+            data: vec![1, 2, 3].into(),
+            ..Output::default()
+        };
+        assert_eq!(output.version, ARTNET_PROTOCOL_VERSION);
+        assert_eq!(
+            ArtCommand::Output(output).write_to_buffer().unwrap(),
+            ArtCommand::Output(spread).write_to_buffer().unwrap()
+        );
+    }
+
+    #[test]
+    fn with_sequence_rejects_zero() {
+        let output = Output::new(1, vec![1]).with_sequence(5).unwrap();
+        assert_eq!(output.sequence, 5);
+        assert!(matches!(
+            Output::new(1, vec![1]).with_sequence(0),
+            Err(Error::SequenceZero)
+        ));
+
+        // Sequencing can still be disabled on purpose
+        let mut output = output;
+        output.sequence = 0;
+        let bytes = ArtCommand::Output(output).write_to_buffer().unwrap();
+        assert_eq!(bytes[12], 0);
+    }
+
+    #[test]
+    fn set_physical_up_to_3() {
+        let mut output = Output::new(1, vec![1]);
+        output.set_physical(3).unwrap();
+        assert_eq!(output.physical, 3);
+        assert!(matches!(
+            output.set_physical(4),
+            Err(Error::FieldOutOfRange {
+                field: "physical",
+                value: 4,
+                max: 3
+            })
+        ));
+        assert_eq!(output.physical, 3);
+
+        output.set_physical_with_max(4, 7).unwrap();
+        assert_eq!(output.physical, 4);
+    }
+
     #[test]
     fn test_invalid_length() {
         let command = ArtCommand::Output(Output {
@@ -72,11 +137,73 @@ mod serialization {
         });
         assert!(command.write_to_buffer().is_err());
     }
+
+    #[test]
+    fn oversized_length_is_not_truncated() {
+        // 65_538 would be written as 2 if the length was truncated to a u16
+        let output = Output {
+            data: vec![0xff; 65_538].into(),
+            ..Output::default()
+        };
+        let mut buffer = Vec::new();
+        match Convertable::write_to_buffer(&output.length, &mut buffer, &output) {
+            Err(Error::MessageSizeInvalid { message, .. }) => assert_eq!(message.len(), 65_538),
+            result => panic!("Expected a size error, got {:?}", result),
+        }
+        assert!(buffer.is_empty());
+
+        assert!(matches!(
+            BigEndianLength::<Output>::write(&mut buffer, 65_538),
+            Err(Error::MessageSizeInvalid { .. })
+        ));
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn length_is_the_padded_payload_len() {
+        let output = Output::new(1, vec![1, 2, 3]);
+        assert_eq!(output.payload_len().unwrap(), 4);
+        let mut buffer = Vec::new();
+        Convertable::write_to_buffer(&output.length, &mut buffer, &output).unwrap();
+        assert_eq!(buffer, [0, 4]);
+
+        let output = Output {
+            data: vec![0xff; 513].into(),
+            ..Output::default()
+        };
+        assert!(output.payload_len().is_err());
+    }
 }
 
 mod parsing {
     use super::*;
 
+    #[test]
+    fn truncated_port_address() {
+        let packet = &[65, 114, 116, 45, 78, 101, 116, 0, 0, 80, 0, 14, 0, 0, 1];
+        match ArtCommand::from_buffer(packet) {
+            Err(Error::OpcodeError("Output", inner)) => match *inner {
+                Error::CursorEof { offset, context } => {
+                    assert_eq!(offset, 14);
+                    assert_eq!(context, "Output::port_address");
+                }
+                e => panic!("Expected a CursorEof, got {:?}", e),
+            },
+            result => panic!("Expected an Output error, got {:?}", result),
+        }
+
+        match ArtCommand::from_buffer_ref(packet) {
+            Err(Error::OpcodeError("Output", inner)) => assert!(matches!(
+                *inner,
+                Error::CursorEof {
+                    offset: 14,
+                    context: "OutputRef::port_address"
+                }
+            )),
+            result => panic!("Expected an Output error, got {:?}", result),
+        }
+    }
+
     #[test]
     fn protver_below_14() {
         // Because Art-Net is guaranteed to be backwards-compatible,
@@ -88,6 +215,7 @@ mod parsing {
         let command = ArtCommand::from_buffer(packet).unwrap();
         if let ArtCommand::Output(output) = command {
             assert_eq!(output.version, [0, 0]);
+            assert!(output.protocol_version() < ProtocolVersion::CURRENT);
             assert_eq!(output.sequence, 0);
             assert_eq!(output.physical, 0);
             assert_eq!(output.port_address, 1.into());
@@ -96,6 +224,126 @@ mod parsing {
         }
     }
 
+    #[test]
+    fn protver_below_14_with_options() {
+        let packet = &[
+            65, 114, 116, 45, 78, 101, 116, 0, 0, 80, 0, 0, 0, 0, 1, 0, 0, 2, 255, 255,
+        ];
+        let lenient = ArtCommand::from_buffer_with(packet, &ParseOptions::default());
+        assert!(matches!(lenient, Ok(ArtCommand::Output(_))));
+
+        let strict = ParseOptions {
+            strict_version: true,
+        };
+        assert!(matches!(
+            ArtCommand::from_buffer_with(packet, &strict),
+            Err(Error::UnsupportedVersion { got: 0 })
+        ));
+
+        let current = ArtCommand::Output(Output {
+            data: vec![255, 255].into(),
+            ..Output::default()
+        })
+        .write_to_buffer()
+        .unwrap();
+        assert!(ArtCommand::from_buffer_with(&current, &strict).is_ok());
+    }
+
+    #[test]
+    fn sync_length_after_changing_data() {
+        let packet = &[
+            65, 114, 116, 45, 78, 101, 116, 0, 0, 80, 0, 14, 0, 0, 1, 0, 0, 2, 255, 255,
+        ];
+        let mut output = match ArtCommand::from_buffer(packet).unwrap() {
+            ArtCommand::Output(output) => output,
+            command => panic!("Expected an Output, got {:?}", command),
+        };
+        output.data.set(4, 1).unwrap();
+        assert_eq!(*output.length, 2);
+        output.sync_length();
+        assert_eq!(*output.length, 6);
+
+        let bytes = ArtCommand::Output(output).write_to_buffer().unwrap();
+        assert_eq!(&bytes[16..18], &[0, 6]);
+        match ArtCommand::from_buffer(&bytes).unwrap() {
+            ArtCommand::Output(output) => assert_eq!(*output.length, 6),
+            command => panic!("Expected an Output, got {:?}", command),
+        }
+    }
+
+    #[test]
+    fn length_follows_the_data() {
+        let mut output = Output::new(1, vec![1, 2, 3]);
+        assert_eq!(output.length(), 4);
+        Output::patch(&mut output, 4, &[5]).unwrap();
+        assert_eq!(output.length(), 6);
+
+        // Changing the data directly needs a sync
+        output.data.as_mut().truncate(2);
+        assert_eq!(output.length(), 6);
+        output.sync_length();
+        assert_eq!(output.length(), 2);
+        assert_eq!(Output::default().length(), 0);
+    }
+
+    #[test]
+    fn data_hash_detects_changes() {
+        let output = Output::new(1, vec![0; 512]);
+        assert_eq!(output.data_hash(), Output::new(1, vec![0; 512]).data_hash());
+
+        let mut changed = output.clone();
+        changed.data.set(100, 1).unwrap();
+        assert_ne!(output.data_hash(), changed.data_hash());
+        assert_ne!(output.data_hash(), Output::new(2, vec![0; 512]).data_hash());
+    }
+
+    #[test]
+    fn data_hash_of_a_changed_parsed_packet() {
+        let bytes = ArtCommand::Output(Output::new(1, vec![1, 2, 3]))
+            .write_to_buffer()
+            .unwrap();
+        let mut output = match ArtCommand::from_buffer(&bytes).unwrap() {
+            ArtCommand::Output(output) => output,
+            command => panic!("Expected an Output, got {:?}", command),
+        };
+        let hash = output.data_hash();
+        assert_eq!(hash, Output::new(1, vec![1, 2, 3]).data_hash());
+
+        // Changing the data without syncing the length still changes the hash
+        output.data.set(6, 7).unwrap();
+        assert_ne!(output.data_hash(), hash);
+        let hash = output.data_hash();
+        Output::patch(&mut output, 1, &[20]).unwrap();
+        assert_ne!(output.data_hash(), hash);
+    }
+
+    #[test]
+    fn dmx_data_is_limited_to_length() {
+        let bytes = ArtCommand::Output(Output::new(1, vec![1, 2, 3]))
+            .write_to_buffer()
+            .unwrap();
+        let output = match ArtCommand::from_buffer(&bytes).unwrap() {
+            ArtCommand::Output(output) => output,
+            command => panic!("Expected an Output, got {:?}", command),
+        };
+        assert_eq!(&bytes[16..18], &[0, 4]);
+        assert_eq!(output.dmx_data().len(), 4);
+        assert_eq!(output.dmx_data(), &[1, 2, 3, 0]);
+
+        // Bytes after the length of the packet are not DMX data
+        let packet = &[
+            65, 114, 116, 45, 78, 101, 116, 0, 0, 80, 0, 14, 0, 0, 1, 0, 0, 2, 255, 255, 7, 7,
+        ];
+        let output = match ArtCommand::from_buffer(packet).unwrap() {
+            ArtCommand::Output(output) => output,
+            command => panic!("Expected an Output, got {:?}", command),
+        };
+        assert_eq!(output.data.len(), 4);
+        assert_eq!(output.dmx_data(), &[255, 255]);
+
+        assert_eq!(Output::new(1, vec![1, 2, 3]).dmx_data(), &[1, 2, 3]);
+    }
+
     #[test]
     fn invalid_port_address() {
         // Here Port-Address is 32_768
@@ -111,3 +359,339 @@ mod parsing {
         .is_err());
     }
 }
+
+mod padded_data {
+    use super::*;
+
+    #[test]
+    fn get_out_of_range() {
+        let data = PaddedData::from(vec![1, 2, 3]);
+        assert_eq!(data.get(0), Some(1));
+        assert_eq!(data.get(2), Some(3));
+        assert_eq!(data.get(3), None);
+        assert_eq!(data.get(600), None);
+        assert_eq!(data.as_slice(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn set_extends_data() {
+        let mut data = PaddedData::default();
+        data.set(0, 10).unwrap();
+        assert_eq!(data.as_slice(), &[10]);
+        data.set(4, 20).unwrap();
+        assert_eq!(data.as_slice(), &[10, 0, 0, 0, 20]);
+        data.set(1, 30).unwrap();
+        assert_eq!(data.as_slice(), &[10, 30, 0, 0, 20]);
+
+        data.set(511, 40).unwrap();
+        assert_eq!(data.len(), 512);
+        assert_eq!(data.get(511), Some(40));
+        assert!(matches!(data.set(512, 50), Err(Error::InvalidChannel(512))));
+        assert_eq!(data.len(), 512);
+    }
+
+    #[test]
+    fn patch_in_range() {
+        let mut output = Output::new(1, vec![1, 2, 3, 4]);
+        Output::patch(&mut output, 1, &[20, 30]).unwrap();
+        assert_eq!(output.data.as_slice(), &[1, 20, 30, 4]);
+
+        // Writing past the end extends the data with zeroes
+        Output::patch(&mut output, 6, &[70, 80]).unwrap();
+        assert_eq!(output.data.as_slice(), &[1, 20, 30, 4, 0, 0, 70, 80]);
+
+        Output::patch(&mut output, 510, &[1, 2]).unwrap();
+        assert_eq!(output.data.len(), 512);
+        assert_eq!(output.data.get(511), Some(2));
+    }
+
+    #[test]
+    fn patch_past_the_parsed_length() {
+        let packet = &[
+            65, 114, 116, 45, 78, 101, 116, 0, 0, 80, 0, 14, 0, 0, 1, 0, 0, 2, 1, 2,
+        ];
+        let mut output = match ArtCommand::from_buffer(packet).unwrap() {
+            ArtCommand::Output(output) => output,
+            command => panic!("Expected an Output, got {:?}", command),
+        };
+        assert_eq!(output.dmx_data(), &[1, 2]);
+        let hash = output.data_hash();
+
+        Output::patch(&mut output, 2, &[9, 9, 9]).unwrap();
+        assert_eq!(*output.length, 6);
+        assert_eq!(output.dmx_data().len(), 5);
+        assert_eq!(output.dmx_data(), &[1, 2, 9, 9, 9]);
+        assert_eq!(output.channels().count(), 5);
+        assert_ne!(output.data_hash(), hash);
+    }
+
+    #[test]
+    fn patch_past_512_channels() {
+        let mut output = Output::new(1, vec![1, 2, 3, 4]);
+        assert!(matches!(
+            Output::patch(&mut output, 510, &[1, 2, 3]),
+            Err(Error::InvalidChannel(512))
+        ));
+        assert!(matches!(
+            Output::patch(&mut output, usize::MAX, &[1]),
+            Err(Error::InvalidChannel(_))
+        ));
+        assert_eq!(output.data.as_slice(), &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn deref_to_slice() {
+        let data = PaddedData::from(vec![1, 2, 3]);
+        assert_eq!(data.len(), 3);
+        assert_eq!(data.padded_len(), 4);
+        assert!(!data.is_empty());
+        assert_eq!(data[1], 2);
+        assert_eq!(data.iter().sum::<u8>(), 6);
+        assert!(PaddedData::default().is_empty());
+    }
+
+    #[test]
+    fn from_slice_and_array() {
+        let slice: &[u8] = &[1, 2, 3];
+        assert_eq!(PaddedData::from(slice), PaddedData::from(vec![1, 2, 3]));
+        assert_eq!(PaddedData::from([1, 2, 3]), PaddedData::from(vec![1, 2, 3]));
+        assert_eq!(Output::new(1, [0; 512]).data.len(), 512);
+    }
+
+    #[test]
+    fn try_new_rejects_too_long_data() {
+        match PaddedData::try_new(vec![0; 600]) {
+            Err(Error::MessageSizeInvalid {
+                message,
+                allowed_size,
+            }) => {
+                assert_eq!(message.len(), 600);
+                assert_eq!(allowed_size, 0..513);
+            }
+            result => panic!("Expected a size error, got {:?}", result),
+        }
+        let data = PaddedData::try_new(vec![1; 512]).unwrap();
+        assert_eq!(data.len(), 512);
+        assert!(PaddedData::try_new(vec![]).is_ok());
+    }
+
+    #[test]
+    fn validate_reports_the_accepted_lengths() {
+        for (data, accepted) in [
+            (vec![], false),
+            (vec![1], true),
+            (vec![1; 512], true),
+            (vec![1; 513], false),
+        ] {
+            match PaddedData::from(data.clone()).validate() {
+                Ok(()) => assert!(accepted),
+                Err(Error::MessageSizeInvalid { allowed_size, .. }) => {
+                    assert!(!accepted);
+                    assert_eq!(allowed_size, 1..513);
+                    assert!(!allowed_size.contains(&data.len()));
+                }
+                result => panic!("Expected a size error, got {:?}", result),
+            }
+        }
+    }
+}
+
+mod equality {
+    use super::*;
+
+    #[test]
+    fn identical_outputs_are_equal() {
+        let first = ArtCommand::Output(Output::new(3, vec![1, 2, 3]));
+        let mut second = Output {
+            port_address: 3.into(),
+            data: vec![1, 2, 3].into(),
+            ..Output::default()
+        };
+        second.sync_length();
+        assert_eq!(first, ArtCommand::Output(second));
+        assert_ne!(first, ArtCommand::Output(Output::new(3, vec![1, 2, 4])));
+        assert_ne!(first, ArtCommand::Output(Output::new(4, vec![1, 2, 3])));
+    }
+
+    #[test]
+    fn lengths_compare_after_parsing() {
+        let bytes = ArtCommand::Output(Output::new(1, vec![1, 2]))
+            .write_to_buffer()
+            .unwrap();
+        let first = ArtCommand::from_buffer(&bytes).unwrap();
+        let second = ArtCommand::from_buffer(&bytes).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first, ArtCommand::Output(Output::new(1, vec![1, 2])));
+        // A parsed length is not equal to a length that is not known yet
+        let unsynced = Output {
+            data: vec![1, 2].into(),
+            ..Output::default()
+        };
+        assert_ne!(first, ArtCommand::Output(unsynced));
+    }
+}
+
+mod clone {
+    use super::*;
+
+    #[test]
+    fn clone_of_parsed_output_is_independent() {
+        let packet = &[
+            65, 114, 116, 45, 78, 101, 116, 0, 0, 80, 0, 14, 0, 0, 1, 0, 0, 2, 255, 255,
+        ];
+        let original = match ArtCommand::from_buffer(packet).unwrap() {
+            ArtCommand::Output(output) => output,
+            command => panic!("Expected an Output, got {:?}", command),
+        };
+        let mut clone = original.clone();
+        assert_eq!(clone.length.parsed_length, Some(2));
+        assert_eq!(clone, original);
+
+        clone.data.set(0, 1).unwrap();
+        assert_eq!(original.data.as_slice(), &[255, 255]);
+        assert_eq!(clone.data.as_slice(), &[1, 255]);
+        assert_ne!(clone, original);
+    }
+}
+
+mod channels {
+    use super::*;
+
+    #[test]
+    fn iterates_unpadded_data() {
+        let output = Output {
+            data: vec![10, 20, 30].into(),
+            ..Output::default()
+        };
+        let mut count = 0;
+        for (channel, value) in &output {
+            assert_eq!(value, 10 * (channel as u8 + 1));
+            count += 1;
+        }
+        assert_eq!(count, 3);
+        assert_eq!(
+            output.channels().collect::<Vec<_>>(),
+            vec![(0, 10), (1, 20), (2, 30)]
+        );
+    }
+
+    #[test]
+    fn parsed_packet_stops_at_the_length() {
+        let mut bytes = ArtCommand::Output(Output::new(1, vec![10, 20, 30]))
+            .write_to_buffer()
+            .unwrap();
+        // Bytes after the length of the packet are not channels
+        bytes.extend_from_slice(&[0xff, 0xff]);
+        let output = match ArtCommand::from_buffer(&bytes).unwrap() {
+            ArtCommand::Output(output) => output,
+            command => panic!("Expected an Output, got {:?}", command),
+        };
+        // The length is 4, so the padding byte can not be told apart from a fourth channel
+        assert_eq!(
+            output.channels().collect::<Vec<_>>(),
+            vec![(0, 10), (1, 20), (2, 30), (3, 0)]
+        );
+        assert_eq!((&output).into_iter().count(), 4);
+    }
+}
+
+mod encode_into {
+    use super::*;
+
+    #[test]
+    fn exactly_sized_buffer() {
+        for data in [vec![1, 2, 3], vec![0xff; 512]] {
+            let output = Output {
+                sequence: 7,
+                physical: 1,
+                ..Output::new(PortAddress::try_from(0x1234).unwrap(), data)
+            };
+            let expected = ArtCommand::Output(output.clone())
+                .write_to_buffer()
+                .unwrap();
+            let mut buffer = [0xaa; 530];
+            let written = output.encode_into(&mut buffer[..expected.len()]).unwrap();
+            assert_eq!(written, expected.len());
+            assert_eq!(&buffer[..written], &expected[..]);
+        }
+    }
+
+    #[test]
+    fn too_small_buffer() {
+        let output = Output::new(1, vec![1, 2, 3]);
+        let mut buffer = [0xaa; 21];
+        assert!(matches!(
+            output.encode_into(&mut buffer),
+            Err(Error::BufferTooSmall {
+                required: 22,
+                available: 21
+            })
+        ));
+        assert_eq!(buffer, [0xaa; 21]);
+        assert!(Output::new(1, vec![]).encode_into(&mut [0; 530]).is_err());
+    }
+}
+
+mod split {
+    use super::*;
+
+    #[test]
+    fn splits_into_consecutive_universes() {
+        let data: Vec<u8> = (0..1100).map(|channel| channel as u8).collect();
+        let outputs = Output::split(4.into(), &data).unwrap();
+        assert_eq!(outputs.len(), 3);
+        for (index, (output, chunk)) in outputs.iter().zip(data.chunks(512)).enumerate() {
+            assert_eq!(output.port_address, (4 + index as u8).into());
+            assert_eq!(output.data.as_slice(), chunk);
+        }
+        assert_eq!(
+            outputs
+                .iter()
+                .map(|output| output.data.len())
+                .collect::<Vec<_>>(),
+            vec![512, 512, 76]
+        );
+        assert!(Output::split(4.into(), &[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn fails_past_the_last_port_address() {
+        assert_eq!(Output::split(PortAddress::MAX, &[0; 512]).unwrap().len(), 1);
+        assert!(matches!(
+            Output::split(PortAddress::MAX, &[0; 513]),
+            Err(Error::InvalidPortAddress(32_768))
+        ));
+    }
+}
+
+mod serialized_len {
+    use super::*;
+
+    #[test]
+    fn matches_buffer_length() {
+        for len in [1, 2, 3, 100, 511, 512] {
+            let command = ArtCommand::Output(Output {
+                data: vec![0xff; len].into(),
+                ..Output::default()
+            });
+            let expected = 18 + len + len % 2;
+            assert_eq!(command.serialized_len(), expected, "length {}", len);
+            assert_eq!(command.write_to_buffer().unwrap().len(), expected);
+        }
+    }
+}
+
+mod fuzzing {
+    use super::*;
+
+    #[test]
+    fn truncated_buffers_do_not_panic() {
+        let packet = ArtCommand::Output(Output::new(1, vec![1, 2, 3, 4]))
+            .write_to_buffer()
+            .unwrap();
+        for len in 0..packet.len() {
+            let _ = ArtCommand::from_buffer(&packet[..len]);
+            let _ = ArtCommand::from_buffer_ref(&packet[..len]);
+        }
+    }
+}
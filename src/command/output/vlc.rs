@@ -0,0 +1,219 @@
+use super::{Output, PaddedData};
+use crate::{Error, PortAddress, Result, VlcFlags};
+use alloc::vec::Vec;
+use byteorder::{BigEndian, ByteOrder};
+
+/// The magic number at the start of the data of an ArtVlc packet
+const MAGIC: [u8; 3] = [0x41, 0x4c, 0x45];
+
+/// The length of the VLC header, including the magic number
+const HEADER_LEN: usize = 22;
+
+/// The maximum length of a VLC payload
+const MAX_PAYLOAD_LEN: usize = 480;
+
+/// The payload of an ArtVlc packet, which is used to send Visible Light Communication data to VLC-capable fixtures.
+///
+/// ArtVlc is not a separate opcode, it is sent as the DMX512 data of a packet, starting with a magic number.
+/// The specification sends it with an `Nzs` with start code `0x91`, see [Vlc::START_CODE](#associatedconstant.START_CODE).
+/// Use [Output::from_vlc](struct.Output.html#method.from_vlc) and [Output::vlc](struct.Output.html#method.vlc) to embed and extract it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Vlc {
+    /// The flags of this payload
+    pub flags: VlcFlags,
+    /// The transaction number, which is used to match replies to their request
+    pub transaction: u16,
+    /// The address of the slot for which the payload is intended. 0 addresses all slots, 1 to 512 address a single slot.
+    pub slot_addr: u16,
+    /// The number of bytes in the payload, set by the artnet library itself
+    pub payload_count: u16,
+    /// The 16 bit additive checksum of the payload, set by the artnet library itself
+    pub payload_checksum: u16,
+    /// The modulation depth, as a percentage from 1 to 100. 0 means the transmitter uses its default.
+    pub mod_depth: u8,
+    /// The modulation frequency in Hz. 0 means the transmitter uses its default.
+    pub mod_freq: u16,
+    /// The modulation type of the transmitter. 0 means the transmitter uses its default.
+    pub mod_bandwidth: u16,
+    /// The language code of the payload, 0 for ASCII text
+    pub lang_code: u16,
+    /// The frequency in Hz at which a beacon payload is repeated
+    pub beacon_repeat: u16,
+    /// The payload that is transmitted, at most 480 bytes
+    pub payload: Vec<u8>,
+}
+
+impl Vlc {
+    /// The start code of the `Nzs` packet that the specification uses to send ArtVlc
+    pub const START_CODE: u8 = 0x91;
+
+    /// Create a payload for all slots, with the default modulation of the transmitter
+    pub fn new(payload: Vec<u8>) -> Vlc {
+        Vlc {
+            payload_count: payload.len() as u16,
+            payload_checksum: checksum(&payload),
+            payload,
+            ..Vlc::default()
+        }
+    }
+
+    /// Whether `payload_checksum` matches the payload, e.g. after parsing a packet
+    pub fn is_checksum_valid(&self) -> bool {
+        self.payload_checksum == checksum(&self.payload)
+    }
+
+    /// Serialize this payload as DMX512 data, computing the payload count and checksum from the payload.
+    ///
+    /// Returns an error if the payload is longer than 480 bytes.
+    pub fn to_data(&self) -> Result<PaddedData> {
+        let len = self.payload.len();
+        if len > MAX_PAYLOAD_LEN {
+            return Err(Error::MessageSizeInvalid {
+                message: self.payload.clone(),
+                allowed_size: 0..MAX_PAYLOAD_LEN + 1,
+            });
+        }
+        let mut data = Vec::with_capacity(HEADER_LEN + len);
+        data.extend_from_slice(&MAGIC);
+        data.push(self.flags.bits());
+        data.extend_from_slice(&self.transaction.to_be_bytes());
+        data.extend_from_slice(&self.slot_addr.to_be_bytes());
+        data.extend_from_slice(&(len as u16).to_be_bytes());
+        data.extend_from_slice(&checksum(&self.payload).to_be_bytes());
+        data.push(0);
+        data.push(self.mod_depth);
+        data.extend_from_slice(&self.mod_freq.to_be_bytes());
+        data.extend_from_slice(&self.mod_bandwidth.to_be_bytes());
+        data.extend_from_slice(&self.lang_code.to_be_bytes());
+        data.extend_from_slice(&self.beacon_repeat.to_be_bytes());
+        data.extend_from_slice(&self.payload);
+        Ok(data.into())
+    }
+
+    /// Parse a payload from DMX512 data.
+    ///
+    /// Returns an error if the data does not start with the ArtVlc magic number, or if it is shorter than the payload count.
+    pub fn from_data(data: &[u8]) -> Result<Vlc> {
+        if !data.starts_with(&MAGIC) {
+            return Err(Error::InvalidVlcHeader(data.to_vec()));
+        }
+        if data.len() < HEADER_LEN {
+            return Err(Error::MessageTooShort {
+                message: data.to_vec(),
+                min_len: HEADER_LEN,
+            });
+        }
+        let payload_count = BigEndian::read_u16(&data[8..10]);
+        let end = HEADER_LEN + usize::from(payload_count);
+        let payload = data.get(HEADER_LEN..end).ok_or(Error::MessageTooShort {
+            message: data.to_vec(),
+            min_len: end,
+        })?;
+        Ok(Vlc {
+            flags: VlcFlags::from_bits_truncate(data[3]),
+            transaction: BigEndian::read_u16(&data[4..6]),
+            slot_addr: BigEndian::read_u16(&data[6..8]),
+            payload_count,
+            payload_checksum: BigEndian::read_u16(&data[10..12]),
+            mod_depth: data[13],
+            mod_freq: BigEndian::read_u16(&data[14..16]),
+            mod_bandwidth: BigEndian::read_u16(&data[16..18]),
+            lang_code: BigEndian::read_u16(&data[18..20]),
+            beacon_repeat: BigEndian::read_u16(&data[20..22]),
+            payload: payload.to_vec(),
+        })
+    }
+}
+
+impl Default for Vlc {
+    fn default() -> Vlc {
+        Vlc {
+            flags: VlcFlags::empty(),
+            transaction: 0,
+            slot_addr: 0,
+            payload_count: 0,
+            payload_checksum: 0,
+            mod_depth: 0,
+            mod_freq: 0,
+            mod_bandwidth: 0,
+            lang_code: 0,
+            beacon_repeat: 0,
+            payload: Vec::new(),
+        }
+    }
+}
+
+fn checksum(payload: &[u8]) -> u16 {
+    payload
+        .iter()
+        .fold(0u16, |sum, &byte| sum.wrapping_add(u16::from(byte)))
+}
+
+impl Output {
+    /// Create an `Output` for the given port address that carries the VLC payload as its data
+    pub fn from_vlc(port_address: impl Into<PortAddress>, vlc: &Vlc) -> Result<Output> {
+        Ok(Output::new(port_address, vlc.to_data()?))
+    }
+
+    /// Parse the data of this packet as a VLC payload, see [Vlc::from_data](struct.Vlc.html#method.from_data)
+    pub fn vlc(&self) -> Result<Vlc> {
+        Vlc::from_data(&self.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArtCommand;
+
+    #[test]
+    fn round_trip_through_output() {
+        let vlc = Vlc {
+            flags: VlcFlags::BEACON,
+            transaction: 0x1234,
+            slot_addr: 7,
+            mod_depth: 50,
+            mod_freq: 1000,
+            beacon_repeat: 2,
+            ..Vlc::new(b"Hello".to_vec())
+        };
+        let output = Output::from_vlc(3, &vlc).unwrap();
+        assert_eq!(&output.data[..3], b"ALE");
+        assert_eq!(output.data.len(), 27);
+
+        let bytes = ArtCommand::Output(output).write_to_buffer().unwrap();
+        let parsed = match ArtCommand::from_buffer(&bytes).unwrap() {
+            ArtCommand::Output(output) => output,
+            command => panic!("Expected an Output, got {:?}", command),
+        };
+        assert_eq!(parsed.port_address, 3.into());
+        // The odd length is padded, but the payload count excludes the padding
+        let recovered = parsed.vlc().unwrap();
+        assert_eq!(recovered, vlc);
+        assert_eq!(recovered.payload_count, 5);
+        assert_eq!(recovered.payload_checksum, 500);
+        assert!(recovered.is_checksum_valid());
+    }
+
+    #[test]
+    fn invalid_data() {
+        assert!(matches!(
+            Output::new(1, vec![1, 2, 3]).vlc(),
+            Err(Error::InvalidVlcHeader(_))
+        ));
+
+        let mut data: Vec<u8> = Vlc::new(vec![1; 10]).to_data().unwrap().into();
+        data.truncate(25);
+        assert!(matches!(
+            Vlc::from_data(&data),
+            Err(Error::MessageTooShort { min_len: 32, .. })
+        ));
+
+        assert!(Vlc::new(vec![0; 480]).to_data().is_ok());
+        assert!(matches!(
+            Vlc::new(vec![0; 481]).to_data(),
+            Err(Error::MessageSizeInvalid { allowed_size, .. }) if allowed_size == (0..481)
+        ));
+    }
+}
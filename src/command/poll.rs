@@ -1,7 +1,7 @@
 use crate::ArtTalkToMe;
 
 data_structure! {
-    #[derive(Debug)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
     #[doc = "Used to poll the nodes in the network"]
     pub struct Poll {
         #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
@@ -24,3 +24,46 @@ impl Default for Poll {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ArtCommand, ARTNET_PROTOCOL_VERSION};
+
+    #[test]
+    fn default_poll_round_trip() {
+        let bytes = ArtCommand::Poll(Poll::default()).write_to_buffer().unwrap();
+        assert_eq!(
+            bytes,
+            vec![65, 114, 116, 45, 78, 101, 116, 0, 0, 32, 0, 14, 0, 0x80]
+        );
+
+        match ArtCommand::from_buffer(&bytes).unwrap() {
+            ArtCommand::Poll(poll) => {
+                assert_eq!(poll.version, ARTNET_PROTOCOL_VERSION);
+                assert_eq!(poll.talk_to_me, ArtTalkToMe::NONE);
+                assert_eq!(poll.diagnostics_priority, 0x80);
+            }
+            command => panic!("Expected a Poll, got {:?}", command),
+        }
+    }
+
+    #[test]
+    fn talk_to_me_flags_survive() {
+        let bytes = ArtCommand::Poll(Poll {
+            talk_to_me: ArtTalkToMe::EMIT_CHANGES | ArtTalkToMe::ENABLE_DIAGNOSTICS,
+            ..Poll::default()
+        })
+        .write_to_buffer()
+        .unwrap();
+        assert_eq!(bytes[12], 0b0000_0110);
+
+        match ArtCommand::from_buffer(&bytes).unwrap() {
+            ArtCommand::Poll(poll) => assert_eq!(
+                poll.talk_to_me,
+                ArtTalkToMe::EMIT_CHANGES | ArtTalkToMe::ENABLE_DIAGNOSTICS
+            ),
+            command => panic!("Expected a Poll, got {:?}", command),
+        }
+    }
+}
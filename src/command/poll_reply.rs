@@ -1,8 +1,14 @@
-use std::fmt;
-use std::net::Ipv4Addr;
-use std::str;
+use crate::{
+    BigEndianU16, Error, FixedName, GoodInput, GoodOutput, IndicatorState, Oem, PortAddress,
+    PortAddressAuthority, Status1, Status2, ARTNET_PORT,
+};
+use core::convert::TryFrom;
+use core::fmt;
+use core::net::Ipv4Addr;
+use core::str::Utf8Error;
 
 data_structure! {
+    #[derive(Clone, PartialEq, Eq)]
     #[doc = "Gets send by the nodes in the network as a response to the Poll message"]
     pub struct PollReply {
         #[doc = "The IP address of the node"]
@@ -19,23 +25,24 @@ data_structure! {
         pub oem: [u8; 2],
         #[doc = "This field contains the firmware version of the User Bios Extension Area (UBEA). If the UBEA is not programmed, this field contains zero."]
         pub ubea_version: u8,
-        #[doc = "General Status register. Will be expanded on in the future."]
+        #[doc = "General Status register. Use `indicator_state`, `port_address_authority` and `status_1_flags` to decode it."]
         pub status_1: u8,
         #[doc = "The ESTA manufacturer code. These codes are used to represent equipment manufacturer. They are assigned by ESTA. This field can be interpreted as two ASCII bytes representing the manufacturer initials."]
         pub esta_code: u16,
         #[doc = "The array represents a null terminated short name for the Node. The Controller uses the ArtAddress packet to program this string. Max length is 17 characters plus the null. This is a fixed length field, although the string it contains can be shorter than the field."]
-        pub short_name: [u8; 18],
+        pub short_name: FixedName<18>,
         #[doc = "The array represents a null terminated long name for the Node. The Controller uses the ArtAddress packet to program this string. Max length is 63 characters plus the null. This is a fixed length field, although the string it contains can be shorter than the field."]
-        pub long_name: [u8; 64],
+        pub long_name: FixedName<64>,
         #[doc = "The array is a textual report of the Node’s operating status or operational errors. It is primarily intended for ‘engineering’ data rather than ‘end user’ data. The field is formatted as: “#xxxx [yyyy..] zzzzz…” xxxx is a hex status code as defined in Table 3. yyyy is a decimal counter that increments every time the Node sends an ArtPollResponse. This allows the controller to monitor event changes in the Node. zzzz is an English text string defining the status. This is a fixed length field, although the string it contains can be shorter than the field."]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_array"))]
         pub node_report: [u8; 64],
         #[doc = "The number of input or output ports. If number of inputs is not equal to number of outputs, the largest value is taken. Zero is a legal value if no input or output ports are implemented. The maximum value is 4. Nodes can ignore this field as the information is implicit in PortTypes[]"]
-        pub num_ports: [u8; 2],
+        pub num_ports: BigEndianU16,
         #[doc = "This array defines the operation and protocol of each channel. (A product with 4 inputs and 4 outputs would report 0xc0, 0xc0, 0xc0, 0xc0). The array length is fixed, independent of the number of inputs or outputs physically available on the Node."]
         pub port_types: [u8; 4],
-        #[doc = "This array defines input status of the node. Will be converted to a `bitflag` enum in the future."]
+        #[doc = "This array defines input status of the node. Use `good_input_flags` to decode it."]
         pub good_input: [u8; 4],
-        #[doc = "This array defines output status of the node. Will be converted to a `bitflag` enum in the future."]
+        #[doc = "This array defines output status of the node. Use `good_output_flags` to decode it."]
         pub good_output: [u8; 4],
         #[doc = "Bits 3-0 of the 15 bit Port-Address for each of the 4 possible input ports are encoded into the low nibble"]
         pub swin: [u8; 4],
@@ -57,22 +64,127 @@ data_structure! {
         pub bind_ip: [u8; 4],
         #[doc = "This number represents the order of bound devices. A lower number means closer to root device. A value of 1 means root device"]
         pub bind_index: u8,
-        #[doc = "Status 2. Will be expanded in the future"]
+        #[doc = "Status 2. Use `status_2_flags` to decode it."]
         pub status_2: u8,
         #[doc = "Transmit as zero. For future expansion."]
         pub filler: [u8; 26],
     }
 }
 
+impl PollReply {
+    /// Create a reply for the node with the given IP address and Oem code. All other fields are zero, see `PollReply::default()`.
+    pub fn new(address: Ipv4Addr, oem: impl Into<Oem>) -> PollReply {
+        PollReply {
+            address,
+            oem: oem.into().into(),
+            ..PollReply::default()
+        }
+    }
+
+    /// Create a reply for a node with a single output port, from the address of its network interface.
+    ///
+    /// The node is the root device (bind index 1) with an unknown Oem code, its port outputs DMX512 data that is sent to `port_address`, and it supports 15 bit Port-Addresses.
+    /// The names, status and report are left empty, so they can be filled in by the node.
+    pub fn from_interface(ip: Ipv4Addr, mac: [u8; 6], port_address: PortAddress) -> PollReply {
+        PollReply {
+            bind_ip: ip.octets(),
+            mac,
+            port_address: [port_address.net(), port_address.sub_net()],
+            num_ports: BigEndianU16(1),
+            // Output DMX512 data from the Art-Net protocol
+            port_types: [0x80, 0, 0, 0],
+            swout: [port_address.universe(), 0, 0, 0],
+            bind_index: 1,
+            status_2: Status2::PORT_ADDRESS_15_BIT.bits(),
+            ..PollReply::new(ip, Oem::UNKNOWN)
+        }
+    }
+
+    /// The Oem code of the node
+    pub fn oem_code(&self) -> Oem {
+        self.oem.into()
+    }
+
+    /// The short name of the node, up to the first null byte
+    pub fn short_name_str(&self) -> Result<&str, Utf8Error> {
+        self.short_name.as_str()
+    }
+
+    /// The long name of the node, up to the first null byte
+    pub fn long_name_str(&self) -> Result<&str, Utf8Error> {
+        self.long_name.as_str()
+    }
+
+    /// The `num_ports` field as it was sent, which can be above 4 for nodes that do not follow the specification
+    pub fn raw_num_ports(&self) -> u16 {
+        self.num_ports.0
+    }
+
+    /// The number of ports that are described by this reply, which is `num_ports` clamped to 4.
+    ///
+    /// A reply describes at most 4 ports, nodes with more ports send a reply per `bind_index`. Use this to index `port_types`, `good_input`, `swin` etc.
+    pub fn port_count(&self) -> usize {
+        usize::from(self.raw_num_ports().min(4))
+    }
+
+    /// Check that `num_ports` is at most 4, that `style` is a known style code, and that the `port_types` of unused ports are zero.
+    ///
+    /// Parsing does not check this, so this can be used to detect nodes that send malformed replies.
+    pub fn validate(&self) -> Result<(), Error> {
+        fn check(field: &'static str, value: u8, max: u8) -> Result<(), Error> {
+            if value <= max {
+                Ok(())
+            } else {
+                Err(Error::FieldOutOfRange { field, value, max })
+            }
+        }
+        let num_ports = self.raw_num_ports();
+        check("num_ports", u8::try_from(num_ports).unwrap_or(u8::MAX), 4)?;
+        // StNode, StController, StMedia, StRoute, StBackup, StConfig and StVisual
+        check("style", self.style, 0x06)?;
+        for &port_type in &self.port_types[num_ports as usize..] {
+            check("port_types", port_type, 0)?;
+        }
+        Ok(())
+    }
+
+    /// The state of the front panel indicators, from bits 7-6 of `status_1`
+    pub fn indicator_state(&self) -> IndicatorState {
+        IndicatorState::from(self.status_1)
+    }
+
+    /// How the Port-Address of the node was set, from bits 5-4 of `status_1`
+    pub fn port_address_authority(&self) -> PortAddressAuthority {
+        PortAddressAuthority::from(self.status_1)
+    }
+
+    /// The flags in the lower bits of `status_1`
+    pub fn status_1_flags(&self) -> Status1 {
+        Status1::from_bits_truncate(self.status_1)
+    }
+
+    /// The flags of `status_2`
+    pub fn status_2_flags(&self) -> Status2 {
+        Status2::from_bits_truncate(self.status_2)
+    }
+
+    /// The status of the given input port (0 - 3), or `None` if the port is out of range
+    pub fn good_input_flags(&self, port: usize) -> Option<GoodInput> {
+        self.good_input
+            .get(port)
+            .map(|&flags| GoodInput::from_bits_truncate(flags))
+    }
+
+    /// The status of the given output port (0 - 3), or `None` if the port is out of range
+    pub fn good_output_flags(&self, port: usize) -> Option<GoodOutput> {
+        self.good_output
+            .get(port)
+            .map(|&flags| GoodOutput::from_bits_truncate(flags))
+    }
+}
+
 impl fmt::Debug for PollReply {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
-        let short_name = str::from_utf8(&self.short_name)
-            .map(String::from)
-            .unwrap_or_else(|e| format!("Invalid UTF8: {:?}", e));
-        let long_name = str::from_utf8(&self.long_name)
-            .map(String::from)
-            .unwrap_or_else(|e| format!("Invalid UTF8: {:?}", e));
-
         fmt.debug_struct("PollReply")
             .field("address", &self.address)
             .field("port", &self.port)
@@ -82,8 +194,8 @@ impl fmt::Debug for PollReply {
             .field("ubea_version", &self.ubea_version)
             .field("status_1", &self.status_1)
             .field("esta_code", &self.esta_code)
-            .field("short_name", &short_name.trim_end_matches('\0'))
-            .field("long_name", &long_name.trim_end_matches('\0'))
+            .field("short_name", &self.short_name)
+            .field("long_name", &self.long_name)
             .field("node_report", &&self.node_report[..])
             .field("num_ports", &self.num_ports)
             .field("port_types", &self.port_types)
@@ -108,17 +220,17 @@ impl Default for PollReply {
         // Per Art-Net spec, unused fields are zero
         PollReply {
             address: Ipv4Addr::UNSPECIFIED,
-            port: 6454,
+            port: ARTNET_PORT,
             version: [0; 2],
             port_address: [0; 2],
             oem: [0; 2],
             ubea_version: 0,
             status_1: 0,
             esta_code: 0,
-            short_name: [0; 18],
-            long_name: [0; 64],
+            short_name: FixedName::default(),
+            long_name: FixedName::default(),
             node_report: [0; 64],
-            num_ports: [0; 2],
+            num_ports: BigEndianU16(0),
             port_types: [0; 4],
             good_input: [0; 4],
             good_output: [0; 4],
@@ -137,3 +249,206 @@ impl Default for PollReply {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArtCommand;
+
+    // ArtPollReply of a two port node, as seen on the wire
+    const PACKET: &[u8] = &[
+        0x41, 0x72, 0x74, 0x2d, 0x4e, 0x65, 0x74, 0x00, 0x00, 0x21, 0x02, 0x00, 0x00, 0x0a, 0x36,
+        0x19, 0x01, 0x02, 0x00, 0x00, 0x04, 0x30, 0x00, 0xd2, 0x4e, 0x45, 0x4e, 0x6f, 0x64, 0x65,
+        0x20, 0x31, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x54,
+        0x77, 0x6f, 0x20, 0x70, 0x6f, 0x72, 0x74, 0x20, 0x41, 0x72, 0x74, 0x2d, 0x4e, 0x65, 0x74,
+        0x20, 0x6e, 0x6f, 0x64, 0x65, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x23, 0x30, 0x30, 0x30, 0x31, 0x20, 0x5b, 0x30, 0x30, 0x34, 0x32, 0x5d,
+        0x20, 0x50, 0x6f, 0x77, 0x65, 0x72, 0x20, 0x4f, 0x6e, 0x20, 0x54, 0x65, 0x73, 0x74, 0x73,
+        0x20, 0x73, 0x75, 0x63, 0x63, 0x65, 0x73, 0x73, 0x66, 0x75, 0x6c, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0xc0, 0xc0, 0x00, 0x00, 0x08, 0x08,
+        0x00, 0x00, 0x80, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x50, 0xc2, 0x12, 0x34, 0x56, 0x02, 0x00, 0x00,
+        0x0a, 0x01, 0x0e, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    ];
+
+    #[test]
+    fn custom_oem_bytes() {
+        let reply = PollReply::new(Ipv4Addr::new(2, 0, 0, 10), Oem(0x8123));
+        let bytes = ArtCommand::PollReply(Box::new(reply))
+            .write_to_buffer()
+            .unwrap();
+        // Header, OpCode, address, port, version and port address come before the Oem
+        assert_eq!(bytes[20..22], [0x81, 0x23]);
+        assert_eq!(
+            PollReply::new(Ipv4Addr::LOCALHOST, Oem::default()).oem,
+            [0x00, 0xff]
+        );
+    }
+
+    #[test]
+    fn more_than_4_ports() {
+        let mut packet = PACKET.to_vec();
+        // NumPortsLo
+        packet[173] = 8;
+        let reply = match ArtCommand::from_buffer(&packet).unwrap() {
+            ArtCommand::PollReply(reply) => reply,
+            command => panic!("Expected a PollReply, got {:?}", command),
+        };
+        assert_eq!(reply.raw_num_ports(), 8);
+        assert_eq!(reply.port_count(), 4);
+        assert_eq!(reply.port_types[..reply.port_count()], [0xc0, 0xc0, 0, 0]);
+        assert!(reply.validate().is_err());
+    }
+
+    #[test]
+    fn from_interface_offsets() {
+        let port_address = PortAddress::from_parts(1, 2, 3).unwrap();
+        let reply = PollReply::from_interface(
+            Ipv4Addr::new(2, 0, 0, 10),
+            [0x00, 0x50, 0xc2, 0x12, 0x34, 0x56],
+            port_address,
+        );
+        assert!(reply.validate().is_ok());
+        let bytes = ArtCommand::PollReply(Box::new(reply))
+            .write_to_buffer()
+            .unwrap();
+        assert_eq!(bytes[10..14], [2, 0, 0, 10]);
+        assert_eq!(bytes[18..20], [1, 2]);
+        assert_eq!(bytes[190], 3);
+        assert_eq!(bytes[201..207], [0x00, 0x50, 0xc2, 0x12, 0x34, 0x56]);
+        assert_eq!(bytes[207..211], [2, 0, 0, 10]);
+        assert_eq!(bytes[211], 1);
+    }
+
+    #[test]
+    fn parse_poll_reply() {
+        let reply = match ArtCommand::from_buffer(PACKET).unwrap() {
+            ArtCommand::PollReply(reply) => reply,
+            command => panic!("Expected a PollReply, got {:?}", command),
+        };
+        assert_eq!(reply.address, Ipv4Addr::new(2, 0, 0, 10));
+        assert_eq!(reply.port, 6454);
+        assert_eq!(reply.version, [1, 2]);
+        assert_eq!(reply.oem, [0x04, 0x30]);
+        assert_eq!(reply.status_1, 0xd2);
+        assert_eq!(reply.esta_code, 0x454e);
+        assert_eq!(reply.short_name_str(), Ok("Node 1"));
+        assert_eq!(reply.long_name_str(), Ok("Two port Art-Net node"));
+        assert_eq!(reply.num_ports, BigEndianU16(2));
+        assert_eq!(reply.port_types, [0xc0, 0xc0, 0, 0]);
+        assert_eq!(reply.good_output, [0x80, 0, 0, 0]);
+        assert_eq!(reply.swout, [0, 1, 0, 0]);
+        assert_eq!(reply.mac, [0x00, 0x50, 0xc2, 0x12, 0x34, 0x56]);
+        assert_eq!(reply.bind_ip, [2, 0, 0, 10]);
+        assert_eq!(reply.bind_index, 1);
+        assert_eq!(reply.status_2, 0x0e);
+        assert_eq!(reply.indicator_state(), IndicatorState::Normal);
+        assert_eq!(
+            reply.port_address_authority(),
+            PortAddressAuthority::FrontPanel
+        );
+        assert_eq!(reply.status_1_flags(), Status1::RDM_CAPABLE);
+        assert_eq!(
+            reply.status_2_flags(),
+            Status2::PORT_ADDRESS_15_BIT | Status2::DHCP_CAPABLE | Status2::DHCP_CONFIGURED
+        );
+        assert_eq!(
+            reply.good_output_flags(0),
+            Some(GoodOutput::DATA_TRANSMITTED)
+        );
+        assert_eq!(reply.good_output_flags(1), Some(GoodOutput::empty()));
+        assert_eq!(reply.good_output_flags(4), None);
+
+        let bytes = ArtCommand::PollReply(reply).write_to_buffer().unwrap();
+        assert_eq!(bytes, PACKET);
+    }
+
+    #[test]
+    fn validate_poll_reply() {
+        let reply = match ArtCommand::from_buffer(PACKET).unwrap() {
+            ArtCommand::PollReply(reply) => reply,
+            command => panic!("Expected a PollReply, got {:?}", command),
+        };
+        assert!(reply.validate().is_ok());
+
+        let too_many_ports = PollReply {
+            num_ports: BigEndianU16(5),
+            ..PollReply::default()
+        };
+        match too_many_ports.validate() {
+            Err(Error::FieldOutOfRange { field, value, max }) => {
+                assert_eq!((field, value, max), ("num_ports", 5, 4))
+            }
+            result => panic!("Expected an out of range error, got {:?}", result),
+        }
+
+        let unknown_style = PollReply {
+            style: 0x07,
+            ..PollReply::default()
+        };
+        assert!(matches!(
+            unknown_style.validate(),
+            Err(Error::FieldOutOfRange { field: "style", .. })
+        ));
+
+        let unused_port_type = PollReply {
+            num_ports: BigEndianU16(2),
+            port_types: [0xc0, 0xc0, 0x80, 0],
+            ..PollReply::default()
+        };
+        assert!(matches!(
+            unused_port_type.validate(),
+            Err(Error::FieldOutOfRange {
+                field: "port_types",
+                value: 0x80,
+                max: 0
+            })
+        ));
+    }
+
+    #[test]
+    fn decode_status_flags() {
+        let reply = PollReply {
+            status_1: 0b0110_0101,
+            status_2: 0b1001_0001,
+            good_input: [0b1000_1100, 0, 0, 0b0000_0001],
+            ..PollReply::default()
+        };
+        assert_eq!(reply.indicator_state(), IndicatorState::Locate);
+        assert_eq!(
+            reply.port_address_authority(),
+            PortAddressAuthority::Network
+        );
+        assert_eq!(
+            reply.status_1_flags(),
+            Status1::ROM_BOOT | Status1::UBEA_PRESENT
+        );
+        let status_2 = reply.status_2_flags();
+        assert!(status_2.contains(Status2::RDM_ART_COMMAND));
+        assert!(status_2.contains(Status2::SACN_SWITCHING));
+        assert!(status_2.contains(Status2::WEB_CONFIGURATION));
+        assert!(!status_2.contains(Status2::PORT_ADDRESS_15_BIT));
+        assert_eq!(
+            reply.good_input_flags(0),
+            Some(GoodInput::DATA_RECEIVED | GoodInput::DISABLED | GoodInput::RECEIVE_ERRORS)
+        );
+        assert_eq!(reply.good_input_flags(3), Some(GoodInput::SACN));
+    }
+
+    #[test]
+    fn names_are_trimmed_at_first_null() {
+        let mut reply = PollReply::default();
+        let mut short_name = [0; 18];
+        short_name[..7].copy_from_slice(b"abc\0def");
+        reply.short_name = short_name.into();
+        assert_eq!(reply.short_name_str(), Ok("abc"));
+        assert_eq!(reply.long_name_str(), Ok(""));
+
+        reply.long_name = [b'a'; 64].into();
+        assert_eq!(reply.long_name_str().map(str::len), Ok(64));
+    }
+}
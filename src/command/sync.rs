@@ -0,0 +1,50 @@
+use crate::command::ARTNET_PROTOCOL_VERSION;
+
+data_structure! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[doc = "ArtSync is used to force synchronous transfer of ArtDmx packets to the outputs of a node."]
+    #[doc = ""]
+    #[doc = "A node that receives ArtSync will buffer incoming ArtDmx packets and only output them once the next ArtSync arrives. This allows multiple universes to be updated at the same moment."]
+    pub struct Sync {
+        #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
+        pub version: [u8; 2],
+        #[doc = "Transmit as zero"]
+        pub aux1: u8,
+        #[doc = "Transmit as zero"]
+        pub aux2: u8,
+    }
+}
+
+impl Default for Sync {
+    fn default() -> Sync {
+        Sync {
+            version: ARTNET_PROTOCOL_VERSION,
+            aux1: 0,
+            aux2: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArtCommand;
+
+    #[test]
+    fn default_sync_round_trip() {
+        let bytes = ArtCommand::Sync(Sync::default()).write_to_buffer().unwrap();
+        assert_eq!(
+            bytes,
+            vec![65, 114, 116, 45, 78, 101, 116, 0, 0, 82, 0, 14, 0, 0]
+        );
+
+        match ArtCommand::from_buffer(&bytes).unwrap() {
+            ArtCommand::Sync(sync) => {
+                assert_eq!(sync.version, ARTNET_PROTOCOL_VERSION);
+                assert_eq!(sync.aux1, 0);
+                assert_eq!(sync.aux2, 0);
+            }
+            command => panic!("Expected a Sync, got {:?}", command),
+        }
+    }
+}
@@ -0,0 +1,568 @@
+use super::*;
+
+mod header {
+    use super::*;
+
+    #[test]
+    fn random_bytes_are_an_invalid_header() {
+        let buffer = [
+            0x13, 0x37, 0xde, 0xad, 0xbe, 0xef, 0x01, 0x02, 0x00, 0x50, 0x00, 0x0e, 0x00, 0x00,
+            0x01, 0x00, 0x00, 0x02, 0xff, 0xff,
+        ];
+        match ArtCommand::from_buffer(&buffer) {
+            Err(Error::InvalidHeader { found }) => assert_eq!(found, buffer[..8]),
+            result => panic!("Expected an invalid header error, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn short_random_bytes_are_an_invalid_header() {
+        assert!(matches!(
+            ArtCommand::from_buffer(b"GET /"),
+            Err(Error::InvalidHeader {
+                found: [b'G', b'E', b'T', b' ', b'/', 0, 0, 0]
+            })
+        ));
+        assert!(matches!(
+            ArtCommand::from_buffer(b"Art-Net"),
+            Err(Error::MessageTooShort { .. })
+        ));
+        assert!(matches!(
+            ArtCommand::from_buffer(&[]),
+            Err(Error::MessageTooShort { .. })
+        ));
+    }
+}
+
+mod parse_header {
+    use super::*;
+
+    #[test]
+    fn output_header() {
+        let bytes = ArtCommand::Output(Output::new(1, vec![1, 2]))
+            .write_to_buffer()
+            .unwrap();
+        let header = ArtCommand::parse_header(&bytes).unwrap();
+        assert_eq!(header.opcode, 0x5000);
+        assert_eq!(header.version, Some(14));
+
+        // Only the version is needed, not the payload
+        assert_eq!(ArtCommand::parse_header(&bytes[..12]).unwrap(), header);
+        assert!(matches!(
+            ArtCommand::parse_header(&bytes[..11]),
+            Err(Error::MessageTooShort { min_len: 12, .. })
+        ));
+    }
+
+    #[test]
+    fn headers_without_a_version() {
+        let bytes = ArtCommand::PollReply(Box::default())
+            .write_to_buffer()
+            .unwrap();
+        assert_eq!(
+            ArtCommand::parse_header(&bytes).unwrap(),
+            ArtHeader {
+                opcode: 0x2100,
+                version: None,
+            }
+        );
+
+        let unknown = b"Art-Net\0\x34\x12";
+        assert_eq!(
+            ArtCommand::parse_header(unknown).unwrap(),
+            ArtHeader {
+                opcode: 0x1234,
+                version: None,
+            }
+        );
+        assert!(matches!(
+            ArtCommand::parse_header(&unknown[..9]),
+            Err(Error::MessageTooShort { min_len: 10, .. })
+        ));
+        assert!(matches!(
+            ArtCommand::parse_header(b"GET /"),
+            Err(Error::InvalidHeader { .. })
+        ));
+    }
+}
+
+mod opcode_endianness {
+    use super::*;
+
+    #[test]
+    fn output_opcode_is_little_endian() {
+        let bytes = ArtCommand::Output(Output::new(1, vec![1, 2]))
+            .write_to_buffer()
+            .unwrap();
+        assert_eq!(&bytes[8..10], &[0, 80]);
+        assert_eq!(ArtCommand::peek_opcode(&bytes).unwrap(), OpCode::Output);
+
+        let mut buf = [0; 20];
+        let len = Output::new(1, vec![1, 2]).encode_into(&mut buf).unwrap();
+        assert_eq!(&buf[..len], &bytes[..]);
+
+        // The byte swapped opcode is not an ArtDmx
+        let mut swapped = bytes.clone();
+        swapped[8..10].copy_from_slice(&[80, 0]);
+        assert!(matches!(
+            ArtCommand::from_buffer(&swapped).unwrap(),
+            ArtCommand::Unknown { opcode: 0x0050, .. }
+        ));
+    }
+
+    #[test]
+    fn every_packet_writes_and_reads_a_little_endian_opcode() {
+        // The opaque packets get a payload, because a packet must be at least 14 bytes long to be parsed
+        let commands = vec![
+            ArtCommand::Poll(Poll::default()),
+            ArtCommand::PollReply(Box::default()),
+            ArtCommand::DiagData(DiagData::default()),
+            ArtCommand::CommandText(CommandText::default()),
+            ArtCommand::DataRequest(DataRequest::default()),
+            ArtCommand::DataReply(DataReply::default()),
+            ArtCommand::Output(Output::new(1, vec![1, 2])),
+            ArtCommand::Nzs(Nzs {
+                data: vec![1, 2].into(),
+                ..Nzs::default()
+            }),
+            ArtCommand::Sync(Sync::default()),
+            ArtCommand::Address(Address::default()),
+            ArtCommand::Input(Input::default()),
+            ArtCommand::TodRequest(TodRequest::default()),
+            ArtCommand::TodData(TodData::default()),
+            ArtCommand::TodControl(TodControl::default()),
+            ArtCommand::VideoSetup(VideoSetup::default()),
+            ArtCommand::VideoPalette(VideoPalette::default()),
+            ArtCommand::VideoData(VideoData::default()),
+            ArtCommand::MacMaster(MacMaster {
+                data: vec![1, 2],
+                ..MacMaster::default()
+            }),
+            ArtCommand::MacSlave(MacSlave {
+                data: vec![1, 2],
+                ..MacSlave::default()
+            }),
+            ArtCommand::FileTnMaster(Box::default()),
+            ArtCommand::FileFnMaster(Box::default()),
+            ArtCommand::FileFnReply(FileFnReply::default()),
+            ArtCommand::OpIpProg(IpProg::default()),
+            ArtCommand::OpIpProgReply(IpProgReply::default()),
+            ArtCommand::OpMedia(Media {
+                data: vec![1, 2],
+                ..Media::default()
+            }),
+            ArtCommand::OpMediaPatch(MediaPatch {
+                data: vec![1, 2],
+                ..MediaPatch::default()
+            }),
+            ArtCommand::OpMediaControl(MediaControl {
+                data: vec![1, 2],
+                ..MediaControl::default()
+            }),
+            ArtCommand::OpMediaControlReply(MediaControlReply {
+                data: vec![1, 2],
+                ..MediaControlReply::default()
+            }),
+            ArtCommand::OpTimeCode(Timecode::default()),
+            ArtCommand::OpTimeSync(TimeSync::default()),
+            ArtCommand::OpTrigger(Box::default()),
+            ArtCommand::OpDirectory(Directory::default()),
+            ArtCommand::OpDirectoryReply(DirectoryReply::default()),
+        ];
+        for command in commands {
+            let bytes = command.write_to_buffer().unwrap();
+            assert_eq!(
+                &bytes[8..10],
+                &command.opcode().to_le_bytes(),
+                "{}",
+                command.name()
+            );
+            let parsed = ArtCommand::from_buffer(&bytes).unwrap();
+            assert_eq!(parsed.opcode(), command.opcode(), "{}", command.name());
+        }
+
+        // The packets that are not implemented only write the header
+        for command in [
+            ArtCommand::Rdm,
+            ArtCommand::RdmSub,
+            ArtCommand::FirmwareMaster,
+            ArtCommand::FirmwareReply,
+        ] {
+            let bytes = command.write_to_buffer().unwrap();
+            assert_eq!(&bytes[8..10], &command.opcode().to_le_bytes());
+        }
+    }
+}
+
+mod unknown {
+    use super::*;
+
+    #[test]
+    fn unknown_opcode_round_trip() {
+        let packet = [
+            65, 114, 116, 45, 78, 101, 116, 0, 0x34, 0x12, 0, 14, 1, 2, 3,
+        ];
+        let command = ArtCommand::from_buffer(&packet).unwrap();
+        match &command {
+            ArtCommand::Unknown { opcode, payload } => {
+                assert_eq!(*opcode, 0x1234);
+                assert_eq!(payload, &[0, 14, 1, 2, 3]);
+            }
+            command => panic!("Expected an unknown command, got {:?}", command),
+        }
+        assert_eq!(command.write_to_buffer().unwrap(), packet);
+        assert!(matches!(
+            ArtCommand::peek_opcode(&packet),
+            Err(Error::UnknownOpcode(0x1234))
+        ));
+    }
+}
+
+mod write_to {
+    use super::*;
+
+    #[test]
+    fn reused_buffer_has_no_leftover_bytes() {
+        let mut buffer = Vec::new();
+        let long = ArtCommand::Output(Output::new(1, vec![0xff; 512]));
+        long.write_to(&mut buffer).unwrap();
+        assert_eq!(buffer, long.write_to_buffer().unwrap());
+
+        let short = ArtCommand::Output(Output::new(1, vec![1, 2]));
+        short.write_to(&mut buffer).unwrap();
+        assert_eq!(
+            buffer,
+            vec![65, 114, 116, 45, 78, 101, 116, 0, 0, 80, 0, 14, 0, 0, 1, 0, 0, 2, 1, 2]
+        );
+    }
+
+    #[test]
+    fn append_several_commands() {
+        let mut buffer = Vec::new();
+        // The odd length is padded independently of the packet after it
+        ArtCommand::Output(Output::new(1, vec![1, 2, 3]))
+            .append_to_buffer(&mut buffer)
+            .unwrap();
+        ArtCommand::Sync(Sync::default())
+            .append_to_buffer(&mut buffer)
+            .unwrap();
+        assert_eq!(buffer.len(), 22 + 14);
+
+        let too_long = ArtCommand::Output(Output::new(1, vec![0xff; 513]));
+        assert!(too_long.append_to_buffer(&mut buffer).is_err());
+        assert_eq!(buffer.len(), 22 + 14);
+
+        let commands = ArtCommand::from_buffer_multi(&buffer).unwrap();
+        assert_eq!(commands.len(), 2);
+        match &commands[0] {
+            ArtCommand::Output(output) => assert_eq!(output.data.as_ref(), &vec![1, 2, 3, 0]),
+            command => panic!("Expected an Output, got {:?}", command),
+        }
+        assert!(matches!(commands[1], ArtCommand::Sync(_)));
+    }
+}
+
+mod from_buffer_multi {
+    use super::*;
+
+    #[test]
+    fn output_followed_by_sync() {
+        let output = ArtCommand::Output(Output::new(1, vec![1, 2, 3]));
+        let buffer = [
+            output.write_to_buffer().unwrap(),
+            ArtCommand::Sync(Sync::default()).write_to_buffer().unwrap(),
+        ]
+        .concat();
+
+        let commands = ArtCommand::from_buffer_multi(&buffer).unwrap();
+        assert_eq!(commands.len(), 2);
+        match &commands[0] {
+            ArtCommand::Output(output) => assert_eq!(output.data.as_slice(), &[1, 2, 3, 0]),
+            command => panic!("Expected an Output, got {:?}", command),
+        }
+        assert!(matches!(commands[1], ArtCommand::Sync(_)));
+    }
+
+    #[test]
+    fn truncated_output_is_too_short() {
+        let mut buffer = ArtCommand::Output(Output::new(1, vec![1, 2, 3, 4]))
+            .write_to_buffer()
+            .unwrap();
+        buffer.pop();
+        assert!(matches!(
+            ArtCommand::from_buffer_multi(&buffer),
+            Err(Error::MessageTooShort { min_len: 22, .. })
+        ));
+    }
+
+    #[test]
+    fn mac_master_ends_at_the_next_header() {
+        let mut buffer = b"Art-Net\0".to_vec();
+        buffer.extend_from_slice(&[0x00, 0xf0, 0, 14, 1, 2, 3]);
+        buffer.extend(ArtCommand::Sync(Sync::default()).write_to_buffer().unwrap());
+        buffer.extend(ArtCommand::Poll(Poll::default()).write_to_buffer().unwrap());
+
+        let commands = ArtCommand::from_buffer_multi(&buffer).unwrap();
+        assert_eq!(commands.len(), 3);
+        match &commands[0] {
+            ArtCommand::MacMaster(mac) => assert_eq!(mac.data, [1, 2, 3]),
+            command => panic!("Expected a MacMaster, got {:?}", command),
+        }
+        assert!(matches!(commands[1], ArtCommand::Sync(_)));
+        assert!(matches!(commands[2], ArtCommand::Poll(_)));
+    }
+
+    #[test]
+    fn art_net_4_poll_followed_by_sync() {
+        // An Art-Net 4 ArtPoll, with the target Port-Addresses, ESTA manufacturer and OEM code after the fields this crate parses
+        let mut buffer = b"Art-Net\0".to_vec();
+        buffer.extend_from_slice(&[
+            0x00, 0x20, 0, 14, 0x02, 0x80, 0, 1, 0, 2, 0x41, 0x4c, 0xff, 0xff,
+        ]);
+        assert_eq!(buffer.len(), 22);
+        buffer.extend(ArtCommand::Sync(Sync::default()).write_to_buffer().unwrap());
+
+        let commands = ArtCommand::from_buffer_multi(&buffer).unwrap();
+        assert_eq!(commands.len(), 2);
+        match &commands[0] {
+            ArtCommand::Poll(poll) => assert_eq!(poll.diagnostics_priority, 0x80),
+            command => panic!("Expected a Poll, got {:?}", command),
+        }
+        assert!(matches!(commands[1], ArtCommand::Sync(_)));
+    }
+}
+
+mod try_from_buffer {
+    use super::*;
+
+    fn output_packet() -> Vec<u8> {
+        ArtCommand::Output(Output::new(1, vec![0xff; 100]))
+            .write_to_buffer()
+            .unwrap()
+    }
+
+    #[test]
+    fn truncated_output_is_incomplete() {
+        let packet = output_packet();
+        assert_eq!(packet.len(), 118);
+        for (len, needed) in [(0, 14), (5, 9), (15, 3), (16, 2), (18, 100), (117, 1)] {
+            match ArtCommand::try_from_buffer(&packet[..len]) {
+                Err(Error::Incomplete { needed: got }) => {
+                    assert_eq!(got, needed, "length {}", len)
+                }
+                result => panic!("Expected an incomplete error, got {:?}", result),
+            }
+        }
+        assert!(matches!(
+            ArtCommand::try_from_buffer(&packet),
+            Ok(ArtCommand::Output(_))
+        ));
+    }
+
+    #[test]
+    fn truncated_poll_reply_is_incomplete() {
+        let packet = ArtCommand::PollReply(Box::default())
+            .write_to_buffer()
+            .unwrap();
+        assert_eq!(packet.len(), 239);
+        assert!(matches!(
+            ArtCommand::try_from_buffer(&packet[..100]),
+            Err(Error::Incomplete { needed: 139 })
+        ));
+        assert!(matches!(
+            ArtCommand::try_from_buffer(&packet),
+            Ok(ArtCommand::PollReply(_))
+        ));
+    }
+
+    #[test]
+    fn trailing_bytes_are_ignored() {
+        let mut buffer = output_packet();
+        buffer.extend_from_slice(&output_packet()[..20]);
+        match ArtCommand::try_from_buffer(&buffer).unwrap() {
+            ArtCommand::Output(output) => assert_eq!(output.data.len(), 100),
+            command => panic!("Expected an Output, got {:?}", command),
+        }
+    }
+
+    #[test]
+    fn invalid_data_is_malformed() {
+        match ArtCommand::try_from_buffer(b"GET / HTTP/1.1") {
+            Err(Error::Malformed(inner)) => {
+                assert!(matches!(*inner, Error::InvalidHeader { .. }))
+            }
+            result => panic!("Expected a malformed error, got {:?}", result),
+        }
+
+        // A Port-Address above 32_767
+        let mut packet = output_packet();
+        packet[15] = 0x80;
+        assert!(matches!(
+            ArtCommand::try_from_buffer(&packet),
+            Err(Error::Malformed(_))
+        ));
+    }
+}
+
+#[cfg(feature = "std")]
+mod from_reader {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn reads_one_output_at_a_time() {
+        let mut bytes = ArtCommand::Output(Output::new(3, vec![1, 2, 3, 4]))
+            .write_to_buffer()
+            .unwrap();
+        bytes.extend(ArtCommand::Sync(Sync::default()).write_to_buffer().unwrap());
+        let mut reader = Cursor::new(bytes);
+
+        match ArtCommand::from_reader(&mut reader).unwrap() {
+            ArtCommand::Output(output) => assert_eq!(output.dmx_data(), [1, 2, 3, 4]),
+            command => panic!("Expected an Output, got {:?}", command),
+        }
+        assert_eq!(reader.position(), 22);
+        assert!(matches!(
+            ArtCommand::from_reader(&mut reader).unwrap(),
+            ArtCommand::Sync(_)
+        ));
+        assert!(matches!(
+            ArtCommand::from_reader(&mut reader),
+            Err(Error::Io(_))
+        ));
+    }
+
+    #[test]
+    fn reads_video_data_and_sync() {
+        let video_data = ArtCommand::VideoData(VideoData {
+            len_x: 2,
+            len_y: 1,
+            data: vec![b'A', 0x07, b'B', 0x07],
+            ..VideoData::default()
+        });
+        let mut bytes = video_data.write_to_buffer().unwrap();
+        bytes.extend(ArtCommand::Sync(Sync::default()).write_to_buffer().unwrap());
+        let mut reader = Cursor::new(bytes);
+
+        assert_eq!(ArtCommand::from_reader(&mut reader).unwrap(), video_data);
+        assert_eq!(reader.position(), 22);
+        assert!(matches!(
+            ArtCommand::from_reader(&mut reader).unwrap(),
+            ArtCommand::Sync(_)
+        ));
+        assert_eq!(reader.position(), 36);
+    }
+
+    #[test]
+    fn poll_has_no_fixed_length() {
+        // An Art-Net 4 ArtPoll is 22 bytes, which is longer than the fields this crate parses
+        let mut bytes = b"Art-Net\0".to_vec();
+        bytes.extend_from_slice(&[
+            0x00, 0x20, 0, 14, 0x02, 0x80, 0, 1, 0, 2, 0x41, 0x4c, 0xff, 0xff,
+        ]);
+        bytes.extend(ArtCommand::Sync(Sync::default()).write_to_buffer().unwrap());
+        let mut reader = Cursor::new(bytes);
+        assert!(matches!(
+            ArtCommand::from_reader(&mut reader),
+            Err(Error::UnknownPacketLength(0x2000))
+        ));
+    }
+
+    #[test]
+    fn mac_master_has_no_length() {
+        let mut bytes = b"Art-Net\0".to_vec();
+        bytes.extend_from_slice(&[0x00, 0xf0, 0, 14, 1, 2, 3]);
+        bytes.extend(ArtCommand::Poll(Poll::default()).write_to_buffer().unwrap());
+        let mut reader = Cursor::new(bytes);
+        assert!(matches!(
+            ArtCommand::from_reader(&mut reader),
+            Err(Error::UnknownPacketLength(0xf000))
+        ));
+    }
+
+    #[test]
+    fn invalid_header() {
+        let mut reader = Cursor::new(b"Not-Art-Net data".to_vec());
+        assert!(matches!(
+            ArtCommand::from_reader(&mut reader),
+            Err(Error::InvalidHeader { .. })
+        ));
+    }
+}
+
+mod try_from {
+    use super::*;
+    use core::convert::{TryFrom, TryInto};
+
+    #[test]
+    fn bytes_and_commands() {
+        let bytes: Vec<u8> = ArtCommand::Poll(Poll::default()).try_into().unwrap();
+        assert_eq!(bytes[8..10], [0x00, 0x20]);
+
+        let command = ArtCommand::try_from(bytes.as_slice()).unwrap();
+        assert!(matches!(command, ArtCommand::Poll(_)));
+        assert_eq!(Vec::try_from(command).unwrap(), bytes);
+
+        let result: Result<ArtCommand> = b"Art-Net".as_ref().try_into();
+        assert!(matches!(result, Err(Error::MessageTooShort { .. })));
+    }
+}
+
+mod protocol_version {
+    use super::*;
+
+    #[test]
+    fn version_of_parsed_packets() {
+        let mut output = Output::new(1, vec![1, 2]);
+        output.version = [0, 0];
+        let output = ArtCommand::Output(output);
+        let bytes = output.write_to_buffer().unwrap();
+        let parsed = ArtCommand::from_buffer(&bytes).unwrap();
+        assert_eq!(parsed.protocol_version(), Some(ProtocolVersion(0)));
+
+        assert_eq!(
+            ArtCommand::Poll(Poll::default()).protocol_version(),
+            Some(ProtocolVersion::CURRENT)
+        );
+        assert_eq!(
+            ArtCommand::PollReply(Box::default()).protocol_version(),
+            None
+        );
+        assert_eq!(ArtCommand::Rdm.protocol_version(), None);
+    }
+}
+
+mod broadcast {
+    use super::*;
+
+    #[test]
+    fn poll_is_broadcast_but_output_is_not() {
+        assert!(ArtCommand::Poll(Poll::default()).is_broadcast_appropriate());
+        assert!(ArtCommand::Sync(Sync::default()).is_broadcast_appropriate());
+        assert!(!ArtCommand::Output(Output::new(1, vec![1, 2])).is_broadcast_appropriate());
+        assert!(!ArtCommand::Address(Address::default()).is_broadcast_appropriate());
+        assert!(!ArtCommand::Unknown {
+            opcode: 0x1234,
+            payload: vec![],
+        }
+        .is_broadcast_appropriate());
+    }
+}
+
+mod hex_dump {
+    use super::*;
+
+    #[test]
+    fn output_dump_shows_header_as_ascii() {
+        let dump = ArtCommand::Output(Output::new(1, vec![1, 2])).hex_dump();
+        let lines: Vec<&str> = dump.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "00000000  41 72 74 2d 4e 65 74 00  00 50 00 0e 00 00 01 00  |Art-Net..P......|",
+                "00000010  00 02 01 02                                       |....|",
+                "00000014",
+            ]
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use crate::command::ARTNET_PROTOCOL_VERSION;
+#[cfg(feature = "std")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+data_structure! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[doc = "ArtTimeSync is used to synchronise the real time date and clock of the nodes on the network."]
+    #[doc = ""]
+    #[doc = "The time fields follow the layout of the C `struct tm`."]
+    pub struct TimeSync {
+        #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
+        pub version: [u8; 2],
+        #[doc = "Transmit as zero"]
+        pub filler1: u8,
+        #[doc = "Transmit as zero"]
+        pub filler2: u8,
+        #[doc = "Set to 0x01 to program the date and time of the node, 0x00 to only request it"]
+        pub prog: u8,
+        #[doc = "Seconds after the minute, 0 - 59"]
+        pub tm_sec: u8,
+        #[doc = "Minutes after the hour, 0 - 59"]
+        pub tm_min: u8,
+        #[doc = "Hours since midnight, 0 - 23"]
+        pub tm_hour: u8,
+        #[doc = "Day of the month, 1 - 31"]
+        pub tm_mday: u8,
+        #[doc = "Months since January, 0 - 11"]
+        pub tm_mon: u8,
+        #[doc = "Years since 1900, big endian"]
+        pub tm_year: [u8; 2],
+        #[doc = "Days since Sunday, 0 - 6"]
+        pub tm_wday: u8,
+        #[doc = "Daylight saving time flag, non-zero if daylight saving time is in effect"]
+        pub tm_isdst: u8,
+    }
+}
+
+impl TimeSync {
+    /// Create a packet that programs the given time, in UTC, into the nodes.
+    ///
+    /// Times before the unix epoch are clamped to 1970-01-01 00:00:00.
+    #[cfg(feature = "std")]
+    pub fn from_system_time(time: SystemTime) -> TimeSync {
+        let seconds = time
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        TimeSync::from_unix_time(seconds)
+    }
+
+    /// Create a packet that programs the given number of seconds since 1970-01-01 00:00:00 UTC into the nodes.
+    pub fn from_unix_time(seconds: u64) -> TimeSync {
+        let days = seconds / 86_400;
+        let time_of_day = seconds % 86_400;
+
+        // Convert the day count to a civil date, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let z = days + 719_468;
+        let era = z / 146_097;
+        let day_of_era = z % 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        TimeSync {
+            prog: 1,
+            tm_sec: (time_of_day % 60) as u8,
+            tm_min: (time_of_day / 60 % 60) as u8,
+            tm_hour: (time_of_day / 3600) as u8,
+            tm_mday: day as u8,
+            tm_mon: (month - 1) as u8,
+            tm_year: ((year - 1900) as u16).to_be_bytes(),
+            // 1970-01-01 was a Thursday
+            tm_wday: ((days + 4) % 7) as u8,
+            tm_isdst: 0,
+            ..TimeSync::default()
+        }
+    }
+
+    /// The full year, e.g. 2024
+    pub fn year(&self) -> u16 {
+        1900 + u16::from_be_bytes(self.tm_year)
+    }
+}
+
+impl Default for TimeSync {
+    fn default() -> TimeSync {
+        TimeSync {
+            version: ARTNET_PROTOCOL_VERSION,
+            filler1: 0,
+            filler2: 0,
+            prog: 0,
+            tm_sec: 0,
+            tm_min: 0,
+            tm_hour: 0,
+            tm_mday: 1,
+            tm_mon: 0,
+            tm_year: [0, 70],
+            tm_wday: 4,
+            tm_isdst: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArtCommand;
+
+    #[test]
+    fn from_unix_time() {
+        // 2024-02-29 13:37:42 UTC, a Thursday
+        let sync = TimeSync::from_unix_time(1_709_213_862);
+        assert_eq!(sync.prog, 1);
+        assert_eq!((sync.tm_hour, sync.tm_min, sync.tm_sec), (13, 37, 42));
+        assert_eq!((sync.tm_mday, sync.tm_mon), (29, 1));
+        assert_eq!(sync.year(), 2024);
+        assert_eq!(sync.tm_wday, 4);
+
+        let epoch = TimeSync::from_unix_time(0);
+        assert_eq!(epoch.tm_year, TimeSync::default().tm_year);
+        assert_eq!(epoch.tm_mday, 1);
+        assert_eq!(epoch.tm_wday, 4);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn from_system_time() {
+        use std::time::Duration;
+
+        let time = UNIX_EPOCH + Duration::from_secs(1_709_213_862);
+        let sync = TimeSync::from_system_time(time);
+        assert_eq!(
+            sync.to_bytes().unwrap(),
+            TimeSync::from_unix_time(1_709_213_862).to_bytes().unwrap()
+        );
+        assert_eq!(
+            TimeSync::from_system_time(UNIX_EPOCH - Duration::from_secs(1)).year(),
+            1970
+        );
+    }
+
+    #[test]
+    fn time_sync_round_trip() {
+        let sync = TimeSync::from_unix_time(1_709_213_862);
+        let bytes = ArtCommand::OpTimeSync(sync).write_to_buffer().unwrap();
+        assert_eq!(bytes.len(), 24);
+        assert_eq!(&bytes[8..10], &[0x00, 0x98]);
+        assert_eq!(&bytes[14..], &[1, 42, 37, 13, 29, 1, 0, 124, 4, 0]);
+
+        match ArtCommand::from_buffer(&bytes).unwrap() {
+            ArtCommand::OpTimeSync(parsed) => {
+                assert_eq!(parsed.tm_min, 37);
+                assert_eq!(parsed.year(), 2024);
+                assert_eq!(parsed.tm_wday, 4);
+            }
+            command => panic!("Expected a TimeSync, got {:?}", command),
+        }
+    }
+}
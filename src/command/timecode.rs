@@ -1,5 +1,11 @@
+use crate::{Error, FrameRate, Result};
+use core::convert::TryFrom;
+use core::time::Duration;
+
+const NANOS_PER_SECOND: u128 = 1_000_000_000;
+
 data_structure! {
-    #[derive(Debug)]
+    #[derive(Debug, Clone, PartialEq, Eq)]
     #[doc = "Used to send timecode data"]
     pub struct Timecode {
         #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
@@ -28,6 +34,134 @@ data_structure! {
     }
 }
 
+impl Timecode {
+    /// Create a timecode with the given time and frame rate
+    pub fn new(hours: u8, minutes: u8, seconds: u8, frames: u8, frame_rate: FrameRate) -> Self {
+        Timecode {
+            frames,
+            seconds,
+            minutes,
+            hours,
+            key_type: frame_rate.into(),
+            ..Timecode::default()
+        }
+    }
+
+    /// The frame rate of this timecode. Returns an error if the `key_type` is unknown.
+    pub fn frame_rate(&self) -> Result<FrameRate> {
+        FrameRate::try_from(self.key_type)
+    }
+
+    /// Check that the time fits the frame rate, and that the seconds, minutes and hours are in range.
+    ///
+    /// This is done automatically when the timecode is written to a buffer.
+    pub fn validate(&self) -> Result<()> {
+        fn check(field: &'static str, value: u8, max: u8) -> Result<()> {
+            if value <= max {
+                Ok(())
+            } else {
+                Err(Error::FieldOutOfRange { field, value, max })
+            }
+        }
+        let frame_rate = self.frame_rate()?;
+        check("frames", self.frames, frame_rate.nominal_fps() - 1)?;
+        check("seconds", self.seconds, 59)?;
+        check("minutes", self.minutes, 59)?;
+        check("hours", self.hours, 23)
+    }
+
+    /// The total number of frames since 00:00:00:00, using the frame rate of `key_type`.
+    ///
+    /// For 29.97 fps (`key_type` 2) this uses drop-frame counting, so frames 0 and 1 of every minute that is not a multiple of 10 are skipped.
+    /// Unknown key types are counted as 30 fps.
+    pub fn to_frames(&self) -> u32 {
+        let (fps, drop_frame) = frame_rate(self.key_type);
+        let total_minutes = 60 * self.hours as u32 + self.minutes as u32;
+        let frames = (60 * total_minutes + self.seconds as u32) * fps + self.frames as u32;
+        if drop_frame {
+            frames - 2 * (total_minutes - total_minutes / 10)
+        } else {
+            frames
+        }
+    }
+
+    /// Create a timecode from a frame count since 00:00:00:00, the inverse of [to_frames](#method.to_frames).
+    ///
+    /// Frame counts of more than 24 hours wrap around to 00:00:00:00.
+    pub fn from_frames(frames: u32, key_type: u8) -> Self {
+        let (fps, drop_frame) = frame_rate(key_type);
+        let mut frames = frames % frames_per_day(key_type);
+        if drop_frame {
+            const FRAMES_PER_MINUTE: u32 = 60 * 30 - 2;
+            let tens = frames / FRAMES_PER_10_MINUTES;
+            let remainder = frames % FRAMES_PER_10_MINUTES;
+            frames += 18 * tens;
+            if remainder > 2 {
+                frames += 2 * ((remainder - 2) / FRAMES_PER_MINUTE);
+            }
+        }
+        let seconds = frames / fps;
+        Timecode {
+            frames: (frames % fps) as u8,
+            seconds: (seconds % 60) as u8,
+            minutes: (seconds / 60 % 60) as u8,
+            hours: (seconds / 3600) as u8,
+            key_type,
+            ..Timecode::default()
+        }
+    }
+
+    /// Create the timecode of the frame that is shown at the given time since 00:00:00:00.
+    ///
+    /// For 29.97 fps the real frame rate is used, while the time is counted with drop-frame, so the timecode slowly drifts from the wall clock.
+    /// Durations of more than 24 hours of timecode wrap around to 00:00:00:00.
+    pub fn from_duration(duration: Duration, frame_rate: FrameRate) -> Self {
+        let key_type = frame_rate.into();
+        let (numerator, denominator) = fps_ratio(key_type);
+        let frames = duration.as_nanos() * numerator / (denominator * NANOS_PER_SECOND);
+        let frames = frames % u128::from(frames_per_day(key_type));
+        Timecode::from_frames(frames as u32, key_type)
+    }
+
+    /// The time since 00:00:00:00 at which this frame starts, the inverse of [from_duration](#method.from_duration).
+    ///
+    /// Unknown key types are counted as 30 fps.
+    pub fn to_duration(&self) -> Duration {
+        let (numerator, denominator) = fps_ratio(self.key_type);
+        let frames = u128::from(self.to_frames());
+        // Round up, so that converting back gives the same frame instead of the one before it
+        let nanos = (frames * denominator * NANOS_PER_SECOND).div_ceil(numerator);
+        Duration::new(
+            (nanos / NANOS_PER_SECOND) as u64,
+            (nanos % NANOS_PER_SECOND) as u32,
+        )
+    }
+}
+
+/// The nominal frames per second for a key type, and whether drop-frame counting is used
+fn frame_rate(key_type: u8) -> (u32, bool) {
+    let frame_rate = FrameRate::try_from(key_type).unwrap_or(FrameRate::Smpte30);
+    (frame_rate.nominal_fps() as u32, frame_rate.is_drop_frame())
+}
+
+const FRAMES_PER_10_MINUTES: u32 = 10 * 60 * 30 - 9 * 2;
+
+/// The number of frames from 00:00:00:00 up to 24:00:00:00
+fn frames_per_day(key_type: u8) -> u32 {
+    match frame_rate(key_type) {
+        (_, true) => 24 * 6 * FRAMES_PER_10_MINUTES,
+        (fps, false) => 24 * 60 * 60 * fps,
+    }
+}
+
+/// The actual frames per second of a key type as a fraction, so that 29.97 fps is exact
+fn fps_ratio(key_type: u8) -> (u128, u128) {
+    match FrameRate::try_from(key_type).unwrap_or(FrameRate::Smpte30) {
+        FrameRate::DropFrame2997 => (30_000, 1001),
+        frame_rate => (frame_rate.nominal_fps() as u128, 1),
+    }
+}
+
 impl Default for Timecode {
     fn default() -> Timecode {
         Timecode {
@@ -42,3 +176,178 @@ impl Default for Timecode {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::convert::assert_fully_consumed;
+    use crate::ArtCommand;
+
+    fn timecode(hours: u8, minutes: u8, seconds: u8, frames: u8, key_type: u8) -> Timecode {
+        Timecode {
+            hours,
+            minutes,
+            seconds,
+            frames,
+            key_type,
+            ..Timecode::default()
+        }
+    }
+
+    fn assert_frames(timecode: Timecode, frames: u32) {
+        assert_eq!(timecode.to_frames(), frames, "to_frames of {:?}", timecode);
+        let converted = Timecode::from_frames(frames, timecode.key_type);
+        assert_eq!(
+            (
+                converted.hours,
+                converted.minutes,
+                converted.seconds,
+                converted.frames
+            ),
+            (
+                timecode.hours,
+                timecode.minutes,
+                timecode.seconds,
+                timecode.frames
+            ),
+            "from_frames of {}",
+            frames
+        );
+    }
+
+    #[test]
+    fn non_drop_frame() {
+        assert_frames(timecode(0, 0, 0, 0, 0), 0);
+        assert_frames(timecode(0, 0, 1, 0, 0), 24);
+        assert_frames(timecode(1, 0, 0, 0, 1), 90_000);
+        assert_frames(timecode(10, 20, 30, 12, 1), 930_762);
+        assert_frames(timecode(23, 59, 59, 29, 3), 2_591_999);
+    }
+
+    #[test]
+    fn drop_frame_minute_boundaries() {
+        assert_frames(timecode(0, 0, 59, 29, 2), 1799);
+        // Frames 0 and 1 are skipped at the start of minute 1
+        assert_frames(timecode(0, 1, 0, 2, 2), 1800);
+        assert_frames(timecode(0, 1, 59, 29, 2), 3597);
+        assert_frames(timecode(0, 2, 0, 2, 2), 3598);
+        assert_frames(timecode(0, 9, 59, 29, 2), 17_981);
+        // But not at every tenth minute
+        assert_frames(timecode(0, 10, 0, 0, 2), 17_982);
+        assert_frames(timecode(0, 10, 0, 1, 2), 17_983);
+        assert_frames(timecode(0, 11, 0, 2, 2), 19_782);
+        assert_frames(timecode(1, 0, 0, 0, 2), 107_892);
+        assert_frames(timecode(23, 59, 59, 29, 2), 2_589_407);
+    }
+
+    #[test]
+    fn frame_rate_accessor() {
+        let timecode = Timecode::new(1, 2, 3, 4, FrameRate::Ebu25);
+        assert_eq!(timecode.key_type, 1);
+        assert_eq!(timecode.frame_rate().unwrap(), FrameRate::Ebu25);
+
+        let invalid = Timecode {
+            key_type: 7,
+            ..Timecode::default()
+        };
+        assert!(invalid.frame_rate().is_err());
+    }
+
+    #[test]
+    fn validate_ranges() {
+        assert!(timecode(23, 59, 59, 29, 3).validate().is_ok());
+        match timecode(0, 0, 0, 30, 3).validate() {
+            Err(Error::FieldOutOfRange { field, value, max }) => {
+                assert_eq!((field, value, max), ("frames", 30, 29))
+            }
+            result => panic!("Expected an out of range error, got {:?}", result),
+        }
+        assert!(timecode(0, 0, 0, 24, 0).validate().is_err());
+        assert!(timecode(0, 0, 60, 0, 3).validate().is_err());
+        assert!(timecode(0, 60, 0, 0, 3).validate().is_err());
+        assert!(timecode(99, 0, 0, 0, 3).validate().is_err());
+        assert!(timecode(0, 0, 0, 0, 4).validate().is_err());
+    }
+
+    #[test]
+    fn invalid_timecode_is_not_serialized() {
+        let command = ArtCommand::OpTimeCode(timecode(0, 0, 0, 29, 3));
+        assert!(command.write_to_buffer().is_ok());
+        let command = ArtCommand::OpTimeCode(timecode(0, 0, 0, 30, 3));
+        assert!(matches!(
+            command.write_to_buffer(),
+            Err(Error::FieldOutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn duration_round_trip() {
+        let timecode = Timecode::new(1, 2, 3, 4, FrameRate::Smpte30);
+        let duration = timecode.to_duration();
+        assert_eq!(duration.as_secs(), 3723);
+        assert_eq!(duration.subsec_nanos(), 133_333_334);
+        assert_eq!(
+            Timecode::from_duration(duration, FrameRate::Smpte30),
+            timecode
+        );
+        assert_eq!(
+            Timecode::from_duration(duration - Duration::from_nanos(1), FrameRate::Smpte30),
+            Timecode::new(1, 2, 3, 3, FrameRate::Smpte30)
+        );
+        assert_eq!(
+            Timecode::from_duration(Duration::from_millis(3_723_500), FrameRate::Ebu25),
+            Timecode::new(1, 2, 3, 12, FrameRate::Ebu25)
+        );
+    }
+
+    #[test]
+    fn drop_frame_duration() {
+        // One hour of drop-frame timecode is 107_892 frames at 29.97 fps, which is 3599.9964 seconds
+        let timecode = Timecode::new(1, 0, 0, 0, FrameRate::DropFrame2997);
+        assert_eq!(timecode.to_duration().as_millis(), 3_599_996);
+        assert_eq!(
+            Timecode::from_duration(timecode.to_duration(), FrameRate::DropFrame2997),
+            timecode
+        );
+        let timecode = Timecode::new(1, 2, 3, 4, FrameRate::DropFrame2997);
+        assert_eq!(
+            Timecode::from_duration(timecode.to_duration(), FrameRate::DropFrame2997),
+            timecode
+        );
+    }
+
+    #[test]
+    fn from_duration_wraps_after_a_day() {
+        let day = Duration::from_secs(24 * 60 * 60);
+        assert_eq!(
+            Timecode::from_duration(day + Duration::from_secs(1), FrameRate::Film24),
+            Timecode::new(0, 0, 1, 0, FrameRate::Film24)
+        );
+    }
+
+    #[test]
+    fn parse_reads_every_byte() {
+        let payload = [0, 14, 0, 1, 4, 3, 2, 1, 3];
+        let timecode = assert_fully_consumed(Timecode::from_with_position, &payload);
+        assert_eq!(
+            timecode,
+            Timecode {
+                stream_id: 1,
+                ..Timecode::new(1, 2, 3, 4, FrameRate::Smpte30)
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Only 9 of 10 bytes were read")]
+    fn trailing_bytes_are_detected() {
+        let payload = [0, 14, 0, 1, 4, 3, 2, 1, 3, 0];
+        assert_fully_consumed(Timecode::from_with_position, &payload);
+    }
+
+    #[test]
+    fn from_frames_wraps_after_a_day() {
+        assert_eq!(Timecode::from_frames(2_592_000, 3).to_frames(), 0);
+        assert_eq!(Timecode::from_frames(2_589_408, 2).to_frames(), 0);
+    }
+}
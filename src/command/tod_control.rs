@@ -0,0 +1,63 @@
+use crate::command::ARTNET_PROTOCOL_VERSION;
+
+data_structure! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[doc = "ArtTodControl is sent by a controller to control the RDM discovery of a node, e.g. to flush its ToD and start a full discovery."]
+    pub struct TodControl {
+        #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
+        pub version: [u8; 2],
+        #[doc = "Pad length to match ArtPoll"]
+        pub filler1: u8,
+        #[doc = "Pad length to match ArtPoll"]
+        pub filler2: u8,
+        #[doc(hidden)]
+        pub spare: [u8; 7],
+        #[doc = "The top 7 bits of the Port-Address of the port that should be controlled"]
+        pub net: u8,
+        #[doc = "0x00 (AtcNone) does nothing, 0x01 (AtcFlush) flushes the ToD and starts a full discovery, 0x02 (AtcEnd) ends discovery, 0x03 (AtcIncOn) enables incremental discovery and 0x04 (AtcIncOff) disables it."]
+        pub command: u8,
+        #[doc = "The low byte of the Port-Address of the port that should be controlled"]
+        pub address: u8,
+    }
+}
+
+impl Default for TodControl {
+    fn default() -> TodControl {
+        TodControl {
+            version: ARTNET_PROTOCOL_VERSION,
+            filler1: 0,
+            filler2: 0,
+            spare: [0; 7],
+            net: 0,
+            command: 0,
+            address: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArtCommand;
+
+    #[test]
+    fn flush_round_trip() {
+        let command = ArtCommand::TodControl(TodControl {
+            command: 0x01,
+            address: 0x05,
+            ..TodControl::default()
+        });
+        let bytes = command.write_to_buffer().unwrap();
+        assert_eq!(bytes.len(), 24);
+        assert_eq!(&bytes[8..10], &[0x00, 0x82]);
+        assert_eq!(&bytes[21..], &[0, 0x01, 0x05]);
+
+        match ArtCommand::from_buffer(&bytes).unwrap() {
+            ArtCommand::TodControl(control) => {
+                assert_eq!(control.command, 0x01);
+                assert_eq!(control.address, 0x05);
+            }
+            command => panic!("Expected a TodControl, got {:?}", command),
+        }
+    }
+}
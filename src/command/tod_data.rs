@@ -0,0 +1,130 @@
+use crate::command::ARTNET_PROTOCOL_VERSION;
+use crate::{Error, Result};
+use alloc::vec::Vec;
+
+/// The maximum number of UIDs in a single ArtTodData packet
+const MAX_UIDS: usize = 200;
+
+data_structure! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[doc = "ArtTodData is sent by a node in reply to ArtTodRequest and ArtTodControl, and contains (part of) the Table of RDM Devices (ToD) of one of its ports."]
+    #[doc = ""]
+    #[doc = "A ToD with more than 200 devices is sent in multiple packets, see `block_count`."]
+    pub struct TodData {
+        #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
+        pub version: [u8; 2],
+        #[doc = "The RDM version that is used. 0x01 is RDM STANDARD V1.0"]
+        pub rdm_version: u8,
+        #[doc = "The physical port of the node, 1 - 4"]
+        pub port: u8,
+        #[doc(hidden)]
+        pub spare: [u8; 6],
+        #[doc = "The bind index of the node that sends this packet. 0 or 1 for the root device."]
+        pub bind_index: u8,
+        #[doc = "The top 7 bits of the Port-Address of the port"]
+        pub net: u8,
+        #[doc = "0x00 (TodFull) if this is the complete ToD, 0xff (TodNak) if the ToD is not available"]
+        pub command_response: u8,
+        #[doc = "The low byte of the Port-Address of the port"]
+        pub address: u8,
+        #[doc = "The total number of devices in the ToD, big endian"]
+        pub uid_total: [u8; 2],
+        #[doc = "The index of this packet, if the ToD is sent in multiple packets"]
+        pub block_count: u8,
+        #[doc = "The number of UIDs in this packet"]
+        pub uid_count: u8,
+        #[remainder]
+        #[doc = "The 6 byte UIDs of the devices. Use `uids` and `set_uids` instead of accessing this directly."]
+        pub tod: Vec<u8>,
+    }
+}
+
+impl TodData {
+    /// The UIDs in this packet, based on `uid_count`
+    pub fn uids(&self) -> impl Iterator<Item = [u8; 6]> + '_ {
+        self.tod
+            .chunks_exact(6)
+            .take(self.uid_count as usize)
+            .map(|uid| [uid[0], uid[1], uid[2], uid[3], uid[4], uid[5]])
+    }
+
+    /// Set the UIDs in this packet, and update `uid_count`.
+    ///
+    /// Returns an error if there are more than 200 UIDs, these need to be split over multiple packets.
+    pub fn set_uids(&mut self, uids: &[[u8; 6]]) -> Result<()> {
+        if uids.len() > MAX_UIDS {
+            return Err(Error::MessageSizeInvalid {
+                message: uids.concat(),
+                allowed_size: 0..MAX_UIDS * 6 + 1,
+            });
+        }
+        self.tod = uids.concat();
+        self.uid_count = uids.len() as u8;
+        Ok(())
+    }
+}
+
+impl Default for TodData {
+    fn default() -> TodData {
+        TodData {
+            version: ARTNET_PROTOCOL_VERSION,
+            rdm_version: 0x01,
+            port: 1,
+            spare: [0; 6],
+            bind_index: 0,
+            net: 0,
+            command_response: 0,
+            address: 0,
+            uid_total: [0, 0],
+            block_count: 0,
+            uid_count: 0,
+            tod: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArtCommand;
+
+    const PACKET: [u8; 40] = [
+        0x41, 0x72, 0x74, 0x2d, 0x4e, 0x65, 0x74, 0x00, 0x00, 0x81, 0x00, 0x0e, 0x01, 0x02, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x12, 0x00, 0x02, 0x00, 0x02, 0x45, 0x4e,
+        0x00, 0x00, 0x00, 0x01, 0x45, 0x4e, 0x00, 0x00, 0x00, 0x02,
+    ];
+
+    #[test]
+    fn parse_two_uids() {
+        match ArtCommand::from_buffer(&PACKET).unwrap() {
+            ArtCommand::TodData(data) => {
+                assert_eq!(data.port, 2);
+                assert_eq!(data.bind_index, 1);
+                assert_eq!(data.address, 0x12);
+                assert_eq!(data.uid_total, [0, 2]);
+                assert_eq!(data.uid_count, 2);
+                let uids: Vec<_> = data.uids().collect();
+                assert_eq!(
+                    uids,
+                    vec![[0x45, 0x4e, 0, 0, 0, 0x01], [0x45, 0x4e, 0, 0, 0, 0x02]]
+                );
+
+                let bytes = ArtCommand::TodData(data).write_to_buffer().unwrap();
+                assert_eq!(bytes, PACKET);
+            }
+            command => panic!("Expected a TodData, got {:?}", command),
+        }
+    }
+
+    #[test]
+    fn set_uids() {
+        let mut data = TodData::default();
+        data.set_uids(&[[1, 2, 3, 4, 5, 6]]).unwrap();
+        assert_eq!(data.uid_count, 1);
+        assert_eq!(data.uids().collect::<Vec<_>>(), vec![[1, 2, 3, 4, 5, 6]]);
+        assert!(matches!(
+            data.set_uids(&[[0; 6]; 201]),
+            Err(Error::MessageSizeInvalid { allowed_size, .. }) if allowed_size == (0..MAX_UIDS * 6 + 1)
+        ));
+    }
+}
@@ -0,0 +1,97 @@
+use crate::command::ARTNET_PROTOCOL_VERSION;
+use crate::{Error, Result};
+
+data_structure! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[doc = "ArtTodRequest is sent by a controller to request the Table of RDM Devices (ToD) of the given Port-Addresses."]
+    #[doc = ""]
+    #[doc = "Nodes reply with an ArtTodData packet for each port that matches one of the addresses."]
+    pub struct TodRequest {
+        #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
+        pub version: [u8; 2],
+        #[doc = "Pad length to match ArtPoll"]
+        pub filler1: u8,
+        #[doc = "Pad length to match ArtPoll"]
+        pub filler2: u8,
+        #[doc(hidden)]
+        pub spare: [u8; 7],
+        #[doc = "The top 7 bits of the Port-Addresses that should respond"]
+        pub net: u8,
+        #[doc = "The request. 0x00 (TodFull) requests the entire ToD."]
+        pub command: u8,
+        #[doc = "The number of entries in `address` that are used, 0 - 32"]
+        pub ad_count: u8,
+        #[doc = "The low byte of the Port-Addresses that should respond. Only the first `ad_count` entries are used."]
+        pub address: [u8; 32],
+    }
+}
+
+impl TodRequest {
+    /// Request the full ToD of all ports in the given Net, with the given low bytes of the Port-Address.
+    ///
+    /// Returns an error if more than 32 addresses are given.
+    pub fn new(net: u8, addresses: &[u8]) -> Result<TodRequest> {
+        if addresses.len() > 32 {
+            return Err(Error::MessageSizeInvalid {
+                message: addresses.to_vec(),
+                allowed_size: 0..33,
+            });
+        }
+        let mut address = [0; 32];
+        address[..addresses.len()].copy_from_slice(addresses);
+        Ok(TodRequest {
+            net,
+            ad_count: addresses.len() as u8,
+            address,
+            ..TodRequest::default()
+        })
+    }
+
+    /// The addresses that are used, based on `ad_count`
+    pub fn addresses(&self) -> &[u8] {
+        &self.address[..(self.ad_count as usize).min(32)]
+    }
+}
+
+impl Default for TodRequest {
+    fn default() -> TodRequest {
+        TodRequest {
+            version: ARTNET_PROTOCOL_VERSION,
+            filler1: 0,
+            filler2: 0,
+            spare: [0; 7],
+            net: 0,
+            command: 0,
+            ad_count: 0,
+            address: [0; 32],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArtCommand;
+
+    #[test]
+    fn tod_request_round_trip() {
+        let request = TodRequest::new(1, &[0x12, 0x13]).unwrap();
+        let bytes = ArtCommand::TodRequest(request).write_to_buffer().unwrap();
+        assert_eq!(bytes.len(), 56);
+        assert_eq!(&bytes[8..10], &[0x00, 0x80]);
+        assert_eq!(&bytes[21..26], &[1, 0, 2, 0x12, 0x13]);
+
+        match ArtCommand::from_buffer(&bytes).unwrap() {
+            ArtCommand::TodRequest(request) => {
+                assert_eq!(request.net, 1);
+                assert_eq!(request.addresses(), &[0x12, 0x13]);
+            }
+            command => panic!("Expected a TodRequest, got {:?}", command),
+        }
+
+        assert!(matches!(
+            TodRequest::new(0, &[0; 33]),
+            Err(Error::MessageSizeInvalid { allowed_size, .. }) if allowed_size == (0..33)
+        ));
+    }
+}
@@ -0,0 +1,101 @@
+use crate::{command::ARTNET_PROTOCOL_VERSION, Error, Result};
+
+data_structure! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[doc = "ArtTrigger is used to send trigger macros to the network, e.g. to start a show or a cue."]
+    pub struct Trigger {
+        #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
+        pub version: [u8; 2],
+        #[doc = "Pad length to match ArtPoll"]
+        pub filler1: u8,
+        #[doc = "Pad length to match ArtPoll"]
+        pub filler2: u8,
+        #[doc = "The manufacturer code of the nodes that should accept this trigger. Set to 0xffff to address all nodes."]
+        pub oem: [u8; 2],
+        #[doc = "The trigger key. If the Oem is 0xffff, this is 0 for ASCII, 1 for macro, 2 for soft key and 3 for show. Otherwise the meaning is manufacturer specific."]
+        pub key: u8,
+        #[doc = "The trigger sub-key, which is interpreted based on the key"]
+        pub subkey: u8,
+        #[doc = "The payload of the trigger, interpreted based on the key. This is a fixed length field."]
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_array"))]
+        pub data: [u8; 512],
+    }
+}
+
+impl Trigger {
+    /// Create a trigger with the given payload. The payload is padded with zeroes up to 512 bytes.
+    ///
+    /// Returns an error if the payload is longer than 512 bytes.
+    pub fn new(oem: [u8; 2], key: u8, subkey: u8, payload: &[u8]) -> Result<Trigger> {
+        if payload.len() > 512 {
+            return Err(Error::MessageSizeInvalid {
+                message: payload.to_vec(),
+                allowed_size: 0..513,
+            });
+        }
+        let mut data = [0; 512];
+        data[..payload.len()].copy_from_slice(payload);
+        Ok(Trigger {
+            oem,
+            key,
+            subkey,
+            data,
+            ..Trigger::default()
+        })
+    }
+}
+
+impl Default for Trigger {
+    fn default() -> Trigger {
+        Trigger {
+            version: ARTNET_PROTOCOL_VERSION,
+            filler1: 0,
+            filler2: 0,
+            oem: [0xff, 0xff],
+            key: 0,
+            subkey: 0,
+            data: [0; 512],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArtCommand;
+
+    #[test]
+    fn new_pads_payload() {
+        let trigger = Trigger::new([0xff, 0xff], 1, 2, &[1, 2, 3]).unwrap();
+        assert_eq!(&trigger.data[..4], &[1, 2, 3, 0]);
+        assert!(trigger.data[3..].iter().all(|&b| b == 0));
+
+        let bytes = ArtCommand::OpTrigger(Box::new(trigger))
+            .write_to_buffer()
+            .unwrap();
+        assert_eq!(bytes.len(), 530);
+        assert_eq!(
+            &bytes[..21],
+            &[65, 114, 116, 45, 78, 101, 116, 0, 0, 0x99, 0, 14, 0, 0, 0xff, 0xff, 1, 2, 1, 2, 3]
+        );
+
+        match ArtCommand::from_buffer(&bytes).unwrap() {
+            ArtCommand::OpTrigger(trigger) => {
+                assert_eq!(trigger.oem, [0xff, 0xff]);
+                assert_eq!(trigger.key, 1);
+                assert_eq!(trigger.subkey, 2);
+                assert_eq!(&trigger.data[..3], &[1, 2, 3]);
+            }
+            command => panic!("Expected a Trigger, got {:?}", command),
+        }
+    }
+
+    #[test]
+    fn new_rejects_oversized_payload() {
+        assert!(Trigger::new([0xff, 0xff], 0, 0, &[0; 512]).is_ok());
+        assert!(matches!(
+            Trigger::new([0xff, 0xff], 0, 0, &[0; 513]),
+            Err(Error::MessageSizeInvalid { allowed_size, .. }) if allowed_size == (0..513)
+        ));
+    }
+}
@@ -0,0 +1,76 @@
+use crate::command::ARTNET_PROTOCOL_VERSION;
+use alloc::vec::Vec;
+
+data_structure! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[doc = "ArtVideoData is sent by a controller to write characters to the screen of a node that implements the extended video features. This packet is deprecated."]
+    #[doc = ""]
+    #[doc = "It updates a window of `len_x` by `len_y` characters, starting at `pos_x`, `pos_y`. Every character takes two bytes in `data`, the data is sent one scanline after the other."]
+    pub struct VideoData {
+        #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
+        pub version: [u8; 2],
+        #[doc = "Pad length to match ArtPoll"]
+        pub filler1: u8,
+        #[doc = "Pad length to match ArtPoll"]
+        pub filler2: u8,
+        #[doc = "The column of the top left character of the window"]
+        pub pos_x: u8,
+        #[doc = "The row of the top left character of the window"]
+        pub pos_y: u8,
+        #[doc = "The width of the window in characters"]
+        pub len_x: u8,
+        #[doc = "The height of the window in characters"]
+        pub len_y: u8,
+        #[remainder]
+        #[doc = "The characters of the window, `2 * len_x * len_y` bytes"]
+        pub data: Vec<u8>,
+    }
+}
+
+impl VideoData {
+    /// The number of `data` bytes that `len_x` and `len_y` describe
+    pub fn expected_data_len(&self) -> usize {
+        2 * usize::from(self.len_x) * usize::from(self.len_y)
+    }
+}
+
+impl Default for VideoData {
+    fn default() -> VideoData {
+        VideoData {
+            version: ARTNET_PROTOCOL_VERSION,
+            filler1: 0,
+            filler2: 0,
+            pos_x: 0,
+            pos_y: 0,
+            len_x: 0,
+            len_y: 0,
+            data: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArtCommand;
+
+    #[test]
+    fn one_scanline_round_trip() {
+        let data = VideoData {
+            pos_x: 2,
+            pos_y: 5,
+            len_x: 3,
+            len_y: 1,
+            data: vec![b'A', 0x07, b'B', 0x07, b'C', 0x07],
+            ..VideoData::default()
+        };
+        assert_eq!(data.expected_data_len(), 6);
+
+        let command = ArtCommand::VideoData(data);
+        let bytes = command.write_to_buffer().unwrap();
+        assert_eq!(bytes.len(), 24);
+        assert_eq!(&bytes[8..10], &[0x40, 0xa0]);
+        assert_eq!(&bytes[14..18], &[2, 5, 3, 1]);
+        assert_eq!(ArtCommand::from_buffer(&bytes).unwrap(), command);
+    }
+}
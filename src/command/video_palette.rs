@@ -0,0 +1,92 @@
+use crate::command::ARTNET_PROTOCOL_VERSION;
+
+data_structure! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[doc = "ArtVideoPalette is sent by a controller to set the colour palette of a node that implements the extended video features. This packet is deprecated."]
+    #[doc = ""]
+    #[doc = "The palette has 17 colours, which are sent as separate arrays of red, green and blue components. Use [colour](#method.colour) and [set_colour](#method.set_colour) to access a single colour."]
+    pub struct VideoPalette {
+        #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
+        pub version: [u8; 2],
+        #[doc = "Pad length to match ArtPoll"]
+        pub filler1: u8,
+        #[doc = "Pad length to match ArtPoll"]
+        pub filler2: u8,
+        #[doc = "The red component of every colour"]
+        pub colour_red: [u8; 17],
+        #[doc = "The green component of every colour"]
+        pub colour_green: [u8; 17],
+        #[doc = "The blue component of every colour"]
+        pub colour_blue: [u8; 17],
+    }
+}
+
+impl VideoPalette {
+    /// The number of colours in the palette
+    pub const COLOURS: usize = 17;
+
+    /// The colour at `index` as `[red, green, blue]`, or `None` if `index` is not below 17
+    pub fn colour(&self, index: usize) -> Option<[u8; 3]> {
+        Some([
+            *self.colour_red.get(index)?,
+            self.colour_green[index],
+            self.colour_blue[index],
+        ])
+    }
+
+    /// Set the colour at `index` to `[red, green, blue]`. Returns `false` if `index` is not below 17.
+    pub fn set_colour(&mut self, index: usize, [red, green, blue]: [u8; 3]) -> bool {
+        if index >= VideoPalette::COLOURS {
+            return false;
+        }
+        self.colour_red[index] = red;
+        self.colour_green[index] = green;
+        self.colour_blue[index] = blue;
+        true
+    }
+}
+
+impl Default for VideoPalette {
+    fn default() -> VideoPalette {
+        VideoPalette {
+            version: ARTNET_PROTOCOL_VERSION,
+            filler1: 0,
+            filler2: 0,
+            colour_red: [0; 17],
+            colour_green: [0; 17],
+            colour_blue: [0; 17],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArtCommand;
+
+    #[test]
+    fn video_palette_round_trip() {
+        let mut palette = VideoPalette::default();
+        assert!(palette.set_colour(0, [0xff, 0x00, 0x00]));
+        assert!(palette.set_colour(16, [0x10, 0x20, 0x30]));
+        assert!(!palette.set_colour(17, [1, 2, 3]));
+
+        let command = ArtCommand::VideoPalette(palette);
+        let bytes = command.write_to_buffer().unwrap();
+        assert_eq!(bytes.len(), 65);
+        assert_eq!(&bytes[8..10], &[0x20, 0xa0]);
+        assert_eq!(&bytes[14..16], &[0xff, 0]);
+        assert_eq!(bytes[30], 0x10);
+        assert_eq!(bytes[64], 0x30);
+
+        match ArtCommand::from_buffer(&bytes).unwrap() {
+            ArtCommand::VideoPalette(palette) => {
+                assert_eq!(palette.colour(0), Some([0xff, 0x00, 0x00]));
+                assert_eq!(palette.colour(1), Some([0, 0, 0]));
+                assert_eq!(palette.colour(16), Some([0x10, 0x20, 0x30]));
+                assert_eq!(palette.colour(17), None);
+            }
+            command => panic!("Expected a VideoPalette, got {:?}", command),
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use crate::command::ARTNET_PROTOCOL_VERSION;
+use crate::FixedName;
+use alloc::vec::Vec;
+
+data_structure! {
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[doc = "ArtVideoSetup is sent by a controller to set up the screen of a node that implements the extended video features. This packet is deprecated."]
+    pub struct VideoSetup {
+        #[doc = "Determines which version the server has. Will be ARTNET_PROTOCOL_VERSION by default"]
+        pub version: [u8; 2],
+        #[doc(hidden)]
+        pub filler: [u8; 4],
+        #[doc = "Bit 0 is set to use the font in `font_data`, and cleared to use the default font of the node"]
+        pub control: u8,
+        #[doc = "The number of pixel rows of every character of the font"]
+        pub font_height: u8,
+        #[doc = "The first character that is defined in `font_data`"]
+        pub first_font: u8,
+        #[doc = "The last character that is defined in `font_data`"]
+        pub last_font: u8,
+        #[doc = "The name of the font, in the Windows font naming convention"]
+        pub win_font_name: FixedName<64>,
+        #[remainder]
+        #[doc = "The bitmaps of the characters `first_font` to `last_font`, `font_height` bytes for every character"]
+        pub font_data: Vec<u8>,
+    }
+}
+
+impl VideoSetup {
+    /// The bit of `control` that selects the font in `font_data`
+    pub const CUSTOM_FONT: u8 = 0x01;
+}
+
+impl Default for VideoSetup {
+    fn default() -> VideoSetup {
+        VideoSetup {
+            version: ARTNET_PROTOCOL_VERSION,
+            filler: [0; 4],
+            control: 0,
+            font_height: 0,
+            first_font: 0,
+            last_font: 0,
+            win_font_name: FixedName::default(),
+            font_data: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ArtCommand;
+
+    #[test]
+    fn video_setup_round_trip() {
+        let command = ArtCommand::VideoSetup(VideoSetup {
+            control: VideoSetup::CUSTOM_FONT,
+            font_height: 2,
+            first_font: b'A',
+            last_font: b'B',
+            win_font_name: FixedName::new("Courier").unwrap(),
+            font_data: vec![0x18, 0x24, 0x3c, 0x24],
+            ..VideoSetup::default()
+        });
+        let bytes = command.write_to_buffer().unwrap();
+        assert_eq!(bytes.len(), 88);
+        assert_eq!(&bytes[16..20], &[0x01, 2, b'A', b'B']);
+        assert_eq!(&bytes[20..27], b"Courier");
+        assert_eq!(ArtCommand::from_buffer(&bytes).unwrap(), command);
+    }
+}
@@ -0,0 +1,192 @@
+use std::io::ErrorKind;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4, ToSocketAddrs, UdpSocket};
+use std::time::{Duration, Instant};
+
+use crate::{ArtCommand, Error, Poll, PollReply, Result, ARTNET_PORT};
+
+/// The size of the receive buffer. This is larger than the largest Art-Net packet.
+pub(crate) const MAX_PACKET_LENGTH: usize = 2048;
+
+/// A blocking UDP socket that sends and receives `ArtCommand`s.
+///
+/// ```no_run
+/// use artnet_protocol::{ArtCommand, Connection, Poll};
+///
+/// let connection = Connection::bind().unwrap();
+/// connection.broadcast(&ArtCommand::Poll(Poll::default())).unwrap();
+/// loop {
+///     let (command, addr) = connection.recv().unwrap();
+///     println!("Received {:?} from {}", command, addr);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Connection {
+    socket: UdpSocket,
+}
+
+impl Connection {
+    /// Bind to port 6454 on all interfaces.
+    pub fn bind() -> Result<Connection> {
+        Connection::bind_to(("0.0.0.0", ARTNET_PORT))
+    }
+
+    /// Bind to the given address. This is useful if the node should only listen on a single interface.
+    pub fn bind_to(addr: impl ToSocketAddrs) -> Result<Connection> {
+        let socket = UdpSocket::bind(addr).map_err(Error::Io)?;
+        Ok(Connection { socket })
+    }
+
+    /// Wrap a socket that was already set up
+    pub fn from_socket(socket: UdpSocket) -> Connection {
+        Connection { socket }
+    }
+
+    /// The address that this socket is bound to
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        self.socket.local_addr().map_err(Error::Io)
+    }
+
+    /// Serialize the command and send it to the given address
+    pub fn send(&self, command: &ArtCommand, addr: SocketAddr) -> Result<()> {
+        let bytes = command.write_to_buffer()?;
+        self.send_bytes(&bytes, addr)
+    }
+
+    /// Send bytes that were already serialized to the given address
+    pub(crate) fn send_bytes(&self, bytes: &[u8], addr: SocketAddr) -> Result<()> {
+        self.socket.send_to(bytes, addr).map_err(Error::Io)?;
+        Ok(())
+    }
+
+    /// Send the command to all devices on the network (255.255.255.255:6454), e.g. an ArtPoll to discover nodes.
+    ///
+    /// This enables broadcasting on the socket.
+    pub fn broadcast(&self, command: &ArtCommand) -> Result<()> {
+        self.socket.set_broadcast(true).map_err(Error::Io)?;
+        self.send(
+            command,
+            SocketAddrV4::new(Ipv4Addr::BROADCAST, ARTNET_PORT).into(),
+        )
+    }
+
+    /// Block until the next packet arrives and parse it.
+    ///
+    /// Packets that are not valid Art-Net return an error, the connection can be used again afterwards.
+    pub fn recv(&self) -> Result<(ArtCommand, SocketAddr)> {
+        let mut buffer = [0u8; MAX_PACKET_LENGTH];
+        let (length, addr) = self.socket.recv_from(&mut buffer).map_err(Error::Io)?;
+        let command = ArtCommand::from_buffer(&buffer[..length])?;
+        Ok((command, addr))
+    }
+
+    /// Broadcast an ArtPoll and collect the ArtPollReply packets that arrive before the timeout, see [discover](fn.discover.html).
+    pub fn discover(&self, timeout: Duration) -> Result<Vec<PollReply>> {
+        self.discover_at(
+            SocketAddrV4::new(Ipv4Addr::BROADCAST, ARTNET_PORT).into(),
+            timeout,
+        )
+    }
+
+    /// Send an ArtPoll to the given address and collect the ArtPollReply packets that arrive before the timeout.
+    ///
+    /// Replies are deduplicated by their address and bind index, the last reply of a node is kept. Packets that are not valid Art-Net are ignored.
+    /// The read timeout of the socket is restored afterwards.
+    pub fn discover_at(&self, addr: SocketAddr, timeout: Duration) -> Result<Vec<PollReply>> {
+        let previous_timeout = self.socket.read_timeout().map_err(Error::Io)?;
+        let result = self.collect_poll_replies(addr, timeout);
+        let restored = self
+            .socket
+            .set_read_timeout(previous_timeout)
+            .map_err(Error::Io);
+        // An error while collecting the replies is more relevant than an error while restoring the timeout
+        let replies = result?;
+        restored?;
+        Ok(replies)
+    }
+
+    fn collect_poll_replies(&self, addr: SocketAddr, timeout: Duration) -> Result<Vec<PollReply>> {
+        if addr.ip() == Ipv4Addr::BROADCAST {
+            self.socket.set_broadcast(true).map_err(Error::Io)?;
+        }
+        self.send(&ArtCommand::Poll(Poll::default()), addr)?;
+
+        let deadline = Instant::now() + timeout;
+        let mut replies: Vec<PollReply> = Vec::new();
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            if remaining.is_zero() {
+                return Ok(replies);
+            }
+            self.socket
+                .set_read_timeout(Some(remaining))
+                .map_err(Error::Io)?;
+            let reply = match self.recv() {
+                Ok((ArtCommand::PollReply(reply), _)) => reply,
+                Ok(_) => continue,
+                Err(Error::Io(e))
+                    if matches!(e.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) =>
+                {
+                    return Ok(replies);
+                }
+                Err(Error::Io(e)) => return Err(Error::Io(e)),
+                Err(_) => continue,
+            };
+            match replies.iter_mut().find(|known| {
+                known.address == reply.address && known.bind_index == reply.bind_index
+            }) {
+                Some(known) => *known = *reply,
+                None => replies.push(*reply),
+            }
+        }
+    }
+}
+
+/// Find the Art-Net nodes on the network.
+///
+/// This binds to port 6454, broadcasts an ArtPoll and collects the ArtPollReply packets that arrive before the timeout, deduplicated by their address and bind index.
+/// Use [Connection::discover_at](struct.Connection.html#method.discover_at) to reuse a socket, or to poll a single node.
+///
+/// ```no_run
+/// use artnet_protocol::discover;
+/// use std::time::Duration;
+///
+/// for reply in discover(Duration::from_secs(3)).unwrap() {
+///     println!("Found {:?} at {}", reply.short_name_str(), reply.address);
+/// }
+/// ```
+pub fn discover(timeout: Duration) -> Result<Vec<PollReply>> {
+    Connection::bind()?.discover(timeout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Poll;
+
+    #[test]
+    fn broadcast_poll() {
+        let connection = Connection::bind_to("127.0.0.1:0").unwrap();
+        connection
+            .broadcast(&ArtCommand::Poll(Poll::default()))
+            .unwrap();
+    }
+
+    #[test]
+    fn send_and_recv() {
+        let sender = Connection::bind_to("127.0.0.1:0").unwrap();
+        let receiver = Connection::bind_to("127.0.0.1:0").unwrap();
+        sender
+            .send(
+                &ArtCommand::Poll(Poll::default()),
+                receiver.local_addr().unwrap(),
+            )
+            .unwrap();
+
+        let (command, addr) = receiver.recv().unwrap();
+        assert_eq!(addr, sender.local_addr().unwrap());
+        match command {
+            ArtCommand::Poll(_) => {}
+            command => panic!("Expected a Poll, got {:?}", command),
+        }
+    }
+}
@@ -1,55 +1,95 @@
 use crate::{Error, Result};
-use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
-use std::io::{Cursor, Read};
-use std::net::Ipv4Addr;
+use alloc::vec::Vec;
+use byteorder::{ByteOrder, LittleEndian};
+use core::net::Ipv4Addr;
+use core::str::{self, Utf8Error};
 
 pub trait Convertable<T>: Sized {
     fn from_cursor(cursor: &mut Cursor<&[u8]>) -> Result<Self>;
     fn write_to_buffer(&self, buffer: &mut Vec<u8>, context: &T) -> Result<()>;
+    fn serialized_len(&self) -> usize;
     #[cfg(test)]
     fn get_test_value() -> Self;
     #[cfg(test)]
     fn is_equal(&self, other: &Self) -> bool;
 }
 
-impl<T> Convertable<T> for Ipv4Addr {
-    fn from_cursor(cursor: &mut Cursor<&[u8]>) -> Result<Self> {
-        Ok(Ipv4Addr::new(
-            cursor.read_u8().map_err(Error::CursorEof)?,
-            cursor.read_u8().map_err(Error::CursorEof)?,
-            cursor.read_u8().map_err(Error::CursorEof)?,
-            cursor.read_u8().map_err(Error::CursorEof)?,
-        ))
+/// A minimal replacement for `std::io::Cursor`, so that parsing does not depend on `std`.
+pub(crate) struct Cursor<T> {
+    inner: T,
+    position: usize,
+}
+
+impl<'a> Cursor<&'a [u8]> {
+    pub(crate) fn new(inner: &'a [u8]) -> Self {
+        Cursor { inner, position: 0 }
     }
 
-    fn write_to_buffer(&self, buffer: &mut Vec<u8>, _: &T) -> Result<()> {
-        buffer.extend_from_slice(&self.octets());
+    /// Read the bytes after the current position, up to the end. Fails instead of panicking if the position is past the end.
+    pub(crate) fn read_to_end(&mut self) -> Result<&'a [u8]> {
+        let bytes = self.inner.get(self.position..).ok_or(Error::CursorEof {
+            offset: self.position,
+            context: "unknown field",
+        })?;
+        self.position = self.inner.len();
+        Ok(bytes)
+    }
+
+    pub(crate) fn position(&self) -> u64 {
+        self.position as u64
+    }
+
+    pub(crate) fn read_exact(&mut self, buffer: &mut [u8]) -> Result<()> {
+        let end = self.position + buffer.len();
+        let bytes = self.inner.get(self.position..end).ok_or(Error::CursorEof {
+            offset: self.position,
+            context: "unknown field",
+        })?;
+        buffer.copy_from_slice(bytes);
+        self.position = end;
         Ok(())
     }
 
-    #[cfg(test)]
-    fn get_test_value() -> Self {
-        Ipv4Addr::new(1, 2, 3, 4)
+    pub(crate) fn read_u8(&mut self) -> Result<u8> {
+        let mut buffer = [0];
+        self.read_exact(&mut buffer)?;
+        Ok(buffer[0])
     }
-    #[cfg(test)]
-    fn is_equal(&self, other: &Self) -> bool {
-        self == other
+
+    pub(crate) fn read_u16<B: ByteOrder>(&mut self) -> Result<u16> {
+        let mut buffer = [0; 2];
+        self.read_exact(&mut buffer)?;
+        Ok(B::read_u16(&buffer))
     }
 }
 
-impl<T> Convertable<T> for Vec<u8> {
+/// Interpret a fixed length field as a string, stopping at the first null byte.
+pub(crate) fn null_terminated_str(bytes: &[u8]) -> core::result::Result<&str, Utf8Error> {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    str::from_utf8(&bytes[..end])
+}
+
+impl<T> Convertable<T> for Ipv4Addr {
     fn from_cursor(cursor: &mut Cursor<&[u8]>) -> Result<Self> {
-        let remaining = cursor.get_ref();
-        Ok(remaining[cursor.position() as usize..].to_vec())
+        Ok(Ipv4Addr::new(
+            cursor.read_u8()?,
+            cursor.read_u8()?,
+            cursor.read_u8()?,
+            cursor.read_u8()?,
+        ))
     }
 
     fn write_to_buffer(&self, buffer: &mut Vec<u8>, _: &T) -> Result<()> {
-        buffer.extend_from_slice(&self[..]);
+        buffer.extend_from_slice(&self.octets());
         Ok(())
     }
+
+    fn serialized_len(&self) -> usize {
+        4
+    }
     #[cfg(test)]
     fn get_test_value() -> Self {
-        vec![1, 2, 3, 4]
+        Ipv4Addr::new(1, 2, 3, 4)
     }
     #[cfg(test)]
     fn is_equal(&self, other: &Self) -> bool {
@@ -59,13 +99,16 @@ impl<T> Convertable<T> for Vec<u8> {
 
 impl<T> Convertable<T> for u8 {
     fn from_cursor(cursor: &mut Cursor<&[u8]>) -> Result<Self> {
-        cursor.read_u8().map_err(Error::CursorEof)
+        cursor.read_u8()
     }
 
     fn write_to_buffer(&self, buffer: &mut Vec<u8>, _: &T) -> Result<()> {
         buffer.push(*self);
         Ok(())
     }
+    fn serialized_len(&self) -> usize {
+        1
+    }
     #[cfg(test)]
     fn get_test_value() -> Self {
         1
@@ -81,15 +124,16 @@ macro_rules! convert_primitive {
         impl<T> Convertable<T> for [u8; $length] {
             fn from_cursor(cursor: &mut Cursor<&[u8]>) -> Result<Self> {
                 let mut result = [0; $length];
-                cursor
-                    .read_exact(&mut result[..])
-                    .map_err(Error::CursorEof)?;
+                cursor.read_exact(&mut result[..])?;
                 Ok(result)
             }
             fn write_to_buffer(&self, buffer: &mut Vec<u8>, _: &T) -> Result<()> {
                 buffer.extend_from_slice(&self[..]);
                 Ok(())
             }
+            fn serialized_len(&self) -> usize {
+                $length
+            }
             #[cfg(test)]
             fn get_test_value() -> Self {
                 [0; $length]
@@ -103,12 +147,16 @@ macro_rules! convert_primitive {
     ($ty:ty, $read_fn:tt, $write_fn:tt) => {
         impl<T> Convertable<T> for $ty {
             fn from_cursor(cursor: &mut Cursor<&[u8]>) -> Result<Self> {
-                cursor.$read_fn::<LittleEndian>().map_err(Error::CursorEof)
+                cursor.$read_fn::<LittleEndian>()
             }
             fn write_to_buffer(&self, buffer: &mut Vec<u8>, _: &T) -> Result<()> {
-                buffer
-                    .$write_fn::<LittleEndian>(*self)
-                    .map_err(Error::CursorEof)
+                let mut bytes = [0; core::mem::size_of::<$ty>()];
+                LittleEndian::$write_fn(&mut bytes, *self);
+                buffer.extend_from_slice(&bytes);
+                Ok(())
+            }
+            fn serialized_len(&self) -> usize {
+                core::mem::size_of::<$ty>()
             }
             #[cfg(test)]
             fn get_test_value() -> Self {
@@ -127,6 +175,52 @@ convert_primitive!([u8; 2]);
 convert_primitive!([u8; 3]);
 convert_primitive!([u8; 4]);
 convert_primitive!([u8; 6]);
+convert_primitive!([u8; 7]);
+convert_primitive!([u8; 8]);
+convert_primitive!([u8; 16]);
+convert_primitive!([u8; 17]);
 convert_primitive!([u8; 18]);
+convert_primitive!([u8; 21]);
+convert_primitive!([u8; 22]);
 convert_primitive!([u8; 26]);
+convert_primitive!([u8; 32]);
 convert_primitive!([u8; 64]);
+convert_primitive!([u8; 512]);
+
+/// Parse a packet with a fixed size and check that every byte was read, so that a field with the wrong size does not go unnoticed.
+///
+/// Use it with the `from_with_position` function of a `data_structure!`.
+#[cfg(test)]
+pub(crate) fn assert_fully_consumed<T>(parse: fn(&[u8]) -> Result<(T, usize)>, data: &[u8]) -> T {
+    let (value, position) = parse(data).expect("Could not deserialize");
+    assert_eq!(
+        position,
+        data.len(),
+        "Only {} of {} bytes were read",
+        position,
+        data.len()
+    );
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_to_end_past_the_end() {
+        let mut cursor = Cursor::new(&[1, 2, 3][..]);
+        cursor.position = 5;
+        assert!(matches!(
+            cursor.read_to_end(),
+            Err(Error::CursorEof { offset: 5, .. })
+        ));
+        assert!(cursor.read_u8().is_err());
+
+        cursor.position = 3;
+        assert_eq!(cursor.read_to_end().unwrap(), &[] as &[u8]);
+        cursor.position = 1;
+        assert_eq!(cursor.read_to_end().unwrap(), &[2, 3]);
+        assert_eq!(cursor.position(), 3);
+    }
+}
@@ -1,13 +1,18 @@
-use crate::byteorder::ReadBytesExt;
 use crate::convert::Convertable;
+use crate::convert::Cursor;
 use crate::{Error, Result};
-use std::io::Cursor;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
 
 bitflags! {
-    #[derive(Debug, PartialEq)]
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     /// The TalkToMe flag, as to be used in the `Poll` and `PollReply` message
     pub struct ArtTalkToMe: u8 {
-        /// Enable VLC transmission if set, disabled otherwise
+        /// Enable targeted mode, in which only the nodes with a Port-Address in the target range of the poll send a reply
+        const TARGETED_MODE = 0b0010_0000;
+
+        /// Despite the name, the specification defines that VLC transmission is disabled if this is set, and enabled otherwise. See [set_vlc_disabled](#method.set_vlc_disabled).
         const ENABLE_VLC = 0b0001_0000;
 
         /// Diagnostic messages are unicast. If this is not set, the messages are broadcast. Has no effect if `ENABLE_DIAGNOSTICS` is not set.
@@ -24,15 +29,83 @@ bitflags! {
     }
 }
 
+impl ArtTalkToMe {
+    /// Whether the nodes send an ArtPollReply whenever something changes, see `EMIT_CHANGES`
+    pub fn emit_changes(&self) -> bool {
+        self.contains(ArtTalkToMe::EMIT_CHANGES)
+    }
+
+    /// Set whether the nodes send an ArtPollReply whenever something changes, see `EMIT_CHANGES`
+    pub fn set_emit_changes(&mut self, value: bool) {
+        self.set(ArtTalkToMe::EMIT_CHANGES, value)
+    }
+
+    /// Whether the nodes send diagnostics, see `ENABLE_DIAGNOSTICS`
+    pub fn diagnostics_enabled(&self) -> bool {
+        self.contains(ArtTalkToMe::ENABLE_DIAGNOSTICS)
+    }
+
+    /// Set whether the nodes send diagnostics, see `ENABLE_DIAGNOSTICS`
+    pub fn set_diagnostics_enabled(&mut self, value: bool) {
+        self.set(ArtTalkToMe::ENABLE_DIAGNOSTICS, value)
+    }
+
+    /// Whether diagnostics are unicast instead of broadcast, see `UNICAST_DIAGNOSTICS`
+    pub fn unicast_diagnostics(&self) -> bool {
+        self.contains(ArtTalkToMe::UNICAST_DIAGNOSTICS)
+    }
+
+    /// Set whether diagnostics are unicast instead of broadcast, see `UNICAST_DIAGNOSTICS`
+    pub fn set_unicast_diagnostics(&mut self, value: bool) {
+        self.set(ArtTalkToMe::UNICAST_DIAGNOSTICS, value)
+    }
+
+    /// Whether VLC transmission is disabled, which is the `ENABLE_VLC` bit
+    pub fn vlc_disabled(&self) -> bool {
+        self.contains(ArtTalkToMe::ENABLE_VLC)
+    }
+
+    /// Set whether VLC transmission is disabled, which is the `ENABLE_VLC` bit
+    pub fn set_vlc_disabled(&mut self, value: bool) {
+        self.set(ArtTalkToMe::ENABLE_VLC, value)
+    }
+
+    /// Whether targeted mode is enabled, see `TARGETED_MODE`
+    pub fn targeted_mode(&self) -> bool {
+        self.contains(ArtTalkToMe::TARGETED_MODE)
+    }
+
+    /// Set whether targeted mode is enabled, see `TARGETED_MODE`
+    pub fn set_targeted_mode(&mut self, value: bool) {
+        self.set(ArtTalkToMe::TARGETED_MODE, value)
+    }
+}
+
+/// Unknown bits are ignored
+impl From<u8> for ArtTalkToMe {
+    fn from(value: u8) -> ArtTalkToMe {
+        ArtTalkToMe::from_bits_truncate(value)
+    }
+}
+
+impl From<ArtTalkToMe> for u8 {
+    fn from(value: ArtTalkToMe) -> u8 {
+        value.bits()
+    }
+}
+
 impl<T> Convertable<T> for ArtTalkToMe {
     fn from_cursor(cursor: &mut Cursor<&[u8]>) -> Result<Self> {
-        let b = cursor.read_u8().map_err(Error::CursorEof)?;
+        let b = cursor.read_u8()?;
         Ok(ArtTalkToMe::from_bits_truncate(b))
     }
     fn write_to_buffer(&self, buffer: &mut Vec<u8>, _: &T) -> Result<()> {
         buffer.push(self.bits());
         Ok(())
     }
+    fn serialized_len(&self) -> usize {
+        1
+    }
     #[cfg(test)]
     fn get_test_value() -> Self {
         ArtTalkToMe::NONE
@@ -42,3 +115,623 @@ impl<T> Convertable<T> for ArtTalkToMe {
         self == other
     }
 }
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    /// The Command flags, as to be used in the `IpProg` message
+    pub struct IpProgCommand: u8 {
+        /// Any programming will only happen if this is set
+        const ENABLE_PROGRAMMING = 0b1000_0000;
+
+        /// Enable DHCP on the node. If this is set, the other programming flags are ignored
+        const ENABLE_DHCP = 0b0100_0000;
+
+        /// Program the default gateway
+        const PROGRAM_DEFAULT_GATEWAY = 0b0001_0000;
+
+        /// Reset the IP address, subnet mask and default gateway to the default values
+        const RESET_TO_DEFAULT = 0b0000_1000;
+
+        /// Program the IP address
+        const PROGRAM_IP = 0b0000_0100;
+
+        /// Program the subnet mask
+        const PROGRAM_SUBNET_MASK = 0b0000_0010;
+
+        /// Program the port. This is deprecated
+        const PROGRAM_PORT = 0b0000_0001;
+
+        /// No flags, the node will only reply with its current configuration
+        const NONE = 0b0000_0000;
+    }
+}
+
+impl<T> Convertable<T> for IpProgCommand {
+    fn from_cursor(cursor: &mut Cursor<&[u8]>) -> Result<Self> {
+        let b = cursor.read_u8()?;
+        Ok(IpProgCommand::from_bits_truncate(b))
+    }
+    fn write_to_buffer(&self, buffer: &mut Vec<u8>, _: &T) -> Result<()> {
+        buffer.push(self.bits());
+        Ok(())
+    }
+    fn serialized_len(&self) -> usize {
+        1
+    }
+    #[cfg(test)]
+    fn get_test_value() -> Self {
+        IpProgCommand::NONE
+    }
+    #[cfg(test)]
+    fn is_equal(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+/// The command of an ArtAddress packet, as to be used in the `Address` message.
+///
+/// Commands that act on a single port carry the port index (0 - 3).
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AddressCommand {
+    /// No action
+    #[default]
+    None,
+    /// If the node is currently in merge mode, cancel merge mode upon receipt of the next ArtDmx packet
+    CancelMerge,
+    /// The front panel indicators of the node operate normally
+    LedNormal,
+    /// The front panel indicators of the node are disabled and switched off
+    LedMute,
+    /// Rapid flashing of the front panel indicators, to identify the node
+    LedLocate,
+    /// Resets the receive error flags of the node
+    ResetRxFlags,
+    /// Enable analysis and debugging mode
+    AnalysisOn,
+    /// Disable analysis and debugging mode
+    AnalysisOff,
+    /// Set the outputs to hold the last state when the network data is lost
+    FailHold,
+    /// Set the outputs to zero when the network data is lost
+    FailZero,
+    /// Set the outputs to full when the network data is lost
+    FailFull,
+    /// Set the outputs to play the fail-over scene when the network data is lost
+    FailScene,
+    /// Record the current output state as the fail-over scene
+    FailRecord,
+    /// Set the given DMX port to merge in LTP mode
+    MergeLtp(u8),
+    /// Set the given port to output DMX512 data from the network
+    DirectionTx(u8),
+    /// Set the given port to input DMX512 data to the network
+    DirectionRx(u8),
+    /// Set the given DMX port to merge in HTP mode
+    MergeHtp(u8),
+    /// Set the given DMX port to output both DMX512 and RDM packets from the Art-Net protocol
+    ArtNetSel(u8),
+    /// Set the given DMX port to output DMX512 data from the sACN protocol and RDM data from Art-Net
+    AcnSel(u8),
+    /// Clear the DMX output buffer of the given port
+    ClearOp(u8),
+    /// Set the output style of the given port to delta mode
+    StyleDelta(u8),
+    /// Set the output style of the given port to constant mode
+    StyleConst(u8),
+    /// Enable RDM on the given port
+    RdmEnable(u8),
+    /// Disable RDM on the given port
+    RdmDisable(u8),
+    /// A command value that is not defined by the Art-Net specification
+    Unknown(u8),
+}
+
+impl AddressCommand {
+    /// The port index of commands that act on a single port, or `None` for commands that act on the whole node
+    pub fn port(self) -> Option<u8> {
+        match self {
+            AddressCommand::MergeLtp(port)
+            | AddressCommand::DirectionTx(port)
+            | AddressCommand::DirectionRx(port)
+            | AddressCommand::MergeHtp(port)
+            | AddressCommand::ArtNetSel(port)
+            | AddressCommand::AcnSel(port)
+            | AddressCommand::ClearOp(port)
+            | AddressCommand::StyleDelta(port)
+            | AddressCommand::StyleConst(port)
+            | AddressCommand::RdmEnable(port)
+            | AddressCommand::RdmDisable(port) => Some(port),
+            _ => None,
+        }
+    }
+}
+
+impl From<u8> for AddressCommand {
+    fn from(value: u8) -> Self {
+        let port = value & 0x0f;
+        match value {
+            0x00 => AddressCommand::None,
+            0x01 => AddressCommand::CancelMerge,
+            0x02 => AddressCommand::LedNormal,
+            0x03 => AddressCommand::LedMute,
+            0x04 => AddressCommand::LedLocate,
+            0x05 => AddressCommand::ResetRxFlags,
+            0x06 => AddressCommand::AnalysisOn,
+            0x07 => AddressCommand::AnalysisOff,
+            0x08 => AddressCommand::FailHold,
+            0x09 => AddressCommand::FailZero,
+            0x0a => AddressCommand::FailFull,
+            0x0b => AddressCommand::FailScene,
+            0x0c => AddressCommand::FailRecord,
+            0x10..=0x13 => AddressCommand::MergeLtp(port),
+            0x20..=0x23 => AddressCommand::DirectionTx(port),
+            0x30..=0x33 => AddressCommand::DirectionRx(port),
+            0x50..=0x53 => AddressCommand::MergeHtp(port),
+            0x60..=0x63 => AddressCommand::ArtNetSel(port),
+            0x70..=0x73 => AddressCommand::AcnSel(port),
+            0x90..=0x93 => AddressCommand::ClearOp(port),
+            0xa0..=0xa3 => AddressCommand::StyleDelta(port),
+            0xb0..=0xb3 => AddressCommand::StyleConst(port),
+            0xc0..=0xc3 => AddressCommand::RdmEnable(port),
+            0xd0..=0xd3 => AddressCommand::RdmDisable(port),
+            _ => AddressCommand::Unknown(value),
+        }
+    }
+}
+
+impl From<AddressCommand> for u8 {
+    fn from(value: AddressCommand) -> Self {
+        match value {
+            AddressCommand::None => 0x00,
+            AddressCommand::CancelMerge => 0x01,
+            AddressCommand::LedNormal => 0x02,
+            AddressCommand::LedMute => 0x03,
+            AddressCommand::LedLocate => 0x04,
+            AddressCommand::ResetRxFlags => 0x05,
+            AddressCommand::AnalysisOn => 0x06,
+            AddressCommand::AnalysisOff => 0x07,
+            AddressCommand::FailHold => 0x08,
+            AddressCommand::FailZero => 0x09,
+            AddressCommand::FailFull => 0x0a,
+            AddressCommand::FailScene => 0x0b,
+            AddressCommand::FailRecord => 0x0c,
+            AddressCommand::MergeLtp(port) => 0x10 | (port & 0x03),
+            AddressCommand::DirectionTx(port) => 0x20 | (port & 0x03),
+            AddressCommand::DirectionRx(port) => 0x30 | (port & 0x03),
+            AddressCommand::MergeHtp(port) => 0x50 | (port & 0x03),
+            AddressCommand::ArtNetSel(port) => 0x60 | (port & 0x03),
+            AddressCommand::AcnSel(port) => 0x70 | (port & 0x03),
+            AddressCommand::ClearOp(port) => 0x90 | (port & 0x03),
+            AddressCommand::StyleDelta(port) => 0xa0 | (port & 0x03),
+            AddressCommand::StyleConst(port) => 0xb0 | (port & 0x03),
+            AddressCommand::RdmEnable(port) => 0xc0 | (port & 0x03),
+            AddressCommand::RdmDisable(port) => 0xd0 | (port & 0x03),
+            AddressCommand::Unknown(value) => value,
+        }
+    }
+}
+
+impl<T> Convertable<T> for AddressCommand {
+    fn from_cursor(cursor: &mut Cursor<&[u8]>) -> Result<Self> {
+        let b = cursor.read_u8()?;
+        Ok(AddressCommand::from(b))
+    }
+    fn write_to_buffer(&self, buffer: &mut Vec<u8>, _: &T) -> Result<()> {
+        buffer.push((*self).into());
+        Ok(())
+    }
+    fn serialized_len(&self) -> usize {
+        1
+    }
+    #[cfg(test)]
+    fn get_test_value() -> Self {
+        AddressCommand::LedLocate
+    }
+    #[cfg(test)]
+    fn is_equal(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+/// The type of data that is requested in an ArtDataRequest packet, and returned in an ArtDataReply packet.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DataRequestType {
+    /// Poll whether the node supports ArtDataRequest
+    #[default]
+    Poll,
+    /// The URL of the product page of the node
+    UrlProduct,
+    /// The URL of the user guide of the node
+    UrlUserGuide,
+    /// The URL of the support page of the node
+    UrlSupport,
+    /// The URL of the UDR personality file of the node
+    UrlPersonalityUdr,
+    /// The URL of the GDTF personality file of the node
+    UrlPersonalityGdtf,
+    /// A manufacturer specific request, from 0x8000 to 0xffff
+    Manufacturer(u16),
+    /// A request value that is not defined by the Art-Net specification
+    Unknown(u16),
+}
+
+impl From<u16> for DataRequestType {
+    fn from(value: u16) -> Self {
+        match value {
+            0x0000 => DataRequestType::Poll,
+            0x0001 => DataRequestType::UrlProduct,
+            0x0002 => DataRequestType::UrlUserGuide,
+            0x0003 => DataRequestType::UrlSupport,
+            0x0004 => DataRequestType::UrlPersonalityUdr,
+            0x0005 => DataRequestType::UrlPersonalityGdtf,
+            0x8000..=0xffff => DataRequestType::Manufacturer(value),
+            _ => DataRequestType::Unknown(value),
+        }
+    }
+}
+
+impl From<DataRequestType> for u16 {
+    fn from(value: DataRequestType) -> Self {
+        match value {
+            DataRequestType::Poll => 0x0000,
+            DataRequestType::UrlProduct => 0x0001,
+            DataRequestType::UrlUserGuide => 0x0002,
+            DataRequestType::UrlSupport => 0x0003,
+            DataRequestType::UrlPersonalityUdr => 0x0004,
+            DataRequestType::UrlPersonalityGdtf => 0x0005,
+            DataRequestType::Manufacturer(value) | DataRequestType::Unknown(value) => value,
+        }
+    }
+}
+
+/// The frame rate of an ArtTimeCode packet, as to be used in the `key_type` of the `Timecode` message.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FrameRate {
+    /// 24 frames per second, used for film
+    Film24,
+    /// 25 frames per second, used for EBU / PAL
+    Ebu25,
+    /// 29.97 frames per second with drop-frame counting, used for NTSC
+    DropFrame2997,
+    /// 30 frames per second, used for SMPTE
+    Smpte30,
+}
+
+impl FrameRate {
+    /// The actual number of frames per second
+    pub fn fps(self) -> f64 {
+        match self {
+            FrameRate::Film24 => 24.0,
+            FrameRate::Ebu25 => 25.0,
+            FrameRate::DropFrame2997 => 30_000.0 / 1001.0,
+            FrameRate::Smpte30 => 30.0,
+        }
+    }
+
+    /// The number of frames in every second of the timecode. This is 30 for drop-frame, even though some frame numbers are skipped.
+    pub fn nominal_fps(self) -> u8 {
+        match self {
+            FrameRate::Film24 => 24,
+            FrameRate::Ebu25 => 25,
+            FrameRate::DropFrame2997 | FrameRate::Smpte30 => 30,
+        }
+    }
+
+    /// Whether frames 0 and 1 are skipped at the start of every minute that is not a multiple of 10
+    pub fn is_drop_frame(self) -> bool {
+        self == FrameRate::DropFrame2997
+    }
+}
+
+impl TryFrom<u8> for FrameRate {
+    type Error = Error;
+
+    fn try_from(key_type: u8) -> Result<Self> {
+        match key_type {
+            0 => Ok(FrameRate::Film24),
+            1 => Ok(FrameRate::Ebu25),
+            2 => Ok(FrameRate::DropFrame2997),
+            3 => Ok(FrameRate::Smpte30),
+            value => Err(Error::FieldOutOfRange {
+                field: "key_type",
+                value,
+                max: 3,
+            }),
+        }
+    }
+}
+
+impl From<FrameRate> for u8 {
+    fn from(frame_rate: FrameRate) -> u8 {
+        match frame_rate {
+            FrameRate::Film24 => 0,
+            FrameRate::Ebu25 => 1,
+            FrameRate::DropFrame2997 => 2,
+            FrameRate::Smpte30 => 3,
+        }
+    }
+}
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    /// The flags in the lower bits of `status_1` in the `PollReply` message. See [IndicatorState](enum.IndicatorState.html) and [PortAddressAuthority](enum.PortAddressAuthority.html) for the upper bits.
+    pub struct Status1: u8 {
+        /// The node booted from ROM. If this is not set, it booted the normal firmware from flash.
+        const ROM_BOOT = 0b0000_0100;
+
+        /// The node is capable of Remote Device Management (RDM)
+        const RDM_CAPABLE = 0b0000_0010;
+
+        /// The User Bios Extension Area (UBEA) is present
+        const UBEA_PRESENT = 0b0000_0001;
+    }
+}
+
+/// The state of the front panel indicators of a node, as reported in bits 7-6 of `status_1` in the `PollReply` message
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IndicatorState {
+    /// The indicator state is unknown
+    Unknown,
+    /// The indicators are in locate / identify mode
+    Locate,
+    /// The indicators are muted
+    Mute,
+    /// The indicators are in normal mode
+    Normal,
+}
+
+impl From<u8> for IndicatorState {
+    fn from(status_1: u8) -> Self {
+        match status_1 >> 6 {
+            0b01 => IndicatorState::Locate,
+            0b10 => IndicatorState::Mute,
+            0b11 => IndicatorState::Normal,
+            _ => IndicatorState::Unknown,
+        }
+    }
+}
+
+/// How the Port-Address of a node was set, as reported in bits 5-4 of `status_1` in the `PollReply` message
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PortAddressAuthority {
+    /// The Port-Address programming authority is unknown
+    Unknown,
+    /// All Port-Address parameters are set by the front panel controls
+    FrontPanel,
+    /// All or part of the Port-Address is programmed by the network or web browser
+    Network,
+    /// The value is not used by the protocol
+    NotUsed,
+}
+
+impl From<u8> for PortAddressAuthority {
+    fn from(status_1: u8) -> Self {
+        match (status_1 >> 4) & 0b11 {
+            0b01 => PortAddressAuthority::FrontPanel,
+            0b10 => PortAddressAuthority::Network,
+            0b11 => PortAddressAuthority::NotUsed,
+            _ => PortAddressAuthority::Unknown,
+        }
+    }
+}
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    /// The flags of `status_2` in the `PollReply` message
+    pub struct Status2: u8 {
+        /// The node supports control of RDM using ArtCommand
+        const RDM_ART_COMMAND = 0b1000_0000;
+
+        /// The node supports switching the output style between delta and continuous using ArtCommand
+        const OUTPUT_STYLE_SWITCHING = 0b0100_0000;
+
+        /// The node supports squawking
+        const SQUAWKING = 0b0010_0000;
+
+        /// The node is able to switch between Art-Net and sACN
+        const SACN_SWITCHING = 0b0001_0000;
+
+        /// The node supports 15 bit Port-Addresses, which means it implements Art-Net 3 or 4
+        const PORT_ADDRESS_15_BIT = 0b0000_1000;
+
+        /// The node is capable of DHCP
+        const DHCP_CAPABLE = 0b0000_0100;
+
+        /// The IP address of the node is configured by DHCP
+        const DHCP_CONFIGURED = 0b0000_0010;
+
+        /// The node supports configuration with a web browser
+        const WEB_CONFIGURATION = 0b0000_0001;
+    }
+}
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    /// The status of an input port, as reported in `good_input` in the `PollReply` message
+    pub struct GoodInput: u8 {
+        /// Data is received
+        const DATA_RECEIVED = 0b1000_0000;
+
+        /// The channel includes DMX512 test packets
+        const TEST_PACKETS = 0b0100_0000;
+
+        /// The channel includes DMX512 SIP packets
+        const SIP_PACKETS = 0b0010_0000;
+
+        /// The channel includes DMX512 text packets
+        const TEXT_PACKETS = 0b0001_0000;
+
+        /// The input is disabled
+        const DISABLED = 0b0000_1000;
+
+        /// Receive errors were detected
+        const RECEIVE_ERRORS = 0b0000_0100;
+
+        /// The input is converted to sACN. If this is not set, it is converted to Art-Net.
+        const SACN = 0b0000_0001;
+    }
+}
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    /// The status of an output port, as reported in `good_output` in the `PollReply` message
+    pub struct GoodOutput: u8 {
+        /// ArtDmx or sACN data is being output
+        const DATA_TRANSMITTED = 0b1000_0000;
+
+        /// The channel includes DMX512 test packets
+        const TEST_PACKETS = 0b0100_0000;
+
+        /// The channel includes DMX512 SIP packets
+        const SIP_PACKETS = 0b0010_0000;
+
+        /// The channel includes DMX512 text packets
+        const TEXT_PACKETS = 0b0001_0000;
+
+        /// The output is merging Art-Net data
+        const MERGING = 0b0000_1000;
+
+        /// A short on the DMX output was detected on power up
+        const SHORT_DETECTED = 0b0000_0100;
+
+        /// The merge mode is LTP. If this is not set, it is HTP.
+        const MERGE_LTP = 0b0000_0010;
+
+        /// The output transmits sACN. If this is not set, it transmits Art-Net.
+        const SACN = 0b0000_0001;
+    }
+}
+
+bitflags! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    /// The flags of a `Vlc` payload
+    pub struct VlcFlags: u8 {
+        /// The payload is for IEEE VLC. If this is not set, it uses the Art-Net VLC format.
+        const IEEE = 0b1000_0000;
+
+        /// The packet is a reply to a previous VLC packet with the same transaction number
+        const REPLY = 0b0100_0000;
+
+        /// The payload is transmitted continuously as a beacon, at the rate of `beacon_repeat`
+        const BEACON = 0b0010_0000;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn address_command_conversion() {
+        for value in 0..=255u8 {
+            let command = AddressCommand::from(value);
+            assert_eq!(u8::from(command), value);
+        }
+        assert_eq!(AddressCommand::from(0x02), AddressCommand::LedNormal);
+        assert_eq!(AddressCommand::from(0x52), AddressCommand::MergeHtp(2));
+        assert_eq!(AddressCommand::from(0x0d), AddressCommand::Unknown(0x0d));
+        assert_eq!(u8::from(AddressCommand::RdmDisable(3)), 0xd3);
+    }
+
+    #[test]
+    fn data_request_type_conversion() {
+        for value in [0, 1, 2, 3, 4, 5, 6, 0x7fff, 0x8000, 0xffff] {
+            assert_eq!(u16::from(DataRequestType::from(value)), value);
+        }
+        assert_eq!(DataRequestType::from(3), DataRequestType::UrlSupport);
+        assert_eq!(DataRequestType::from(6), DataRequestType::Unknown(6));
+        assert_eq!(
+            DataRequestType::from(0x8001),
+            DataRequestType::Manufacturer(0x8001)
+        );
+    }
+
+    #[test]
+    fn frame_rate_conversion() {
+        for key_type in 0..4u8 {
+            let frame_rate = FrameRate::try_from(key_type).unwrap();
+            assert_eq!(u8::from(frame_rate), key_type);
+        }
+        assert_eq!(FrameRate::try_from(2).unwrap(), FrameRate::DropFrame2997);
+        assert!((FrameRate::DropFrame2997.fps() - 29.97).abs() < 0.001);
+        assert_eq!(FrameRate::DropFrame2997.nominal_fps(), 30);
+        match FrameRate::try_from(4) {
+            Err(Error::FieldOutOfRange { field, value, max }) => {
+                assert_eq!((field, value, max), ("key_type", 4, 3))
+            }
+            result => panic!("Expected an out of range error, got {:?}", result),
+        }
+        assert!(FrameRate::try_from(255).is_err());
+    }
+
+    #[test]
+    fn talk_to_me_setters() {
+        let mut flags = ArtTalkToMe::NONE;
+        flags.set_emit_changes(true);
+        assert!(flags.emit_changes());
+        assert_eq!(u8::from(flags), 0b0000_0010);
+
+        flags.set_diagnostics_enabled(true);
+        assert!(flags.diagnostics_enabled());
+        assert_eq!(u8::from(flags), 0b0000_0110);
+
+        flags.set_unicast_diagnostics(true);
+        assert!(flags.unicast_diagnostics());
+        assert_eq!(u8::from(flags), 0b0000_1110);
+
+        flags.set_vlc_disabled(true);
+        assert!(flags.vlc_disabled());
+        assert_eq!(u8::from(flags), 0b0001_1110);
+
+        flags.set_targeted_mode(true);
+        assert!(flags.targeted_mode());
+        assert_eq!(u8::from(flags), 0b0011_1110);
+
+        flags.set_emit_changes(false);
+        flags.set_vlc_disabled(false);
+        assert!(!flags.emit_changes());
+        assert!(!flags.vlc_disabled());
+        assert_eq!(u8::from(flags), 0b0010_1100);
+
+        // The deprecated bit 0 and the unused bits are ignored
+        assert_eq!(
+            ArtTalkToMe::from(0b1100_0111),
+            ArtTalkToMe::from(0b0000_0110)
+        );
+    }
+
+    #[test]
+    fn status_1_fields() {
+        assert_eq!(IndicatorState::from(0b0000_0000), IndicatorState::Unknown);
+        assert_eq!(IndicatorState::from(0b0100_0000), IndicatorState::Locate);
+        assert_eq!(IndicatorState::from(0b1011_1111), IndicatorState::Mute);
+        assert_eq!(IndicatorState::from(0b1100_0000), IndicatorState::Normal);
+        assert_eq!(
+            PortAddressAuthority::from(0b1100_1111),
+            PortAddressAuthority::Unknown
+        );
+        assert_eq!(
+            PortAddressAuthority::from(0b0001_0000),
+            PortAddressAuthority::FrontPanel
+        );
+        assert_eq!(
+            PortAddressAuthority::from(0b0010_0000),
+            PortAddressAuthority::Network
+        );
+        assert_eq!(
+            PortAddressAuthority::from(0b0011_0000),
+            PortAddressAuthority::NotUsed
+        );
+    }
+}
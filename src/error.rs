@@ -1,13 +1,64 @@
-use std::ops::Range;
+use crate::PortAddress;
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
 
 /// The result that this crate uses
-pub type Result<T> = std::result::Result<T, Error>;
+pub type Result<T> = core::result::Result<T, Error>;
+
+impl Error {
+    /// Name the field that was being read when the message ended, for errors that come from a cursor
+    pub(crate) fn in_field(self, field: &'static str, message: &'static str) -> Error {
+        match self {
+            Error::CursorEof { offset, .. } => Error::CursorEof {
+                offset,
+                context: field,
+            },
+            e => Error::DeserializeError(message, Box::new(e)),
+        }
+    }
+
+    /// Whether the message ended before a field could be read, possibly wrapped in the error of an opcode
+    pub(crate) fn is_cursor_eof(&self) -> bool {
+        match self {
+            Error::CursorEof { .. } => true,
+            Error::OpcodeError(_, inner) | Error::DeserializeError(_, inner) => {
+                inner.is_cursor_eof()
+            }
+            _ => false,
+        }
+    }
+
+    /// Move the offset of `CursorEof` errors, e.g. to account for the header before the data that was parsed
+    pub(crate) fn offset_by(self, amount: usize) -> Error {
+        match self {
+            Error::CursorEof { offset, context } => Error::CursorEof {
+                offset: offset + amount,
+                context,
+            },
+            Error::OpcodeError(opcode, inner) => {
+                Error::OpcodeError(opcode, Box::new(inner.offset_by(amount)))
+            }
+            Error::DeserializeError(message, inner) => {
+                Error::DeserializeError(message, Box::new(inner.offset_by(amount)))
+            }
+            e => e,
+        }
+    }
+}
 
 /// All the possible errors this crate can encounter
 #[derive(Debug)]
 pub enum Error {
-    /// Could not read or write to the inner curso
-    CursorEof(std::io::Error),
+    /// The message ended before all fields could be read
+    CursorEof {
+        /// The offset in the message where the field that could not be read starts
+        offset: usize,
+
+        /// The field that could not be read, e.g. `Output::port_address`
+        context: &'static str,
+    },
 
     /// Could not serialize an artnet command
     SerializeError(&'static str, Box<Error>),
@@ -29,13 +80,41 @@ pub enum Error {
         /// The message that was being send or received
         message: Vec<u8>,
 
-        /// The size that the artnet protocol expects
+        /// The size that the artnet protocol expects. The end is exclusive, like every `Range`
         allowed_size: Range<usize>,
     },
 
+    /// The buffer ends before the end of the packet, see [ArtCommand::try_from_buffer](enum.ArtCommand.html#method.try_from_buffer)
+    Incomplete {
+        /// The number of bytes that are missing. This is exact for packets with a length field, and a lower bound for other packets.
+        needed: usize,
+    },
+
+    /// The buffer can not be parsed, no matter how many bytes are added, see [ArtCommand::try_from_buffer](enum.ArtCommand.html#method.try_from_buffer)
+    Malformed(Box<Error>),
+
+    /// The buffer that a packet is encoded into is too small
+    BufferTooSmall {
+        /// The number of bytes that the packet needs
+        required: usize,
+
+        /// The length of the buffer
+        available: usize,
+    },
+
     /// The artnet header is invalid
+    #[deprecated(note = "an invalid header is reported as `Error::InvalidHeader`")]
     InvalidArtnetHeader(Vec<u8>),
 
+    /// The message does not start with the Art-Net ID `b"Art-Net\0"`
+    InvalidHeader {
+        /// The first 8 bytes of the message. A message that is shorter is padded with zero bytes.
+        found: [u8; 8],
+    },
+
+    /// The data of an `Output` does not start with the ArtVlc magic number
+    InvalidVlcHeader(Vec<u8>),
+
     /// Could not parse the given opcode
     OpcodeError(&'static str, Box<Error>),
 
@@ -44,12 +123,71 @@ pub enum Error {
 
     /// The Art-Net PortAddress was not from 0 to 32_767
     InvalidPortAddress(i32),
+
+    /// The Net, Sub-Net or Universe of an Art-Net PortAddress was out of range
+    InvalidPortAddressPart {
+        /// The name of the part that was out of range
+        part: &'static str,
+
+        /// The value that was given
+        value: u8,
+
+        /// The maximum value of this part
+        max: u8,
+    },
+
+    /// The string could not be parsed as an Art-Net PortAddress
+    InvalidPortAddressString(String),
+
+    /// The DMX channel was out of range. Channels are indexed from 0 to 511
+    InvalidChannel(usize),
+
+    /// Two packets that should have the same PortAddress had a different one
+    PortAddressMismatch(PortAddress, PortAddress),
+
+    /// The underlying socket returned an error
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
+
+    /// A field of a packet had a value that is not allowed by the protocol
+    FieldOutOfRange {
+        /// The name of the field
+        field: &'static str,
+
+        /// The value that was given
+        value: u8,
+
+        /// The maximum value of this field
+        max: u8,
+    },
+
+    /// The sACN universe was not from 1 to 63999
+    InvalidSacnUniverse(u16),
+
+    /// The command of an `Address` is not defined by the Art-Net specification
+    UnknownAddressCommand(u8),
+
+    /// The sequence of an `Output` was 0x00, which disables reordering, while ordering was requested with `Output::with_sequence`
+    SequenceZero,
+
+    /// The packet has no length field and no fixed size, so its end can not be found in a stream, see [ArtCommand::from_reader](enum.ArtCommand.html#method.from_reader)
+    UnknownPacketLength(u16),
+
+    /// The protocol version of the packet is below 14, and strict parsing was requested
+    UnsupportedVersion {
+        /// The protocol version of the packet
+        got: u16,
+    },
 }
 
-impl std::fmt::Display for Error {
-    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+impl core::fmt::Display for Error {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
         match self {
-            Error::CursorEof(inner) => write!(fmt, "Cursor EOF: {}", inner),
+            Error::CursorEof { offset, context } => write!(
+                fmt,
+                "Cursor EOF while reading {} at offset {}",
+                context, offset
+            ),
             Error::SerializeError(message, inner) => write!(fmt, "{}: {}", message, inner),
             Error::DeserializeError(message, inner) => write!(fmt, "{}: {}", message, inner),
             Error::MessageTooShort { message, min_len } => write!(
@@ -66,9 +204,28 @@ impl std::fmt::Display for Error {
                 "Message size invalid, it was {} but artnet expects between {} and {}",
                 message.len(),
                 allowed_size.start,
-                allowed_size.end
+                allowed_size.end.saturating_sub(1)
+            ),
+            Error::Incomplete { needed } => {
+                write!(fmt, "Incomplete packet, at least {} bytes are missing", needed)
+            }
+            Error::Malformed(inner) => write!(fmt, "Malformed packet: {}", inner),
+            Error::BufferTooSmall {
+                required,
+                available,
+            } => write!(
+                fmt,
+                "Buffer too small, the packet needs {} bytes but the buffer has {}",
+                required, available
             ),
+            #[allow(deprecated)]
             Error::InvalidArtnetHeader(_) => write!(fmt, "Invalid artnet header"),
+            Error::InvalidHeader { found } => write!(
+                fmt,
+                "Invalid Art-Net header, expected \"Art-Net\\0\" but found {:?}",
+                found
+            ),
+            Error::InvalidVlcHeader(_) => write!(fmt, "Invalid ArtVlc header"),
             Error::OpcodeError(opcode, inner) => {
                 write!(fmt, "Could not parse opcode {:?}: {}", opcode, inner)
             }
@@ -78,8 +235,162 @@ impl std::fmt::Display for Error {
                 "Art-Net PortAddress must be from 0 to 32_767. Got {:?}",
                 wrong_number
             ),
+            Error::InvalidPortAddressPart { part, value, max } => write!(
+                fmt,
+                "Art-Net PortAddress {} must be from 0 to {}. Got {}",
+                part, max, value
+            ),
+            Error::InvalidPortAddressString(input) => write!(
+                fmt,
+                "Could not parse {:?} as an Art-Net PortAddress, expected \"net:sub-net:universe\" or a number",
+                input
+            ),
+            Error::InvalidChannel(channel) => write!(
+                fmt,
+                "DMX channel must be from 0 to 511. Got {}",
+                channel
+            ),
+            Error::PortAddressMismatch(left, right) => write!(
+                fmt,
+                "Art-Net PortAddress {} does not match {}",
+                left, right
+            ),
+            #[cfg(feature = "std")]
+            Error::Io(inner) => write!(fmt, "IO error: {}", inner),
+            Error::FieldOutOfRange { field, value, max } => write!(
+                fmt,
+                "{} must be from 0 to {}. Got {}",
+                field, max, value
+            ),
+            Error::SequenceZero => write!(
+                fmt,
+                "Sequence 0x00 disables reordering, use a sequence from 0x01 to 0xff or set the field directly to disable it"
+            ),
+            Error::UnknownAddressCommand(command) => {
+                write!(fmt, "Unknown ArtAddress command 0x{:02x}", command)
+            }
+            Error::InvalidSacnUniverse(universe) => write!(
+                fmt,
+                "sACN universe must be from 1 to 63999. Got {}",
+                universe
+            ),
+            Error::UnknownPacketLength(opcode) => write!(
+                fmt,
+                "Can not find the end of a packet with opcode 0x{:X}, it has no length field and no fixed size",
+                opcode
+            ),
+            Error::UnsupportedVersion { got } => write!(
+                fmt,
+                "Art-Net protocol version {} is not supported, expected at least 14",
+                got
+            ),
         }
     }
 }
 
-impl std::error::Error for Error {}
+#[cfg(feature = "std")]
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::SerializeError(_, inner)
+            | Error::DeserializeError(_, inner)
+            | Error::OpcodeError(_, inner)
+            | Error::Malformed(inner) => Some(inner.as_ref()),
+            Error::Io(inner) => Some(inner),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use std::error::Error as _;
+
+    fn every_variant() -> Vec<Error> {
+        vec![
+            Error::CursorEof {
+                offset: 12,
+                context: "Output::port_address",
+            },
+            Error::SerializeError("Could not serialize", Box::new(Error::UnknownOpcode(1))),
+            Error::DeserializeError("Could not deserialize", Box::new(Error::UnknownOpcode(1))),
+            Error::MessageTooShort {
+                message: vec![1, 2],
+                min_len: 14,
+            },
+            Error::MessageSizeInvalid {
+                message: vec![0; 513],
+                allowed_size: 0..513,
+            },
+            #[allow(deprecated)]
+            Error::InvalidArtnetHeader(vec![1, 2, 3]),
+            Error::InvalidHeader {
+                found: *b"GET / HT",
+            },
+            Error::OpcodeError("Output", Box::new(Error::UnknownOpcode(1))),
+            Error::UnknownOpcode(0x1234),
+            Error::InvalidPortAddress(-1),
+            Error::InvalidPortAddressPart {
+                part: "net",
+                value: 200,
+                max: 127,
+            },
+            Error::InvalidPortAddressString("a:b:c".into()),
+            Error::InvalidChannel(512),
+            Error::PortAddressMismatch(1.into(), 2.into()),
+            Error::Io(std::io::Error::other("socket closed")),
+            Error::FieldOutOfRange {
+                field: "physical",
+                value: 5,
+                max: 3,
+            },
+            Error::InvalidSacnUniverse(0),
+            Error::InvalidVlcHeader(vec![1, 2, 3]),
+            Error::BufferTooSmall {
+                required: 530,
+                available: 20,
+            },
+            Error::Incomplete { needed: 4 },
+            Error::Malformed(Box::new(Error::UnknownOpcode(2))),
+            Error::SequenceZero,
+            Error::UnknownAddressCommand(0x42),
+            Error::UnknownPacketLength(0xf000),
+            Error::UnsupportedVersion { got: 13 },
+        ]
+    }
+
+    #[test]
+    fn every_variant_has_a_distinct_message() {
+        let messages: Vec<String> = every_variant().iter().map(ToString::to_string).collect();
+        for (index, message) in messages.iter().enumerate() {
+            assert!(!message.is_empty());
+            assert!(
+                !messages[..index].contains(message),
+                "Duplicate message {:?}",
+                message
+            );
+        }
+    }
+
+    #[test]
+    fn source_chains_the_inner_error() {
+        let io = Error::Io(std::io::Error::other("socket closed"));
+        assert_eq!(io.source().unwrap().to_string(), "socket closed");
+
+        let opcode = Error::OpcodeError("Output", Box::new(Error::InvalidChannel(512)));
+        assert_eq!(
+            opcode.source().unwrap().to_string(),
+            Error::InvalidChannel(512).to_string()
+        );
+        assert!(Error::UnknownOpcode(1).source().is_none());
+
+        // Usable with `?` in a function that returns a boxed error
+        fn parse() -> core::result::Result<(), Box<dyn std::error::Error>> {
+            Err(Error::UnknownOpcode(0x1234))?;
+            Ok(())
+        }
+        assert_eq!(parse().unwrap_err().to_string(), "Unknown opcode 0x1234");
+    }
+}
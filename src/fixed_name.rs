@@ -0,0 +1,217 @@
+use crate::convert::Cursor;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::Utf8Error;
+
+use crate::convert::{null_terminated_str, Convertable};
+use crate::{Error, Result};
+
+/// A null terminated name stored in a fixed length field of `N` bytes, like the short and long name of a node.
+///
+/// The name can be at most `N - 1` bytes long, so that there is always room for the terminating null byte.
+///
+/// ```
+/// use artnet_protocol::FixedName;
+/// let name = FixedName::<18>::new("Node 1").unwrap();
+/// assert_eq!(name.as_str(), Ok("Node 1"));
+/// assert!(FixedName::<4>::new("Node").is_err());
+/// assert_eq!(FixedName::<4>::new_truncated("Node").as_str(), Ok("Nod"));
+/// ```
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+pub struct FixedName<const N: usize>([u8; N]);
+
+impl<const N: usize> FixedName<N> {
+    /// Create a name from a string. Returns an error if the name does not fit in `N - 1` bytes.
+    pub fn new(name: &str) -> Result<Self> {
+        if name.len() >= N {
+            return Err(Error::MessageSizeInvalid {
+                message: name.as_bytes().to_vec(),
+                allowed_size: 0..N,
+            });
+        }
+        Ok(Self::new_truncated(name))
+    }
+
+    /// Create a name from a string, cutting it off at `N - 1` bytes if it is too long.
+    ///
+    /// The name is only cut at a character boundary, so the result is always valid UTF8.
+    pub fn new_truncated(name: &str) -> Self {
+        let mut end = name.len().min(N.saturating_sub(1));
+        while !name.is_char_boundary(end) {
+            end -= 1;
+        }
+        let mut bytes = [0; N];
+        bytes[..end].copy_from_slice(&name.as_bytes()[..end]);
+        FixedName(bytes)
+    }
+
+    /// The name up to the first null byte
+    pub fn as_str(&self) -> core::result::Result<&str, Utf8Error> {
+        null_terminated_str(&self.0)
+    }
+
+    /// The raw bytes of the field, including the null padding
+    pub fn as_bytes(&self) -> &[u8; N] {
+        &self.0
+    }
+}
+
+impl<const N: usize> Default for FixedName<N> {
+    fn default() -> Self {
+        FixedName([0; N])
+    }
+}
+
+impl<const N: usize> From<[u8; N]> for FixedName<N> {
+    fn from(bytes: [u8; N]) -> Self {
+        FixedName(bytes)
+    }
+}
+
+impl<const N: usize> From<FixedName<N>> for [u8; N] {
+    fn from(name: FixedName<N>) -> Self {
+        name.0
+    }
+}
+
+impl<const N: usize> fmt::Debug for FixedName<N> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.as_str() {
+            Ok(name) => fmt::Debug::fmt(name, fmt),
+            Err(e) => write!(fmt, "Invalid UTF8: {:?}", e),
+        }
+    }
+}
+
+impl<T, const N: usize> Convertable<T> for FixedName<N> {
+    fn from_cursor(cursor: &mut Cursor<&[u8]>) -> Result<Self> {
+        let mut bytes = [0; N];
+        cursor.read_exact(&mut bytes)?;
+        Ok(FixedName(bytes))
+    }
+
+    fn write_to_buffer(&self, buffer: &mut Vec<u8>, _: &T) -> Result<()> {
+        buffer.extend_from_slice(&self.0);
+        Ok(())
+    }
+
+    fn serialized_len(&self) -> usize {
+        N
+    }
+    #[cfg(test)]
+    fn get_test_value() -> Self {
+        FixedName::new_truncated("test")
+    }
+    #[cfg(test)]
+    fn is_equal(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+/// Names are serialized as a string if that is lossless, otherwise as the raw bytes of the field
+#[cfg(feature = "serde")]
+impl<const N: usize> serde::Serialize for FixedName<N> {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> core::result::Result<S::Ok, S::Error> {
+        match self.as_str() {
+            Ok(name) if self.0[name.len()..].iter().all(|&b| b == 0) => {
+                serializer.serialize_str(name)
+            }
+            _ => serializer.serialize_bytes(&self.0),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> serde::Deserialize<'de> for FixedName<N> {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> core::result::Result<Self, D::Error> {
+        use serde::de::{self, SeqAccess, Visitor};
+
+        struct NameVisitor<const N: usize>;
+
+        impl<'de, const N: usize> Visitor<'de> for NameVisitor<N> {
+            type Value = FixedName<N>;
+
+            fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+                write!(
+                    fmt,
+                    "a string of at most {} bytes, or an array of {} bytes",
+                    N - 1,
+                    N
+                )
+            }
+
+            fn visit_str<E: de::Error>(self, name: &str) -> core::result::Result<Self::Value, E> {
+                FixedName::new(name).map_err(E::custom)
+            }
+
+            fn visit_bytes<E: de::Error>(
+                self,
+                bytes: &[u8],
+            ) -> core::result::Result<Self::Value, E> {
+                crate::serde_array::ByteArrayVisitor::<N>
+                    .visit_bytes(bytes)
+                    .map(FixedName)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(
+                self,
+                seq: A,
+            ) -> core::result::Result<Self::Value, A::Error> {
+                crate::serde_array::ByteArrayVisitor::<N>
+                    .visit_seq(seq)
+                    .map(FixedName)
+            }
+        }
+
+        deserializer.deserialize_any(NameVisitor::<N>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_zero_padded() {
+        let name = FixedName::<8>::new("abc").unwrap();
+        let mut buffer = Vec::new();
+        Convertable::<()>::write_to_buffer(&name, &mut buffer, &()).unwrap();
+        assert_eq!(buffer, b"abc\0\0\0\0\0");
+        assert_eq!(name.as_str(), Ok("abc"));
+    }
+
+    #[test]
+    fn too_long_names() {
+        assert!(FixedName::<8>::new("1234567").is_ok());
+        match FixedName::<8>::new("12345678") {
+            Err(Error::MessageSizeInvalid { allowed_size, .. }) => assert_eq!(allowed_size, 0..8),
+            result => panic!("Expected a size error, got {:?}", result),
+        }
+
+        assert_eq!(
+            FixedName::<8>::new_truncated("123456789").as_str(),
+            Ok("1234567")
+        );
+        // "é" is two bytes long and should not be split in half
+        assert_eq!(
+            FixedName::<8>::new_truncated("123456é").as_str(),
+            Ok("123456")
+        );
+    }
+
+    #[test]
+    fn exactly_n_length_field() {
+        // A node may fill the whole field without a null terminator
+        let name = FixedName::from([b'a'; 8]);
+        assert_eq!(name.as_str(), Ok("aaaaaaaa"));
+
+        let mut cursor = Cursor::new(&b"abcdefgh"[..]);
+        let parsed: FixedName<8> = Convertable::<()>::from_cursor(&mut cursor).unwrap();
+        assert_eq!(parsed.as_bytes(), b"abcdefgh");
+    }
+}
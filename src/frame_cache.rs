@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::{Clock, Output, PortAddress, SystemClock};
+
+/// How often a frame is sent again when its data does not change, see [FrameCache::set_keepalive](struct.FrameCache.html#method.set_keepalive)
+const DEFAULT_KEEPALIVE: Duration = Duration::from_secs(4);
+
+/// Remembers the last frame that was sent for each Port-Address, so a sender only transmits an `Output` when its data changes, like nodes do.
+///
+/// Unchanged frames are still sent once per keepalive interval, 4 seconds by default, so receivers know that the sender is still there.
+///
+/// ```
+/// use artnet_protocol::{FrameCache, Output};
+///
+/// let mut cache = FrameCache::new();
+/// let output = Output::new(1, vec![255, 0, 0]);
+/// assert!(cache.should_send(&output));
+/// assert!(!cache.should_send(&output));
+/// assert!(cache.should_send(&Output::new(1, vec![0, 255, 0])));
+/// ```
+#[derive(Debug)]
+pub struct FrameCache<C: Clock = SystemClock> {
+    clock: C,
+    keepalive: Duration,
+    frames: HashMap<PortAddress, Frame>,
+}
+
+#[derive(Debug)]
+struct Frame {
+    data: Vec<u8>,
+    sent_at: Duration,
+}
+
+impl FrameCache<SystemClock> {
+    /// Create an empty cache that uses the system time
+    pub fn new() -> Self {
+        FrameCache::with_clock(SystemClock::default())
+    }
+}
+
+impl Default for FrameCache<SystemClock> {
+    fn default() -> Self {
+        FrameCache::new()
+    }
+}
+
+impl<C: Clock> FrameCache<C> {
+    /// Create an empty cache, using the given clock for the keepalive interval
+    pub fn with_clock(clock: C) -> Self {
+        FrameCache {
+            clock,
+            keepalive: DEFAULT_KEEPALIVE,
+            frames: HashMap::new(),
+        }
+    }
+
+    /// Change how often a frame is sent again when its data does not change
+    pub fn set_keepalive(&mut self, keepalive: Duration) {
+        self.keepalive = keepalive;
+    }
+
+    /// Whether the packet should be sent, because its data differs from the last frame for its Port-Address, or because the keepalive interval has passed.
+    ///
+    /// If this returns `true`, the packet is remembered as the last frame that was sent.
+    pub fn should_send(&mut self, output: &Output) -> bool {
+        let now = self.clock.now();
+        if let Some(frame) = self.frames.get_mut(&output.port_address) {
+            if frame.data[..] == output.data[..] && now - frame.sent_at < self.keepalive {
+                return false;
+            }
+            frame.data.clear();
+            frame.data.extend_from_slice(&output.data);
+            frame.sent_at = now;
+        } else {
+            self.frames.insert(
+                output.port_address,
+                Frame {
+                    data: output.data.to_vec(),
+                    sent_at: now,
+                },
+            );
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rate_limit::MockClock;
+
+    #[test]
+    fn suppresses_identical_frames() {
+        let mut cache = FrameCache::with_clock(MockClock::default());
+        assert!(cache.should_send(&Output::new(1, vec![1, 2])));
+        assert!(!cache.should_send(&Output::new(1, vec![1, 2])));
+        // Other Port-Addresses have their own frame
+        assert!(cache.should_send(&Output::new(2, vec![1, 2])));
+        assert!(cache.should_send(&Output::new(1, vec![1, 3])));
+        assert!(!cache.should_send(&Output::new(1, vec![1, 3])));
+    }
+
+    #[test]
+    fn resends_after_keepalive() {
+        let clock = MockClock::default();
+        let mut cache = FrameCache::with_clock(clock.clone());
+        cache.set_keepalive(Duration::from_secs(1));
+        let output = Output::new(1, vec![1, 2]);
+        assert!(cache.should_send(&output));
+
+        clock.set(Duration::from_millis(999));
+        assert!(!cache.should_send(&output));
+        clock.set(Duration::from_millis(1000));
+        assert!(cache.should_send(&output));
+        clock.set(Duration::from_millis(1500));
+        assert!(!cache.should_send(&output));
+    }
+}
@@ -0,0 +1,3 @@
+//! Helpers to bridge Art-Net with other lighting control protocols.
+
+pub mod sacn;
@@ -0,0 +1,98 @@
+//! Conversion between sACN (ANSI E1.31) and Art-Net.
+//!
+//! sACN universes are numbered from 1 to 63999. They are mapped to the Art-Net Port-Address with the same number,
+//! so only sACN universes up to 32767 can be bridged.
+
+use crate::{Error, Output, PaddedData, PortAddress, Result};
+use core::convert::TryFrom;
+use core::net::Ipv4Addr;
+use core::ops::RangeInclusive;
+
+/// The universes that are valid in sACN
+pub const SACN_UNIVERSES: RangeInclusive<u16> = 1..=63999;
+
+/// The Art-Net Port-Address for the given sACN universe.
+///
+/// Returns an error if the universe is not a valid sACN universe, or if it is too high for the 15 bit Port-Address.
+pub fn port_address(universe: u16) -> Result<PortAddress> {
+    if !SACN_UNIVERSES.contains(&universe) {
+        return Err(Error::InvalidSacnUniverse(universe));
+    }
+    PortAddress::try_from(universe)
+}
+
+/// The multicast address that sACN data for the given universe is sent to, which is `239.255.{high byte}.{low byte}`.
+///
+/// ```
+/// use artnet_protocol::interop::sacn::multicast_address;
+/// use std::net::Ipv4Addr;
+/// assert_eq!(multicast_address(256).unwrap(), Ipv4Addr::new(239, 255, 1, 0));
+/// ```
+///
+/// Returns an error if the universe is not a valid sACN universe.
+pub fn multicast_address(universe: u16) -> Result<Ipv4Addr> {
+    if !SACN_UNIVERSES.contains(&universe) {
+        return Err(Error::InvalidSacnUniverse(universe));
+    }
+    let [high, low] = universe.to_be_bytes();
+    Ok(Ipv4Addr::new(239, 255, high, low))
+}
+
+impl Output {
+    /// Create an `Output` from the DMX512 data of an sACN packet, see the [sacn](interop/sacn/index.html) module for the universe mapping.
+    ///
+    /// Returns an error if the universe can not be mapped to a Port-Address, or if there are more than 512 channels.
+    pub fn from_sacn(universe: u16, dmx: &[u8], sequence: u8) -> Result<Output> {
+        let mut output = Output::new(port_address(universe)?, PaddedData::try_new(dmx.to_vec())?);
+        output.sequence = sequence;
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn universe_1_is_port_address_1() {
+        let output = Output::from_sacn(1, &[10, 20, 30], 7).unwrap();
+        assert_eq!(output.port_address, PortAddress::from(1));
+        assert_eq!(output.sequence, 7);
+        assert_eq!(output.data.as_slice(), &[10, 20, 30]);
+    }
+
+    #[test]
+    fn universe_range() {
+        assert_eq!(port_address(32_767).unwrap(), PortAddress::MAX);
+        assert!(matches!(
+            port_address(0),
+            Err(Error::InvalidSacnUniverse(0))
+        ));
+        assert!(matches!(
+            port_address(64_000),
+            Err(Error::InvalidSacnUniverse(64_000))
+        ));
+        assert!(matches!(
+            port_address(32_768),
+            Err(Error::InvalidPortAddress(32_768))
+        ));
+        assert!(Output::from_sacn(1, &[0; 513], 0).is_err());
+    }
+
+    #[test]
+    fn multicast_addresses() {
+        assert_eq!(multicast_address(1).unwrap(), Ipv4Addr::new(239, 255, 0, 1));
+        assert_eq!(
+            multicast_address(256).unwrap(),
+            Ipv4Addr::new(239, 255, 1, 0)
+        );
+        assert_eq!(
+            multicast_address(63_999).unwrap(),
+            Ipv4Addr::new(239, 255, 249, 255)
+        );
+        assert!(matches!(
+            multicast_address(0),
+            Err(Error::InvalidSacnUniverse(0))
+        ));
+    }
+}
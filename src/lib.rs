@@ -4,8 +4,8 @@
 //! use artnet_protocol::*;
 //! use std::net::{UdpSocket, ToSocketAddrs};
 //!
-//! let socket = UdpSocket::bind(("0.0.0.0", 6454)).unwrap();
-//! let broadcast_addr = ("255.255.255.255", 6454).to_socket_addrs().unwrap().next().unwrap();
+//! let socket = UdpSocket::bind(("0.0.0.0", ARTNET_PORT)).unwrap();
+//! let broadcast_addr = ("255.255.255.255", ARTNET_PORT).to_socket_addrs().unwrap().next().unwrap();
 //! socket.set_broadcast(true).unwrap();
 //! let buff = ArtCommand::Poll(Poll::default()).write_to_buffer().unwrap();
 //! socket.send_to(&buff, &broadcast_addr).unwrap();
@@ -22,10 +22,8 @@
 //!         },
 //!         ArtCommand::PollReply(reply) => {
 //!             // This is an ArtNet node on the network. We can send commands to it like this:
-//!             let command = ArtCommand::Output(Output {
-//!                 data: vec![1, 2, 3, 4, 5].into(), // The data we're sending to the node
-//!                 ..Output::default()
-//!             });
+//!             // Send the data to port address 1 of the node
+//!             let command = ArtCommand::Output(Output::new(1, vec![1, 2, 3, 4, 5]));
 //!             let bytes = command.write_to_buffer().unwrap();
 //!             socket.send_to(&bytes, &addr).unwrap();
 //!         },
@@ -34,6 +32,9 @@
 //! }
 //! ```
 #![deny(missing_docs)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
 
 /// Re-export of the bitflags crate that this library uses
 #[macro_use]
@@ -43,13 +44,48 @@ pub extern crate byteorder;
 
 #[macro_use]
 mod macros;
+mod big_endian;
 mod command;
+#[cfg(feature = "std")]
+mod connection;
 mod convert;
 mod enums;
 mod error;
+mod fixed_name;
+#[cfg(feature = "std")]
+mod frame_cache;
+pub mod interop;
+mod oem;
 mod port_address;
+pub mod prelude;
+mod protocol_version;
+#[cfg(feature = "std")]
+mod rate_limit;
+mod reorder;
+mod sequence;
+#[cfg(feature = "serde")]
+mod serde_array;
+#[cfg(feature = "tokio")]
+mod socket;
 
+pub use crate::big_endian::BigEndianU16;
 pub use crate::command::*;
-pub use crate::enums::ArtTalkToMe;
+#[cfg(feature = "std")]
+pub use crate::connection::{discover, Connection};
+pub use crate::enums::{
+    AddressCommand, ArtTalkToMe, DataRequestType, FrameRate, GoodInput, GoodOutput, IndicatorState,
+    IpProgCommand, PortAddressAuthority, Status1, Status2, VlcFlags,
+};
 pub use crate::error::*;
+#[cfg(feature = "std")]
+pub use crate::rate_limit::{Clock, RateLimitedSender, SystemClock, DEFAULT_MAX_RATE};
+pub use fixed_name::FixedName;
+#[cfg(feature = "std")]
+pub use frame_cache::FrameCache;
+pub use oem::Oem;
 pub use port_address::PortAddress;
+pub use protocol_version::{version_bytes, ProtocolVersion};
+pub use reorder::ReorderBuffer;
+pub use sequence::SequenceCounter;
+#[cfg(feature = "tokio")]
+pub use socket::ArtNetSocket;
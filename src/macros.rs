@@ -1,48 +1,104 @@
+/// Defines a packet struct, and generates the code to serialize and parse it field by field with `Convertable`.
+///
+/// The last field can be marked with `#[remainder]` (before its other attributes). It takes up the rest of the buffer when parsing,
+/// and can be of any type that implements `From<&[u8]>` and `AsRef<[u8]>`, e.g. `Vec<u8>`.
+/// Every other field keeps the visibility it is declared with, so a field without `pub` can only be read through a method.
+///
+/// The generated round trip test is always called `test_encode_decode`, so two structs in the same module would clash.
+/// Start the invocation with `mod name;` to define the struct in a module of that name, which is re-exported. Macros that define several packets, like `opaque_packet!`, use this.
 macro_rules! data_structure {
+    // A `#[remainder]` field, which must be the last field
     (
-        $(#[$outer:meta])*
-        pub struct $name:ident {
-            $(
-                $(#[$field_meta:meta])*
-                pub $field:ident : $ty:ty,
-            )*
-        }
+        @munch [$($outer:tt)*] $name:ident [$($fields:tt)*]
+        #[remainder]
+        $(#[$rem_meta:meta])*
+        pub $rem:ident : $rem_ty:ty $(,)?
+    ) => {
+        data_structure!(@emit [$($outer)*] $name [$($fields)*] [[$(#[$rem_meta])*] $rem : $rem_ty]);
+    };
+    (
+        @munch [$($outer:tt)*] $name:ident [$($fields:tt)*]
+        $(#[$field_meta:meta])*
+        $field_vis:vis $field:ident : $ty:ty,
+        $($rest:tt)*
+    ) => {
+        data_structure!(@munch [$($outer)*] $name [$($fields)* [$(#[$field_meta])*] $field_vis $field : $ty;] $($rest)*);
+    };
+    (@munch [$($outer:tt)*] $name:ident [$($fields:tt)*]) => {
+        data_structure!(@emit [$($outer)*] $name [$($fields)*] []);
+    };
+    (
+        @emit [$(#[$outer:meta])*] $name:ident
+        [$([$(#[$field_meta:meta])*] $field_vis:vis $field:ident : $ty:ty;)*]
+        [$([$(#[$rem_meta:meta])*] $rem:ident : $rem_ty:ty)?]
     ) => {
         $(#[$outer])*
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
         pub struct $name {
             $(
                 $(#[$field_meta])*
-                pub $field: $ty,
+                $field_vis $field: $ty,
             )*
+            $(
+                $(#[$rem_meta])*
+                pub $rem: $rem_ty,
+            )?
         }
 
         impl $name {
             /// Convert this struct to a byte array.
-            pub fn to_bytes(&self) -> crate::Result<Vec<u8>> {
+            pub fn to_bytes(&self) -> crate::Result<alloc::vec::Vec<u8>> {
+                let mut result = alloc::vec::Vec::new();
+                self.write_bytes(&mut result)?;
+                Ok(result)
+            }
+
+            /// The number of bytes that `to_bytes` will return.
+            pub fn serialized_len(&self) -> usize {
+                use crate::convert::Convertable;
+
+                0 $(+ Convertable::<$name>::serialized_len(&self.$field))*
+                    $(+ AsRef::<[u8]>::as_ref(&self.$rem).len())?
+            }
+
+            /// Append this struct to the end of the buffer.
+            pub(crate) fn write_bytes(&self, buffer: &mut alloc::vec::Vec<u8>) -> crate::Result<()> {
                 use crate::convert::Convertable;
                 use crate::Error;
 
-                let mut result = Vec::new();
                 $(
-                    self.$field.write_to_buffer(&mut result, &self)
-                        .map_err(|e| Error::SerializeError(concat!("Could not serialize field ", stringify!($name), "::", stringify!($field)), Box::new(e)))?;
+                    self.$field.write_to_buffer(buffer, &self)
+                        .map_err(|e| Error::SerializeError(concat!("Could not serialize field ", stringify!($name), "::", stringify!($field)), alloc::boxed::Box::new(e)))?;
                 )*
-                Ok(result)
+                $(
+                    buffer.extend_from_slice(AsRef::<[u8]>::as_ref(&self.$rem));
+                )?
+                Ok(())
             }
 
             /// Convert a byte array to an instance of this struct.
             pub fn from(data: &[u8]) -> crate::Result<$name> {
+                $name::from_with_position(data).map(|(value, _)| value)
+            }
+
+            /// Convert a byte array to an instance of this struct, and return the number of bytes that were read.
+            pub(crate) fn from_with_position(data: &[u8]) -> crate::Result<($name, usize)> {
                 use crate::convert::Convertable;
-                use crate::Error;
 
-                let mut cursor = ::std::io::Cursor::new(data);
+                let mut cursor = crate::convert::Cursor::new(data);
                 $(
                     let $field: $ty = Convertable::<$name>::from_cursor(&mut cursor)
-                        .map_err(|e| Error::DeserializeError(concat!("Could not deserialize field ", stringify!($name), "::", stringify!($field)), Box::new(e)))?;
+                        .map_err(|e| e.in_field(concat!(stringify!($name), "::", stringify!($field)), concat!("Could not deserialize field ", stringify!($name), "::", stringify!($field))))?;
                 )*
-                Ok($name {
+                $(
+                    let $rem: $rem_ty = cursor.read_to_end()
+                        .map(<$rem_ty>::from)
+                        .map_err(|e| e.in_field(concat!(stringify!($name), "::", stringify!($rem)), concat!("Could not deserialize field ", stringify!($name), "::", stringify!($rem))))?;
+                )?
+                Ok(($name {
                     $($field, )*
-                })
+                    $($rem, )?
+                }, cursor.position() as usize))
             }
         }
 
@@ -53,12 +109,78 @@ macro_rules! data_structure {
                 $(
                     $field: crate::convert::Convertable::<$name>::get_test_value(),
                 )*
+                $(
+                    $rem: <$rem_ty>::from(&[1, 2, 3, 4][..]),
+                )?
             };
             let bytes = start.to_bytes().expect("Could not serialize");
+            assert_eq!(start.serialized_len(), bytes.len());
             let end = $name::from(&bytes).expect("Could not deserialize");
             $(
                 assert!(crate::convert::Convertable::<$name>::is_equal(&start.$field, &end.$field));
             )*
+            $(
+                assert_eq!(AsRef::<[u8]>::as_ref(&start.$rem), AsRef::<[u8]>::as_ref(&end.$rem));
+            )?
         }
     };
+    (
+        mod $module:ident;
+        $(#[$outer:meta])*
+        pub struct $name:ident {
+            $($body:tt)*
+        }
+    ) => {
+        mod $module {
+            // The types of the fields, unless they are all primitive types
+            #[allow(unused_imports)]
+            use super::*;
+
+            data_structure! {
+                $(#[$outer])*
+                pub struct $name {
+                    $($body)*
+                }
+            }
+        }
+
+        pub use self::$module::$name;
+    };
+    (
+        $(#[$outer:meta])*
+        pub struct $name:ident {
+            $($body:tt)*
+        }
+    ) => {
+        data_structure!(@munch [$(#[$outer])*] $name [] $($body)*);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    data_structure! {
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        #[doc = "A toy packet with a fixed field and a remainder"]
+        pub struct Toy {
+            #[doc = "A fixed field"]
+            pub kind: u8,
+            #[remainder]
+            #[doc = "The rest of the packet"]
+            pub rest: Vec<u8>,
+        }
+    }
+
+    #[test]
+    fn remainder_takes_the_rest() {
+        let toy = Toy::from(&[7, 1, 2, 3]).unwrap();
+        assert_eq!(toy.kind, 7);
+        assert_eq!(toy.rest, [1, 2, 3]);
+        assert_eq!(toy.serialized_len(), 4);
+        assert_eq!(toy.to_bytes().unwrap(), [7, 1, 2, 3]);
+
+        assert_eq!(Toy::from(&[7]).unwrap().rest, []);
+        assert!(Toy::from(&[]).is_err());
+    }
 }
@@ -0,0 +1,73 @@
+/// The Oem code of a product, which identifies its manufacturer and product type. Codes are assigned by Artistic Licence.
+///
+/// Bit 15 is set if the product supports the extended features of Art-Net. The default is [Oem::UNKNOWN](#associatedconstant.UNKNOWN), for products that do not have a code.
+///
+/// ```
+/// use artnet_protocol::{Oem, PollReply};
+/// use core::net::Ipv4Addr;
+///
+/// let reply = PollReply::new(Ipv4Addr::new(2, 0, 0, 10), Oem(0x1234));
+/// assert_eq!(reply.oem, [0x12, 0x34]);
+/// assert_eq!(reply.oem_code(), Oem(0x1234));
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Oem(pub u16);
+
+impl Oem {
+    /// OemDMXHub, the Artistic Licence DMX-Hub
+    pub const DMX_HUB: Oem = Oem(0x0000);
+
+    /// OemUnknown, for products that have not been assigned a code
+    pub const UNKNOWN: Oem = Oem(0x00ff);
+
+    /// Whether bit 15 is set, which means that the product supports the extended features of Art-Net
+    pub fn has_extended_features(self) -> bool {
+        self.0 & 0x8000 != 0
+    }
+}
+
+impl Default for Oem {
+    fn default() -> Oem {
+        Oem::UNKNOWN
+    }
+}
+
+impl From<u16> for Oem {
+    fn from(code: u16) -> Oem {
+        Oem(code)
+    }
+}
+
+impl From<Oem> for u16 {
+    fn from(oem: Oem) -> u16 {
+        oem.0
+    }
+}
+
+/// The `Oem` as it is sent in packets, big endian
+impl From<Oem> for [u8; 2] {
+    fn from(oem: Oem) -> [u8; 2] {
+        oem.0.to_be_bytes()
+    }
+}
+
+impl From<[u8; 2]> for Oem {
+    fn from(bytes: [u8; 2]) -> Oem {
+        Oem(u16::from_be_bytes(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oem_conversions() {
+        assert_eq!(Oem::default(), Oem::UNKNOWN);
+        assert_eq!(<[u8; 2]>::from(Oem(0x8123)), [0x81, 0x23]);
+        assert_eq!(Oem::from([0x81, 0x23]), Oem::from(0x8123));
+        assert!(Oem(0x8123).has_extended_features());
+        assert!(!Oem::UNKNOWN.has_extended_features());
+    }
+}
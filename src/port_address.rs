@@ -1,7 +1,11 @@
-use std::convert::TryFrom;
-use std::io::Cursor;
+use crate::convert::Cursor;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+use core::str::FromStr;
 
-use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use byteorder::LittleEndian;
 
 use crate::{convert::Convertable, Error, Result};
 
@@ -11,7 +15,7 @@ use crate::{convert::Convertable, Error, Result};
 ///
 /// ```
 /// use artnet_protocol::PortAddress;
-/// use std::convert::TryInto;
+/// use core::convert::TryInto;
 /// let a: PortAddress = 1.into(); //convert from u8 never fails
 /// let b: PortAddress = 2u16.try_into().unwrap(); //u16 could fail if too big
 /// let c: PortAddress = 3_000.try_into().unwrap(); //i32 could fail if too big or negative
@@ -19,8 +23,106 @@ use crate::{convert::Convertable, Error, Result};
 /// let better_not = PortAddress::from(0);
 /// ```
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "u16", into = "u16")
+)]
 pub struct PortAddress(u16);
 
+impl PortAddress {
+    /// The lowest Port-Address, 0
+    pub const MIN: PortAddress = PortAddress(0);
+
+    /// The highest Port-Address, 32_767
+    pub const MAX: PortAddress = PortAddress(0x7fff);
+
+    /// Whether the given number is a valid Port-Address, i.e. if it is not above `PortAddress::MAX`.
+    ///
+    /// ```
+    /// use artnet_protocol::PortAddress;
+    /// assert!(PortAddress::is_valid(32_767));
+    /// assert!(!PortAddress::is_valid(32_768));
+    /// ```
+    pub const fn is_valid(raw: u16) -> bool {
+        raw <= PortAddress::MAX.0
+    }
+
+    /// Create a `PortAddress` from its Net (0 - 127), Sub-Net (0 - 15) and Universe (0 - 15).
+    ///
+    /// ```
+    /// use artnet_protocol::PortAddress;
+    /// let address = PortAddress::from_parts(1, 2, 3).unwrap();
+    /// assert_eq!(u16::from(address), 0x0123);
+    /// assert_eq!(address.net(), 1);
+    /// assert_eq!(address.sub_net(), 2);
+    /// assert_eq!(address.universe(), 3);
+    /// ```
+    pub fn from_parts(net: u8, sub_net: u8, universe: u8) -> Result<Self> {
+        fn check(part: &'static str, value: u8, max: u8) -> Result<()> {
+            if value <= max {
+                Ok(())
+            } else {
+                Err(Error::InvalidPortAddressPart { part, value, max })
+            }
+        }
+        check("Net", net, 0x7f)?;
+        check("Sub-Net", sub_net, 0x0f)?;
+        check("Universe", universe, 0x0f)?;
+        Ok(PortAddress(
+            (net as u16) << 8 | (sub_net as u16) << 4 | universe as u16,
+        ))
+    }
+
+    /// Iterate over all addresses from `start` up to, but not including, `end`.
+    ///
+    /// ```
+    /// use artnet_protocol::PortAddress;
+    /// let addresses: Vec<_> = PortAddress::range(1.into(), 4.into()).collect();
+    /// assert_eq!(addresses, vec![1.into(), 2.into(), 3.into()]);
+    /// ```
+    pub fn range(start: PortAddress, end: PortAddress) -> impl Iterator<Item = PortAddress> {
+        (start.0..end.0).map(PortAddress)
+    }
+
+    /// The Port-Address for an sACN (E1.31) universe, which is the Port-Address with the same number.
+    ///
+    /// ```
+    /// use artnet_protocol::PortAddress;
+    /// let address = PortAddress::from_sacn_universe(1).unwrap();
+    /// assert_eq!(address, PortAddress::from(1));
+    /// assert_eq!(address.to_sacn_universe(), 1);
+    /// ```
+    ///
+    /// Returns an error if the universe is not from 1 to 63999, or if it is above `PortAddress::MAX`.
+    /// See the [sacn](interop/sacn/index.html) module to bridge whole packets.
+    pub fn from_sacn_universe(universe: u16) -> Result<Self> {
+        crate::interop::sacn::port_address(universe)
+    }
+
+    /// The sACN (E1.31) universe with the same number as this address, the inverse of [from_sacn_universe](#method.from_sacn_universe).
+    ///
+    /// Port-Address 0 returns 0, which is not a valid sACN universe.
+    pub fn to_sacn_universe(&self) -> u16 {
+        self.0
+    }
+
+    /// The Net of this address, which is bits 14-8
+    pub fn net(&self) -> u8 {
+        (self.0 >> 8) as u8
+    }
+
+    /// The Sub-Net of this address, which is bits 7-4
+    pub fn sub_net(&self) -> u8 {
+        ((self.0 >> 4) & 0x0f) as u8
+    }
+
+    /// The Universe of this address, which is bits 3-0
+    pub fn universe(&self) -> u8 {
+        (self.0 & 0x0f) as u8
+    }
+}
+
 // basic support for u8 literals
 impl From<u8> for PortAddress {
     fn from(value: u8) -> Self {
@@ -32,7 +134,7 @@ impl From<u8> for PortAddress {
 impl TryFrom<u16> for PortAddress {
     type Error = Error;
     fn try_from(value: u16) -> Result<Self> {
-        if value <= 32_767 {
+        if PortAddress::is_valid(value) {
             Ok(PortAddress(value))
         } else {
             Err(Error::InvalidPortAddress(value.into()))
@@ -44,7 +146,7 @@ impl TryFrom<u16> for PortAddress {
 impl TryFrom<i32> for PortAddress {
     type Error = Error;
     fn try_from(value: i32) -> Result<Self> {
-        if (0..=32767).contains(&value) {
+        if (PortAddress::MIN.0 as i32..=PortAddress::MAX.0 as i32).contains(&value) {
             Ok(PortAddress(value as u16))
         } else {
             Err(Error::InvalidPortAddress(value))
@@ -52,6 +154,42 @@ impl TryFrom<i32> for PortAddress {
     }
 }
 
+/// Formats the address as `net:sub-net:universe`
+impl fmt::Display for PortAddress {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{}:{}:{}", self.net(), self.sub_net(), self.universe())
+    }
+}
+
+/// Parses either the `net:sub-net:universe` form or a plain number:
+///
+/// ```
+/// use artnet_protocol::PortAddress;
+/// let a: PortAddress = "0:1:5".parse().unwrap();
+/// let b: PortAddress = "21".parse().unwrap();
+/// assert_eq!(a, b);
+/// assert_eq!(a.to_string(), "0:1:5");
+/// ```
+impl FromStr for PortAddress {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || Error::InvalidPortAddressString(s.to_string());
+        let parts = s
+            .split(':')
+            .map(|part| part.trim().parse::<u8>())
+            .collect::<core::result::Result<Vec<u8>, _>>();
+        match parts.as_deref() {
+            Ok([net, sub_net, universe]) => PortAddress::from_parts(*net, *sub_net, *universe),
+            _ if !s.contains(':') => s
+                .trim()
+                .parse::<i32>()
+                .map_err(|_| invalid())
+                .and_then(PortAddress::try_from),
+            _ => Err(invalid()),
+        }
+    }
+}
+
 impl From<PortAddress> for u16 {
     fn from(value: PortAddress) -> Self {
         value.0
@@ -60,18 +198,18 @@ impl From<PortAddress> for u16 {
 
 impl<T> Convertable<T> for PortAddress {
     fn from_cursor(cursor: &mut Cursor<&[u8]>) -> Result<Self> {
-        let number = cursor
-            .read_u16::<LittleEndian>()
-            .map_err(Error::CursorEof)?;
+        let number = cursor.read_u16::<LittleEndian>()?;
         PortAddress::try_from(number)
     }
 
     fn write_to_buffer(&self, buffer: &mut Vec<u8>, _context: &T) -> Result<()> {
-        buffer
-            .write_u16::<LittleEndian>(self.0)
-            .map_err(Error::CursorEof)
+        buffer.extend_from_slice(&self.0.to_le_bytes());
+        Ok(())
     }
 
+    fn serialized_len(&self) -> usize {
+        2
+    }
     #[cfg(test)]
     fn get_test_value() -> Self {
         PortAddress::from(1)
@@ -89,7 +227,7 @@ mod tests {
 
     #[test]
     fn port_address_bound_check() {
-        use std::convert::TryInto;
+        use core::convert::TryInto;
         assert!(
             PortAddress::try_from(32_768u16).is_err(),
             "u16 values over 32_767 should not convert to PortAddress succesfully"
@@ -114,4 +252,142 @@ mod tests {
         let _f: PortAddress = 256.try_into().unwrap();
         let _f: PortAddress = 32_767u16.try_into().unwrap();
     }
+
+    #[test]
+    fn port_address_limits() {
+        assert_eq!(u16::from(PortAddress::MIN), 0);
+        assert_eq!(u16::from(PortAddress::MAX), 0x7fff);
+        assert!(PortAddress::is_valid(0));
+        assert!(PortAddress::is_valid(0x7fff));
+        assert!(!PortAddress::is_valid(0x8000));
+        assert!(!PortAddress::is_valid(u16::MAX));
+
+        assert_eq!(PortAddress::try_from(0u16).unwrap(), PortAddress::MIN);
+        assert_eq!(PortAddress::try_from(0x7fffu16).unwrap(), PortAddress::MAX);
+        assert!(matches!(
+            PortAddress::try_from(0x8000u16),
+            Err(Error::InvalidPortAddress(0x8000))
+        ));
+        assert_eq!(PortAddress::MAX.to_string(), "127:15:15");
+    }
+
+    #[test]
+    fn sacn_universe_mapping() {
+        let first = PortAddress::from_sacn_universe(1).unwrap();
+        assert_eq!(first, PortAddress::from(1));
+        assert_eq!(first.to_sacn_universe(), 1);
+
+        let last = PortAddress::from_sacn_universe(32_767).unwrap();
+        assert_eq!(last, PortAddress::MAX);
+        assert_eq!(last.to_sacn_universe(), 32_767);
+
+        // Valid in sACN, but too high for a Port-Address
+        assert!(matches!(
+            PortAddress::from_sacn_universe(32_768),
+            Err(Error::InvalidPortAddress(32_768))
+        ));
+        assert!(matches!(
+            PortAddress::from_sacn_universe(63_999),
+            Err(Error::InvalidPortAddress(63_999))
+        ));
+        assert!(matches!(
+            PortAddress::from_sacn_universe(0),
+            Err(Error::InvalidSacnUniverse(0))
+        ));
+        assert!(matches!(
+            PortAddress::from_sacn_universe(64_000),
+            Err(Error::InvalidSacnUniverse(64_000))
+        ));
+    }
+
+    #[test]
+    fn port_address_display_from_str() {
+        assert_eq!("0:1:5".parse::<PortAddress>().unwrap(), 21.into());
+        assert_eq!(
+            "127:15:15".parse::<PortAddress>().unwrap(),
+            PortAddress::try_from(32_767).unwrap()
+        );
+        assert_eq!(
+            "300".parse::<PortAddress>().unwrap(),
+            PortAddress::try_from(300).unwrap()
+        );
+        assert_eq!(PortAddress::from(21).to_string(), "0:1:5");
+
+        for value in [0, 1, 15, 16, 255, 256, 0x1234, 32_767] {
+            let address = PortAddress::try_from(value).unwrap();
+            assert_eq!(address.to_string().parse::<PortAddress>().unwrap(), address);
+        }
+
+        assert!(matches!(
+            "0:16:0".parse::<PortAddress>(),
+            Err(Error::InvalidPortAddressPart { .. })
+        ));
+        assert!(matches!(
+            "32768".parse::<PortAddress>(),
+            Err(Error::InvalidPortAddress(32_768))
+        ));
+        for invalid in ["", "abc", "1:2", "1:2:3:4", "1::3", "-1:0:0", "0:1:x"] {
+            assert!(
+                matches!(
+                    invalid.parse::<PortAddress>(),
+                    Err(Error::InvalidPortAddressString(_))
+                ),
+                "{:?} should not parse",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn port_address_as_map_key() {
+        use std::collections::{BTreeMap, HashMap};
+
+        let addresses = [0x0100, 0x0001, 0x7fff, 0x0010].map(|a| PortAddress::try_from(a).unwrap());
+        let hash_map: HashMap<PortAddress, usize> = addresses.iter().copied().zip(0..).collect();
+        let btree_map: BTreeMap<PortAddress, usize> = addresses.iter().copied().zip(0..).collect();
+        for (index, address) in addresses.iter().enumerate() {
+            assert_eq!(hash_map[address], index);
+            assert_eq!(btree_map[address], index);
+        }
+        assert_eq!(hash_map.get(&PortAddress::from(2)), None);
+
+        // Ordered by the numeric 15 bit value
+        let ordered: Vec<u16> = btree_map.keys().map(|&a| a.into()).collect();
+        assert_eq!(ordered, vec![0x0001, 0x0010, 0x0100, 0x7fff]);
+    }
+
+    #[test]
+    fn port_address_range() {
+        let addresses: Vec<_> = PortAddress::range(0x000f.into(), 0x0011.into()).collect();
+        assert_eq!(addresses, vec![0x000f.into(), 0x0010.into()]);
+        assert_eq!(addresses[1].sub_net(), 1);
+        assert_eq!(addresses[1].universe(), 0);
+
+        let end = PortAddress::try_from(32_767).unwrap();
+        let start = PortAddress::try_from(32_765).unwrap();
+        assert_eq!(PortAddress::range(start, end).count(), 2);
+        assert_eq!(PortAddress::range(end, end).count(), 0);
+        assert_eq!(PortAddress::range(end, start).count(), 0);
+    }
+
+    #[test]
+    fn port_address_parts() {
+        let max = PortAddress::from_parts(127, 15, 15).unwrap();
+        assert_eq!(u16::from(max), 0x7fff);
+        assert_eq!((max.net(), max.sub_net(), max.universe()), (127, 15, 15));
+
+        let address = PortAddress::try_from(0x0a5c).unwrap();
+        assert_eq!(
+            (address.net(), address.sub_net(), address.universe()),
+            (10, 5, 12)
+        );
+        assert_eq!(PortAddress::from_parts(10, 5, 12).unwrap(), address);
+
+        let zero = PortAddress::from_parts(0, 0, 0).unwrap();
+        assert_eq!(u16::from(zero), 0);
+
+        assert!(PortAddress::from_parts(128, 0, 0).is_err());
+        assert!(PortAddress::from_parts(0, 16, 0).is_err());
+        assert!(PortAddress::from_parts(0, 0, 16).is_err());
+    }
 }
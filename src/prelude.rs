@@ -0,0 +1,19 @@
+//! The types that most users of this crate need, so they can be imported at once:
+//!
+//! ```
+//! use artnet_protocol::prelude::*;
+//!
+//! let command = ArtCommand::Output(Output::new(1, vec![255, 128, 0]));
+//! let bytes = command.write_to_buffer().unwrap();
+//! ```
+
+pub use crate::{
+    ArtCommand, ArtCommandRef, Error, OpCode, Output, PaddedData, Poll, PollReply, PortAddress,
+    Result, SequenceCounter, ARTNET_PORT, ARTNET_PROTOCOL_VERSION,
+};
+
+#[cfg(feature = "std")]
+pub use crate::Connection;
+
+#[cfg(feature = "tokio")]
+pub use crate::ArtNetSocket;
@@ -0,0 +1,181 @@
+use core::fmt;
+
+use crate::command::*;
+
+/// The Art-Net protocol version of a packet, as sent in the `ProtVerHi` and `ProtVerLo` bytes.
+///
+/// Versions are compared numerically, which makes it easy to spot packets from old nodes:
+///
+/// ```
+/// use artnet_protocol::ProtocolVersion;
+/// let version = ProtocolVersion::from([0, 13]);
+/// assert!(version < ProtocolVersion::CURRENT);
+/// assert_eq!(version.major(), 0);
+/// assert_eq!(version.minor(), 13);
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct ProtocolVersion(pub u16);
+
+impl ProtocolVersion {
+    /// The protocol version this library sends by default, see [ARTNET_PROTOCOL_VERSION](constant.ARTNET_PROTOCOL_VERSION.html)
+    pub const CURRENT: ProtocolVersion =
+        ProtocolVersion(u16::from_be_bytes(ARTNET_PROTOCOL_VERSION));
+
+    /// The high byte of the version (`ProtVerHi`)
+    pub fn major(self) -> u8 {
+        (self.0 >> 8) as u8
+    }
+
+    /// The low byte of the version (`ProtVerLo`)
+    pub fn minor(self) -> u8 {
+        self.0 as u8
+    }
+}
+
+/// Build the `version` field of a packet from its high (`ProtVerHi`) and low (`ProtVerLo`) byte.
+///
+/// ```
+/// use artnet_protocol::{version_bytes, ARTNET_PROTOCOL_VERSION};
+/// assert_eq!(version_bytes(0, 14), ARTNET_PROTOCOL_VERSION);
+/// ```
+pub const fn version_bytes(major: u8, minor: u8) -> [u8; 2] {
+    [major, minor]
+}
+
+macro_rules! protocol_version_accessors {
+    ($($packet:ty,)*) => {
+        $(
+            impl $packet {
+                /// The protocol version of this packet
+                pub fn protocol_version(&self) -> ProtocolVersion {
+                    self.version.into()
+                }
+
+                /// The protocol version of this packet as a number, e.g. 14
+                pub fn version_u16(&self) -> u16 {
+                    u16::from_be_bytes(self.version)
+                }
+            }
+        )*
+    };
+}
+
+// PollReply is not included, because its `version` is the firmware version of the node
+protocol_version_accessors! {
+    Address,
+    CommandText,
+    DataReply,
+    DataRequest,
+    DiagData,
+    Directory,
+    DirectoryReply,
+    FileFnMaster,
+    FileFnReply,
+    FileTnMaster,
+    Input,
+    IpProg,
+    IpProgReply,
+    MacMaster,
+    MacSlave,
+    Media,
+    MediaControl,
+    MediaControlReply,
+    MediaPatch,
+    Nzs,
+    Output,
+    Poll,
+    Sync,
+    TimeSync,
+    Timecode,
+    TodControl,
+    TodData,
+    TodRequest,
+    Trigger,
+    VideoData,
+    VideoPalette,
+    VideoSetup,
+}
+
+impl From<[u8; 2]> for ProtocolVersion {
+    fn from(bytes: [u8; 2]) -> Self {
+        ProtocolVersion(u16::from_be_bytes(bytes))
+    }
+}
+
+impl From<ProtocolVersion> for [u8; 2] {
+    fn from(version: ProtocolVersion) -> Self {
+        version.0.to_be_bytes()
+    }
+}
+
+impl From<u16> for ProtocolVersion {
+    fn from(version: u16) -> Self {
+        ProtocolVersion(version)
+    }
+}
+
+impl From<ProtocolVersion> for u16 {
+    fn from(version: ProtocolVersion) -> Self {
+        version.0
+    }
+}
+
+impl fmt::Display for ProtocolVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_are_big_endian() {
+        let version = ProtocolVersion::from([1, 2]);
+        assert_eq!(version, ProtocolVersion(0x0102));
+        assert_eq!(version.major(), 1);
+        assert_eq!(version.minor(), 2);
+        assert_eq!(<[u8; 2]>::from(version), [1, 2]);
+    }
+
+    #[test]
+    fn ordering_across_versions() {
+        let mut versions = vec![
+            ProtocolVersion::from([1, 0]),
+            ProtocolVersion::from([0, 14]),
+            ProtocolVersion::from([0, 0]),
+            ProtocolVersion::from([0, 255]),
+            ProtocolVersion::from([0, 13]),
+        ];
+        versions.sort();
+        assert_eq!(
+            versions,
+            vec![
+                ProtocolVersion::from([0, 0]),
+                ProtocolVersion::from([0, 13]),
+                ProtocolVersion::from([0, 14]),
+                ProtocolVersion::from([0, 255]),
+                ProtocolVersion::from([1, 0]),
+            ]
+        );
+        assert!(ProtocolVersion::from([0, 13]) < ProtocolVersion::CURRENT);
+        assert!(ProtocolVersion::from([1, 0]) > ProtocolVersion::CURRENT);
+        assert_eq!(ProtocolVersion::CURRENT, ProtocolVersion(14));
+    }
+
+    #[test]
+    fn version_u16_of_default_packets() {
+        assert_eq!(Output::default().version_u16(), 14);
+        assert_eq!(Poll::default().version_u16(), 14);
+        assert_eq!(
+            Timecode::default().protocol_version(),
+            ProtocolVersion::CURRENT
+        );
+        let old = Sync {
+            version: version_bytes(1, 2),
+            ..Sync::default()
+        };
+        assert_eq!(old.version_u16(), 0x0102);
+    }
+}
@@ -0,0 +1,178 @@
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use crate::{ArtCommand, Connection, Output, PortAddress, Result};
+
+/// The highest rate at which a DMX512 universe can be refreshed, in frames per second
+pub const DEFAULT_MAX_RATE: u32 = 44;
+
+/// A source of time for the [RateLimitedSender](struct.RateLimitedSender.html). This can be replaced to test code that depends on timing.
+pub trait Clock {
+    /// The time that has passed since a fixed point, e.g. since the clock was created
+    fn now(&self) -> Duration;
+}
+
+/// A [Clock](trait.Clock.html) for tests, which only moves when it is set. Clones share the same time.
+#[cfg(test)]
+#[derive(Clone, Default)]
+pub(crate) struct MockClock(std::rc::Rc<std::cell::Cell<Duration>>);
+
+#[cfg(test)]
+impl MockClock {
+    /// Set the time that `now` returns
+    pub(crate) fn set(&self, now: Duration) {
+        self.0.set(now);
+    }
+}
+
+#[cfg(test)]
+impl Clock for MockClock {
+    fn now(&self) -> Duration {
+        self.0.get()
+    }
+}
+
+/// A [Clock](trait.Clock.html) that uses the monotonic system time
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl Default for SystemClock {
+    fn default() -> SystemClock {
+        SystemClock {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+}
+
+/// Sends `Output` packets to a single address, but drops packets for a Port-Address that are sent faster than the maximum rate.
+///
+/// DMX512 can not be refreshed more than 44 times per second, so sending faster only floods the node.
+/// Dropped packets are not sent later. The next packet that is allowed contains the latest data anyway.
+///
+/// ```no_run
+/// use artnet_protocol::{Connection, Output, RateLimitedSender};
+///
+/// let connection = Connection::bind().unwrap();
+/// let mut sender = RateLimitedSender::new(connection, "10.0.0.2:6454".parse().unwrap());
+/// let output = Output::new(1, vec![255; 512]);
+/// if !sender.try_send(&output).unwrap() {
+///     // The previous frame for this universe was sent too recently
+/// }
+/// ```
+#[derive(Debug)]
+pub struct RateLimitedSender<C: Clock = SystemClock> {
+    connection: Connection,
+    addr: SocketAddr,
+    clock: C,
+    interval: Duration,
+    next_frame: HashMap<PortAddress, Duration>,
+    buffer: Vec<u8>,
+}
+
+impl RateLimitedSender<SystemClock> {
+    /// Send to the given address, at most 44 frames per second for each Port-Address
+    pub fn new(connection: Connection, addr: SocketAddr) -> Self {
+        RateLimitedSender::with_clock(connection, addr, SystemClock::default())
+    }
+}
+
+impl<C: Clock> RateLimitedSender<C> {
+    /// Send to the given address, using the given clock to determine when a frame is allowed
+    pub fn with_clock(connection: Connection, addr: SocketAddr, clock: C) -> Self {
+        RateLimitedSender {
+            connection,
+            addr,
+            clock,
+            interval: Duration::from_secs(1) / DEFAULT_MAX_RATE,
+            next_frame: HashMap::new(),
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Change the maximum number of frames per second that are sent for each Port-Address. A rate of 0 is treated as 1.
+    pub fn set_max_rate(&mut self, frames_per_second: u32) {
+        self.interval = Duration::from_secs(1) / frames_per_second.max(1);
+    }
+
+    /// Send the packet, unless the previous packet for the same Port-Address was sent too recently.
+    ///
+    /// Returns whether the packet was sent.
+    pub fn try_send(&mut self, output: &Output) -> Result<bool> {
+        let now = self.clock.now();
+        if let Some(&next_frame) = self.next_frame.get(&output.port_address) {
+            if now < next_frame {
+                return Ok(false);
+            }
+        }
+
+        ArtCommand::Output(output.clone()).write_to(&mut self.buffer)?;
+        self.connection.send_bytes(&self.buffer, self.addr)?;
+
+        // Schedule frames from the previous deadline, so sending at a slightly uneven rate still reaches the maximum rate
+        let next_frame = match self.next_frame.get(&output.port_address) {
+            Some(&previous) if now - previous < self.interval => previous + self.interval,
+            _ => now + self.interval,
+        };
+        self.next_frame.insert(output.port_address, next_frame);
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rate_limit::MockClock;
+
+    fn sender(clock: MockClock) -> (RateLimitedSender<MockClock>, Connection) {
+        let connection = Connection::bind_to("127.0.0.1:0").unwrap();
+        let receiver = Connection::bind_to("127.0.0.1:0").unwrap();
+        let addr = receiver.local_addr().unwrap();
+        (
+            RateLimitedSender::with_clock(connection, addr, clock),
+            receiver,
+        )
+    }
+
+    #[test]
+    fn limits_to_44_frames_per_second() {
+        let clock = MockClock::default();
+        let (mut sender, _receiver) = sender(clock.clone());
+        let output = Output::new(1, vec![1, 2, 3]);
+
+        let mut sent = 0;
+        for frame in 0..100 {
+            clock.set(Duration::from_millis(frame * 10));
+            if sender.try_send(&output).unwrap() {
+                sent += 1;
+            }
+        }
+        assert!((43..=45).contains(&sent), "sent {} frames", sent);
+    }
+
+    #[test]
+    fn port_addresses_are_limited_separately() {
+        let clock = MockClock::default();
+        let (mut sender, receiver) = sender(clock.clone());
+        sender.set_max_rate(10);
+
+        assert!(sender.try_send(&Output::new(1, vec![1])).unwrap());
+        assert!(sender.try_send(&Output::new(2, vec![1])).unwrap());
+        match receiver.recv().unwrap() {
+            (ArtCommand::Output(output), _) => assert_eq!(output.port_address, 1.into()),
+            (command, _) => panic!("Expected an Output, got {:?}", command),
+        }
+        assert!(!sender.try_send(&Output::new(1, vec![2])).unwrap());
+
+        clock.set(Duration::from_millis(100));
+        assert!(sender.try_send(&Output::new(1, vec![3])).unwrap());
+    }
+}
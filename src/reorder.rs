@@ -0,0 +1,227 @@
+use crate::{Output, PortAddress};
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+/// The number of packets that are buffered per `PortAddress` while waiting for a missing packet, see [ReorderBuffer::with_max_pending](struct.ReorderBuffer.html#method.with_max_pending)
+const DEFAULT_MAX_PENDING: usize = 8;
+
+/// Puts received `Output` packets back in the order of their `sequence` field, per `PortAddress`.
+///
+/// The sequence is incremented in the range 0x01 to 0xff and then wraps around to 0x01. Packets with sequence 0x00 have sequencing disabled, and are passed through immediately.
+/// The first packet of a `PortAddress` is always passed through, and the sequence continues from there.
+/// Packets that arrive after a later packet has already been passed through are dropped, because they contain outdated data.
+///
+/// A packet that got lost would hold back all packets after it, so when too many packets are waiting, the buffer skips ahead to the oldest waiting packet.
+///
+/// ```
+/// use artnet_protocol::{Output, ReorderBuffer};
+///
+/// let mut buffer = ReorderBuffer::default();
+/// for sequence in [1, 3, 2] {
+///     let mut output = Output::new(1, vec![sequence]);
+///     output.sequence = sequence;
+///     buffer.push(output);
+/// }
+/// let sequences: Vec<u8> = buffer.drain_ready().iter().map(|output| output.sequence).collect();
+/// assert_eq!(sequences, vec![1, 2, 3]);
+/// ```
+#[derive(Debug)]
+pub struct ReorderBuffer {
+    max_pending: usize,
+    streams: BTreeMap<PortAddress, Stream>,
+    ready: Vec<Output>,
+}
+
+#[derive(Debug)]
+struct Stream {
+    expected: u8,
+    pending: Vec<Output>,
+}
+
+impl Default for ReorderBuffer {
+    fn default() -> ReorderBuffer {
+        ReorderBuffer::with_max_pending(DEFAULT_MAX_PENDING)
+    }
+}
+
+impl ReorderBuffer {
+    /// Create a buffer that holds at most `max_pending` packets per `PortAddress` while waiting for a missing packet. The default is 8.
+    pub fn with_max_pending(max_pending: usize) -> ReorderBuffer {
+        ReorderBuffer {
+            max_pending,
+            streams: BTreeMap::new(),
+            ready: Vec::new(),
+        }
+    }
+
+    /// Add a received packet. It can be taken out with [drain_ready](#method.drain_ready) once all packets before it have been received.
+    pub fn push(&mut self, output: Output) {
+        let sequence = output.sequence;
+        if sequence == 0 {
+            self.ready.push(output);
+            return;
+        }
+        let stream = match self.streams.get_mut(&output.port_address) {
+            Some(stream) => stream,
+            None => {
+                self.streams.insert(
+                    output.port_address,
+                    Stream {
+                        expected: next_sequence(sequence),
+                        pending: Vec::new(),
+                    },
+                );
+                self.ready.push(output);
+                return;
+            }
+        };
+
+        let ahead = distance(stream.expected, sequence);
+        if ahead >= 0x80 {
+            // This packet is older than the packets that were already passed through
+            return;
+        }
+        if stream
+            .pending
+            .iter()
+            .all(|pending| pending.sequence != sequence)
+        {
+            stream.pending.push(output);
+        }
+        if stream.pending.len() > self.max_pending {
+            if let Some(oldest) = stream
+                .pending
+                .iter()
+                .map(|pending| pending.sequence)
+                .min_by_key(|&pending| distance(stream.expected, pending))
+            {
+                stream.expected = oldest;
+            }
+        }
+        stream.release(&mut self.ready);
+    }
+
+    /// Take the packets that are in order, in the order in which they became ready
+    pub fn drain_ready(&mut self) -> Vec<Output> {
+        core::mem::take(&mut self.ready)
+    }
+
+    /// Take all packets, including the ones that are still waiting for a missing packet, e.g. when the stream has stopped.
+    ///
+    /// The sequence of every `PortAddress` is reset, so the next packet is passed through immediately.
+    pub fn flush(&mut self) -> Vec<Output> {
+        let mut outputs = self.drain_ready();
+        for (_, mut stream) in core::mem::take(&mut self.streams) {
+            let expected = stream.expected;
+            stream
+                .pending
+                .sort_by_key(|pending| distance(expected, pending.sequence));
+            outputs.append(&mut stream.pending);
+        }
+        outputs
+    }
+}
+
+impl Stream {
+    /// Move the pending packets that are next in the sequence to `ready`
+    fn release(&mut self, ready: &mut Vec<Output>) {
+        while let Some(index) = self
+            .pending
+            .iter()
+            .position(|pending| pending.sequence == self.expected)
+        {
+            ready.push(self.pending.swap_remove(index));
+            self.expected = next_sequence(self.expected);
+        }
+    }
+}
+
+/// The sequence after the given one, skipping 0x00
+fn next_sequence(sequence: u8) -> u8 {
+    match sequence {
+        0xff => 0x01,
+        n => n + 1,
+    }
+}
+
+/// How far `to` is ahead of `from` in the sequence 0x01 to 0xff, where 0x01 follows 0xff
+fn distance(from: u8, to: u8) -> u8 {
+    (i16::from(to) - i16::from(from)).rem_euclid(0xff) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(port_address: u8, sequence: u8) -> Output {
+        let mut output = Output::new(port_address, vec![sequence]);
+        output.sequence = sequence;
+        output
+    }
+
+    fn sequences(outputs: &[Output]) -> Vec<u8> {
+        outputs.iter().map(|output| output.sequence).collect()
+    }
+
+    #[test]
+    fn reorders_per_port_address() {
+        let mut buffer = ReorderBuffer::default();
+        for (port_address, sequence) in [(1, 10), (1, 12), (2, 5), (1, 13), (1, 11), (2, 6)] {
+            buffer.push(output(port_address, sequence));
+        }
+        let ready = buffer.drain_ready();
+        assert_eq!(sequences(&ready), vec![10, 5, 11, 12, 13, 6]);
+        assert!(buffer.drain_ready().is_empty());
+
+        // Late and duplicate packets are dropped
+        buffer.push(output(1, 12));
+        buffer.push(output(1, 15));
+        buffer.push(output(1, 15));
+        buffer.push(output(1, 14));
+        assert_eq!(sequences(&buffer.drain_ready()), vec![14, 15]);
+    }
+
+    #[test]
+    fn wraps_from_ff_to_01() {
+        let mut buffer = ReorderBuffer::default();
+        for sequence in [0xfe, 0x01, 0xff, 0x02] {
+            buffer.push(output(1, sequence));
+        }
+        assert_eq!(
+            sequences(&buffer.drain_ready()),
+            vec![0xfe, 0xff, 0x01, 0x02]
+        );
+
+        // 0xfe is behind 0x03 after the wraparound
+        buffer.push(output(1, 0xfe));
+        buffer.push(output(1, 0x03));
+        assert_eq!(sequences(&buffer.drain_ready()), vec![0x03]);
+    }
+
+    #[test]
+    fn sequence_zero_passes_through() {
+        let mut buffer = ReorderBuffer::default();
+        buffer.push(output(1, 5));
+        buffer.push(output(1, 7));
+        buffer.push(output(1, 0));
+        buffer.push(output(1, 0));
+        assert_eq!(sequences(&buffer.drain_ready()), vec![5, 0, 0]);
+    }
+
+    #[test]
+    fn skips_lost_packets() {
+        let mut buffer = ReorderBuffer::with_max_pending(2);
+        buffer.push(output(1, 1));
+        // 2 is lost
+        buffer.push(output(1, 3));
+        buffer.push(output(1, 4));
+        assert_eq!(sequences(&buffer.drain_ready()), vec![1]);
+        buffer.push(output(1, 6));
+        assert_eq!(sequences(&buffer.drain_ready()), vec![3, 4]);
+
+        buffer.push(output(1, 8));
+        assert_eq!(sequences(&buffer.flush()), vec![6, 8]);
+        buffer.push(output(1, 100));
+        assert_eq!(sequences(&buffer.drain_ready()), vec![100]);
+    }
+}
@@ -0,0 +1,60 @@
+use crate::PortAddress;
+use alloc::collections::BTreeMap;
+
+/// Keeps track of the `sequence` field of outgoing `Output` packets, per `PortAddress`.
+///
+/// The Art-Net spec requires the sequence to be incremented in the range 0x01 to 0xff, where 0x00 means that sequencing is disabled.
+/// This counter never hands out 0x00, and wraps around from 0xff to 0x01.
+///
+/// ```
+/// use artnet_protocol::{Output, SequenceCounter};
+///
+/// let mut counter = SequenceCounter::default();
+/// let mut output = Output::new(1, vec![1, 2, 3]);
+/// output.sequence = counter.next(output.port_address);
+/// assert_eq!(output.sequence, 1);
+/// ```
+#[derive(Debug, Default)]
+pub struct SequenceCounter {
+    last: BTreeMap<PortAddress, u8>,
+}
+
+impl SequenceCounter {
+    /// Returns the next sequence number for the given address
+    pub fn next(&mut self, addr: PortAddress) -> u8 {
+        let sequence = self.last.entry(addr).or_insert(0);
+        *sequence = match *sequence {
+            0xff => 0x01,
+            n => n + 1,
+        };
+        *sequence
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wraps_around_without_zero() {
+        let mut counter = SequenceCounter::default();
+        let addr = PortAddress::from(1);
+        for expected in 1..=0xff {
+            assert_eq!(counter.next(addr), expected);
+        }
+        assert_eq!(counter.next(addr), 0x01);
+        assert_eq!(counter.next(addr), 0x02);
+    }
+
+    #[test]
+    fn independent_counters_per_address() {
+        let mut counter = SequenceCounter::default();
+        let a = PortAddress::from(1);
+        let b = PortAddress::from(2);
+        assert_eq!(counter.next(a), 1);
+        assert_eq!(counter.next(a), 2);
+        assert_eq!(counter.next(b), 1);
+        assert_eq!(counter.next(a), 3);
+        assert_eq!(counter.next(b), 2);
+    }
+}
@@ -0,0 +1,72 @@
+use std::net::SocketAddr;
+
+use tokio::net::{ToSocketAddrs, UdpSocket};
+
+use crate::connection::MAX_PACKET_LENGTH;
+use crate::{ArtCommand, Error, Result, ARTNET_PORT};
+
+/// An async UDP socket that sends and receives `ArtCommand`s. Only available with the `tokio` feature.
+///
+/// ```no_run
+/// # async fn run() -> artnet_protocol::Result<()> {
+/// use artnet_protocol::{ArtCommand, ArtNetSocket, Poll};
+///
+/// let socket = ArtNetSocket::bind().await?;
+/// socket
+///     .send(&ArtCommand::Poll(Poll::default()), "255.255.255.255:6454".parse().unwrap())
+///     .await?;
+/// let (command, addr) = socket.recv().await?;
+/// println!("Received {:?} from {}", command, addr);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ArtNetSocket {
+    socket: UdpSocket,
+}
+
+impl ArtNetSocket {
+    /// Bind to port 6454 on all interfaces, with broadcast enabled.
+    pub async fn bind() -> Result<ArtNetSocket> {
+        let socket = ArtNetSocket::bind_to(("0.0.0.0", ARTNET_PORT)).await?;
+        socket.socket.set_broadcast(true).map_err(Error::Io)?;
+        Ok(socket)
+    }
+
+    /// Bind to the given address. This is useful if the node should only listen on a single interface.
+    pub async fn bind_to(addr: impl ToSocketAddrs) -> Result<ArtNetSocket> {
+        let socket = UdpSocket::bind(addr).await.map_err(Error::Io)?;
+        Ok(ArtNetSocket { socket })
+    }
+
+    /// Wrap a socket that was already set up
+    pub fn from_socket(socket: UdpSocket) -> ArtNetSocket {
+        ArtNetSocket { socket }
+    }
+
+    /// The address that this socket is bound to
+    pub fn local_addr(&self) -> Result<SocketAddr> {
+        self.socket.local_addr().map_err(Error::Io)
+    }
+
+    /// Serialize the command and send it to the given address
+    pub async fn send(&self, command: &ArtCommand, addr: SocketAddr) -> Result<()> {
+        let bytes = command.write_to_buffer()?;
+        self.socket.send_to(&bytes, addr).await.map_err(Error::Io)?;
+        Ok(())
+    }
+
+    /// Wait for the next packet and parse it.
+    ///
+    /// Packets that are not valid Art-Net return an error, the socket can be used again afterwards.
+    pub async fn recv(&self) -> Result<(ArtCommand, SocketAddr)> {
+        let mut buffer = [0u8; MAX_PACKET_LENGTH];
+        let (length, addr) = self
+            .socket
+            .recv_from(&mut buffer)
+            .await
+            .map_err(Error::Io)?;
+        let command = ArtCommand::from_buffer(&buffer[..length])?;
+        Ok((command, addr))
+    }
+}
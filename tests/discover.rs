@@ -0,0 +1,51 @@
+#![cfg(feature = "std")]
+
+use artnet_protocol::{ArtCommand, Connection, PollReply};
+use std::net::{Ipv4Addr, UdpSocket};
+use std::thread;
+use std::time::Duration;
+
+#[test]
+fn discover_fake_node_on_loopback() {
+    let node = Connection::bind_to("127.0.0.1:0").unwrap();
+    let node_addr = node.local_addr().unwrap();
+    let responder = thread::spawn(move || {
+        let (command, addr) = node.recv().unwrap();
+        assert!(matches!(command, ArtCommand::Poll(_)));
+        let reply = ArtCommand::PollReply(Box::new(PollReply {
+            address: Ipv4Addr::LOCALHOST,
+            bind_index: 1,
+            ..PollReply::default()
+        }));
+        // Nodes may answer more than once, the duplicate is ignored
+        node.send(&reply, addr).unwrap();
+        node.send(&reply, addr).unwrap();
+    });
+
+    let controller = Connection::bind_to("127.0.0.1:0").unwrap();
+    let replies = controller
+        .discover_at(node_addr, Duration::from_millis(500))
+        .unwrap();
+    responder.join().unwrap();
+
+    assert_eq!(replies.len(), 1);
+    assert_eq!(replies[0].address, Ipv4Addr::LOCALHOST);
+    assert_eq!(replies[0].bind_index, 1);
+}
+
+#[test]
+fn discover_restores_the_read_timeout() {
+    let node = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+    let timeout = Some(Duration::from_secs(7));
+    socket.set_read_timeout(timeout).unwrap();
+    // The clone shares the socket options with the connection
+    let clone = socket.try_clone().unwrap();
+
+    let controller = Connection::from_socket(socket);
+    let replies = controller
+        .discover_at(node.local_addr().unwrap(), Duration::from_millis(50))
+        .unwrap();
+    assert!(replies.is_empty());
+    assert_eq!(clone.read_timeout().unwrap(), timeout);
+}
@@ -0,0 +1,26 @@
+use artnet_protocol::prelude::*;
+
+#[test]
+fn build_output_with_the_prelude() {
+    let address: PortAddress = 3.into();
+    let command = ArtCommand::Output(Output::new(address, vec![1, 2, 3]));
+    let bytes: Vec<u8> = command.write_to_buffer().unwrap();
+
+    let result: Result<ArtCommand> = ArtCommand::from_buffer(&bytes);
+    match result {
+        Ok(ArtCommand::Output(output)) => {
+            assert_eq!(output.port_address, address);
+            assert_eq!(output.version, ARTNET_PROTOCOL_VERSION);
+        }
+        other => panic!("Expected an Output, got {:?}", other),
+    }
+    assert!(matches!(
+        ArtCommand::from_buffer(b"GET /"),
+        Err(Error::InvalidHeader { .. })
+    ));
+}
+
+#[test]
+fn artnet_port_in_the_prelude() {
+    assert_eq!(ARTNET_PORT, 6454);
+}
@@ -0,0 +1,71 @@
+//! Property tests that serialize and parse generated packets.
+//!
+//! A packet type opts in with `roundtrip_prop!`, given a strategy that generates valid packets of that type.
+
+use artnet_protocol::{ArtCommand, Output, PortAddress, Timecode};
+use core::convert::TryFrom;
+use proptest::prelude::*;
+
+/// Check that every packet of the strategy is parsed back to the same packet.
+///
+/// For packets that are changed by serializing, e.g. padded, pass a function that returns the packet that parsing is expected to return.
+macro_rules! roundtrip_prop {
+    ($name:ident, $variant:ident, $strategy:expr) => {
+        roundtrip_prop!($name, $variant, $strategy, |packet| packet);
+    };
+    ($name:ident, $variant:ident, $strategy:expr, $expected:expr) => {
+        proptest! {
+            #[test]
+            fn $name(packet in $strategy) {
+                let bytes = ArtCommand::$variant(packet.clone()).write_to_buffer().unwrap();
+                let expected = ($expected)(packet);
+                match ArtCommand::from_buffer(&bytes).unwrap() {
+                    ArtCommand::$variant(parsed) => prop_assert_eq!(parsed, expected),
+                    command => prop_assert!(false, "Expected {}, got {:?}", stringify!($variant), command),
+                }
+            }
+        }
+    };
+}
+
+/// An `Output` with a length from 2 to 512, which is what the specification allows
+fn output() -> impl Strategy<Value = Output> {
+    (
+        any::<u8>(),
+        any::<u8>(),
+        0..=0x7fffu16,
+        prop::collection::vec(any::<u8>(), 2..=512),
+    )
+        .prop_map(|(sequence, physical, port_address, data)| {
+            let mut output = Output::new(PortAddress::try_from(port_address).unwrap(), data);
+            output.sequence = sequence;
+            output.physical = physical;
+            output
+        })
+}
+
+/// The `Output` that is parsed from a sent `Output`: odd lengths are padded with a single zero byte
+fn padded(mut output: Output) -> Output {
+    if output.data.len() % 2 == 1 {
+        output.data.as_mut().push(0);
+    }
+    output
+}
+
+/// A `Timecode` that passes validation for every frame rate
+fn timecode() -> impl Strategy<Value = Timecode> {
+    (any::<u8>(), 0..24u8, 0..60u8, 0..60u8, 0..24u8, 0..=3u8).prop_map(
+        |(stream_id, frames, seconds, minutes, hours, key_type)| Timecode {
+            stream_id,
+            frames,
+            seconds,
+            minutes,
+            hours,
+            key_type,
+            ..Timecode::default()
+        },
+    )
+}
+
+roundtrip_prop!(output_round_trip, Output, output(), padded);
+roundtrip_prop!(timecode_round_trip, OpTimeCode, timecode());
@@ -0,0 +1,74 @@
+#![cfg(feature = "serde")]
+
+use artnet_protocol::*;
+
+fn assert_round_trip(command: ArtCommand) {
+    let bytes = command.write_to_buffer().unwrap();
+    let decoded = ArtCommand::from_buffer(&bytes).unwrap();
+    let json = serde_json::to_string(&decoded).unwrap();
+    let deserialized: ArtCommand = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        deserialized.write_to_buffer().unwrap(),
+        bytes,
+        "round trip through {}",
+        json
+    );
+}
+
+#[test]
+fn output_round_trip() {
+    let packet = [
+        65, 114, 116, 45, 78, 101, 116, 0, 0, 80, 0, 14, 7, 0, 0x23, 0x01, 0, 4, 255, 128, 1, 0,
+    ];
+    let command = ArtCommand::from_buffer(&packet).unwrap();
+    let json = serde_json::to_string(&command).unwrap();
+    let deserialized: ArtCommand = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized.write_to_buffer().unwrap(), packet);
+}
+
+#[test]
+fn length_is_recomputed() {
+    let json = r#"{"Output":{"version":[0,14],"sequence":1,"physical":0,"port_address":1,"data":[1,2,3]}}"#;
+    let command: ArtCommand = serde_json::from_str(json).unwrap();
+    let bytes = command.write_to_buffer().unwrap();
+    assert_eq!(&bytes[16..18], &[0, 4]);
+}
+
+#[test]
+fn invalid_port_address_is_rejected() {
+    let json = r#"{"Output":{"version":[0,14],"sequence":1,"physical":0,"port_address":32768,"data":[1,2]}}"#;
+    assert!(serde_json::from_str::<ArtCommand>(json).is_err());
+}
+
+#[test]
+fn other_packets_round_trip() {
+    assert_round_trip(ArtCommand::Poll(Poll::default()));
+    assert_round_trip(ArtCommand::OpTimeCode(Timecode::new(
+        1,
+        2,
+        3,
+        4,
+        FrameRate::Ebu25,
+    )));
+    assert_round_trip(ArtCommand::Address(Address {
+        short_name: FixedName::new("Node").unwrap(),
+        command: AddressCommand::MergeHtp(2),
+        ..Address::default()
+    }));
+    assert_round_trip(ArtCommand::OpTrigger(Box::new(
+        Trigger::new([0xff, 0xff], 0, 0, b"GO").unwrap(),
+    )));
+    assert_round_trip(ArtCommand::PollReply(Box::default()));
+}
+
+#[test]
+fn names_are_strings() {
+    let json = serde_json::to_value(FixedName::<18>::new("Node 1").unwrap()).unwrap();
+    assert_eq!(json, serde_json::json!("Node 1"));
+
+    let name: FixedName<4> = serde_json::from_value(serde_json::json!([1, 0, 2, 0])).unwrap();
+    assert_eq!(name.as_bytes(), &[1, 0, 2, 0]);
+    let json = serde_json::to_value(name).unwrap();
+    assert_eq!(json, serde_json::json!([1, 0, 2, 0]));
+    assert!(serde_json::from_value::<FixedName<4>>(serde_json::json!("Node")).is_err());
+}
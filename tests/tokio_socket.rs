@@ -0,0 +1,27 @@
+#![cfg(feature = "tokio")]
+
+use artnet_protocol::{ArtCommand, ArtNetSocket, Output};
+
+#[tokio::test]
+async fn loopback_output() {
+    let sender = ArtNetSocket::bind_to("127.0.0.1:0").await.unwrap();
+    let receiver = ArtNetSocket::bind_to("127.0.0.1:0").await.unwrap();
+
+    let mut output = Output::new(1, vec![1, 2, 3, 4]);
+    output.sequence = 3;
+    sender
+        .send(&ArtCommand::Output(output), receiver.local_addr().unwrap())
+        .await
+        .unwrap();
+
+    let (command, addr) = receiver.recv().await.unwrap();
+    assert_eq!(addr, sender.local_addr().unwrap());
+    match command {
+        ArtCommand::Output(output) => {
+            assert_eq!(output.sequence, 3);
+            assert_eq!(output.port_address, 1.into());
+            assert_eq!(output.data.as_slice(), &[1, 2, 3, 4]);
+        }
+        command => panic!("Expected an Output, got {:?}", command),
+    }
+}
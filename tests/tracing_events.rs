@@ -0,0 +1,98 @@
+#![cfg(all(feature = "tracing", feature = "std"))]
+
+use artnet_protocol::*;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+#[derive(Debug, PartialEq)]
+struct WarnEvent {
+    fields: Vec<(String, String)>,
+}
+
+/// A subscriber that records the fields of every warn event
+#[derive(Clone, Default)]
+struct Recorder {
+    events: Arc<Mutex<Vec<WarnEvent>>>,
+}
+
+struct FieldVisitor<'a>(&'a mut Vec<(String, String)>);
+
+impl Visit for FieldVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .push((field.name().to_string(), format!("{:?}", value)));
+    }
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.push((field.name().to_string(), value.to_string()));
+    }
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+    fn event(&self, event: &Event<'_>) {
+        if *event.metadata().level() == Level::WARN {
+            let mut fields = Vec::new();
+            event.record(&mut FieldVisitor(&mut fields));
+            self.events.lock().unwrap().push(WarnEvent { fields });
+        }
+    }
+    fn enter(&self, _: &Id) {}
+    fn exit(&self, _: &Id) {}
+}
+
+fn field<'a>(event: &'a WarnEvent, name: &str) -> &'a str {
+    event
+        .fields
+        .iter()
+        .find(|(field, _)| field == name)
+        .map(|(_, value)| value.as_str())
+        .unwrap_or_else(|| panic!("No field {} in {:?}", name, event))
+}
+
+#[test]
+fn truncated_packet_emits_a_warning() {
+    let recorder = Recorder::default();
+    let bytes = ArtCommand::OpTimeCode(Timecode::default())
+        .write_to_buffer()
+        .unwrap();
+
+    tracing::subscriber::with_default(recorder.clone(), || {
+        assert!(ArtCommand::from_buffer(&bytes[..16]).is_err());
+        assert!(ArtCommand::from_buffer(&bytes).is_ok());
+    });
+
+    let events = recorder.events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(field(&events[0], "opcode"), "Some(38656)");
+    assert_eq!(field(&events[0], "length"), "16");
+    assert!(field(&events[0], "error").contains("Cursor EOF"));
+}
+
+#[test]
+fn poll_reply_with_more_than_4_ports_emits_a_warning() {
+    let recorder = Recorder::default();
+    let bytes = ArtCommand::PollReply(Box::new(PollReply {
+        num_ports: BigEndianU16(8),
+        ..PollReply::default()
+    }))
+    .write_to_buffer()
+    .unwrap();
+
+    tracing::subscriber::with_default(recorder.clone(), || {
+        assert!(ArtCommand::from_buffer(&bytes).is_ok());
+    });
+
+    let events = recorder.events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(field(&events[0], "num_ports"), "8");
+}